@@ -1,19 +1,28 @@
 mod alert_modal;
 mod avatar;
 mod button;
+mod calendar;
 mod card;
 mod checkbox;
+mod combobox;
+mod date_picker;
 mod divider;
+mod form;
 mod headline;
 mod icon;
 mod indicator;
+mod input;
 mod label;
 mod link;
+mod menu;
 mod modal;
 mod modal_layer;
+mod popover;
 mod radio;
 mod root;
 mod scrollbar;
+mod select;
+mod slider;
 mod stack;
 mod status_toast;
 mod switch;
@@ -24,19 +33,28 @@ mod tooltip;
 pub use alert_modal::*;
 pub use avatar::*;
 pub use button::*;
+pub use calendar::*;
 pub use card::*;
 pub use checkbox::*;
+pub use combobox::*;
+pub use date_picker::*;
 pub use divider::*;
+pub use form::*;
 pub use headline::*;
 pub use icon::*;
 pub use indicator::*;
+pub use input::*;
 pub use label::*;
 pub use link::*;
+pub use menu::*;
 pub use modal::*;
 pub use modal_layer::*;
+pub use popover::*;
 pub use radio::*;
 pub use root::*;
 pub use scrollbar::*;
+pub use select::*;
+pub use slider::*;
 pub use stack::*;
 pub use status_toast::*;
 pub use switch::*;
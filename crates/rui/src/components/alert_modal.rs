@@ -1,5 +1,4 @@
 use crate::{prelude::*, Button, Headline, HeadlineSize};
-use gpui::{hsla, point, BoxShadow};
 use smallvec::{smallvec, SmallVec};
 
 #[derive(IntoElement)]
@@ -51,41 +50,10 @@ impl AlertModal {
 
 impl RenderOnce for AlertModal {
     fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
-        let is_light = cx.theme().appearance.is_light();
-
         self.base
             .v_flex()
             .id(self.id)
-            .bg(cx.theme().colors.bg_elevated_surface)
-            .rounded_lg()
-            .border_1()
-            .border_color(cx.theme().colors.border_variant)
-            .shadow(smallvec![
-                BoxShadow {
-                    color: hsla(0., 0., 0., if is_light { 0.06 } else { 0.12 }),
-                    offset: point(px(0.), px(2.)),
-                    blur_radius: px(3.),
-                    spread_radius: px(0.),
-                },
-                BoxShadow {
-                    color: hsla(0., 0., 0., if is_light { 0.06 } else { 0.08 }),
-                    offset: point(px(0.), px(3.)),
-                    blur_radius: px(6.),
-                    spread_radius: px(0.),
-                },
-                BoxShadow {
-                    color: hsla(0., 0., 0., 0.04),
-                    offset: point(px(0.), px(6.)),
-                    blur_radius: px(12.),
-                    spread_radius: px(0.),
-                },
-                BoxShadow {
-                    color: hsla(0., 0., 0., if is_light { 0.04 } else { 0.12 }),
-                    offset: point(px(1.), px(1.)),
-                    blur_radius: px(0.),
-                    spread_radius: px(0.),
-                },
-            ])
+            .elevation_3(cx)
             .w(px(440.))
             .p_5()
             .child(
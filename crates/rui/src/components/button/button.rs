@@ -194,15 +194,12 @@ impl Styled for Button {
 
 impl RenderOnce for Button {
     fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
-        let is_light = cx.theme().appearance.is_light();
-        let bg = cx.theme().colors.bg;
         let color = self.color.hsla(cx);
-        let outline_color = color.opacity(0.8);
-        let soft_color = color.soft();
-        // let soft_color = color.opacity(0.3);
+        let scale = self.color.scale(cx);
         let text_color = match self.variant {
-            ButtonVariant::Solid => bg,
-            _ => color,
+            ButtonVariant::Solid => Color::foreground_on(scale.solid()),
+            ButtonVariant::Plain => color,
+            _ => scale.text(),
         };
         let loading_icon = self.loading_icon.with_animation(
             "loading",
@@ -233,10 +230,10 @@ impl RenderOnce for Button {
                 this.rounded_br_md()
             })
             .map(|this| match self.variant {
-                ButtonVariant::Solid => this.bg(color),
-                ButtonVariant::Soft => this.bg(soft_color),
-                ButtonVariant::Surface => this.bg(soft_color).border_color(color.opacity(0.4)),
-                ButtonVariant::Outline => this.border_color(outline_color),
+                ButtonVariant::Solid => this.bg(scale.solid()),
+                ButtonVariant::Soft => this.bg(scale.element_bg()),
+                ButtonVariant::Surface => this.bg(scale.subtle_bg()).border_color(scale.border()),
+                ButtonVariant::Outline => this.border_color(scale.hover_border()),
                 ButtonVariant::Ghost | ButtonVariant::Plain => this,
             })
             .map(|this| {
@@ -262,18 +259,21 @@ impl RenderOnce for Button {
             .when(!self.disabled, |this| {
                 this.cursor_pointer()
                     .hover(|this| match self.variant {
-                        ButtonVariant::Solid => {
-                            this.bg(color.opacity(if is_light { 0.9 } else { 0.7 }))
+                        ButtonVariant::Solid => this.bg(scale.solid_hover()),
+                        ButtonVariant::Soft => this.bg(scale.hover_bg()),
+                        ButtonVariant::Surface => {
+                            this.bg(scale.hover_bg()).border_color(scale.hover_border())
+                        }
+                        ButtonVariant::Outline | ButtonVariant::Ghost => {
+                            this.bg(scale.element_bg())
                         }
-                        ButtonVariant::Soft => this.bg(color.opacity(0.6)),
-                        ButtonVariant::Surface => this.bg(color.opacity(0.6)),
-                        ButtonVariant::Outline => this.bg(soft_color),
-                        ButtonVariant::Ghost => this.bg(soft_color),
                         ButtonVariant::Plain => this.opacity(0.9),
                     })
                     .active(|this| match self.variant {
-                        ButtonVariant::Solid => this.bg(color.opacity(1.)),
-                        _ => this,
+                        ButtonVariant::Solid => this.bg(scale.solid()),
+                        ButtonVariant::Soft | ButtonVariant::Surface => this.bg(scale.active_bg()),
+                        ButtonVariant::Outline | ButtonVariant::Ghost => this.bg(scale.hover_bg()),
+                        ButtonVariant::Plain => this,
                     })
             })
             .when_some(
@@ -0,0 +1,467 @@
+use std::rc::Rc;
+
+use gpui::{Context, EventEmitter, Render};
+
+use crate::{prelude::*, Button, Date, IconName, Text, Weekday};
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// The grid always has 6 weeks, so the height doesn't change between months.
+const GRID_DAYS: usize = 42;
+
+/// The selected date of a [`Calendar`], or the selected range in range mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateSelection {
+    Single(Date),
+    /// The start and the end of the range, both included, the start is never after the end.
+    Range(Date, Date),
+}
+
+impl DateSelection {
+    /// Returns a range from two dates in any order.
+    pub fn range(a: Date, b: Date) -> Self {
+        Self::Range(a.min(b), a.max(b))
+    }
+
+    pub fn start(self) -> Date {
+        match self {
+            Self::Single(date) | Self::Range(date, _) => date,
+        }
+    }
+
+    pub fn end(self) -> Date {
+        match self {
+            Self::Single(date) | Self::Range(_, date) => date,
+        }
+    }
+
+    pub fn contains(self, date: Date) -> bool {
+        (self.start()..=self.end()).contains(&date)
+    }
+
+    fn is_end(self, date: Date) -> bool {
+        date == self.start() || date == self.end()
+    }
+}
+
+/// Events emitted by a [`Calendar`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalendarEvent {
+    /// The user picked a date, or the second end of a range.
+    Change(DateSelection),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CalendarView {
+    Days,
+    Months,
+}
+
+/// A month grid to pick a date, or a range of dates.
+///
+/// The header navigates between months, and its title switches to a month grid that
+/// navigates between years. In range mode, the first click picks the start and the second
+/// the end, the range following the mouse in between.
+pub struct Calendar {
+    /// The first day of the shown month.
+    month: Date,
+    view: CalendarView,
+    selection: Option<DateSelection>,
+    /// The first picked end of a range, until the second one is picked.
+    range_start: Option<Date>,
+    hovered: Option<Date>,
+    range: bool,
+    min: Option<Date>,
+    max: Option<Date>,
+    disabled_days: Option<Rc<dyn Fn(Date) -> bool + 'static>>,
+    week_start: Weekday,
+    on_change: Option<Box<dyn Fn(&DateSelection, &mut Window, &mut App) + 'static>>,
+}
+
+impl EventEmitter<CalendarEvent> for Calendar {}
+
+impl Default for Calendar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Calendar {
+    /// Returns a calendar showing the current month.
+    pub fn new() -> Self {
+        Self {
+            month: Date::today().first_of_month(),
+            view: CalendarView::Days,
+            selection: None,
+            range_start: None,
+            hovered: None,
+            range: false,
+            min: None,
+            max: None,
+            disabled_days: None,
+            week_start: Weekday::Monday,
+            on_change: None,
+        }
+    }
+
+    /// Selects the date and shows its month.
+    pub fn selected(mut self, date: Date) -> Self {
+        self.selection = Some(DateSelection::Single(date));
+        self.month = date.first_of_month();
+        self
+    }
+
+    /// Picks a range of dates instead of a single date.
+    pub fn range(mut self, range: bool) -> Self {
+        self.range = range;
+        self
+    }
+
+    /// Selects the range in range mode and shows the month of its start.
+    pub fn selected_range(mut self, start: Date, end: Date) -> Self {
+        let selection = DateSelection::range(start, end);
+        self.range = true;
+        self.selection = Some(selection);
+        self.month = selection.start().first_of_month();
+        self
+    }
+
+    /// Disables the days before this date.
+    pub fn min(mut self, min: Date) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    /// Disables the days after this date.
+    pub fn max(mut self, max: Date) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Disables the days the function returns `true` for, weekends for example.
+    pub fn disabled_days(mut self, disabled: impl Fn(Date) -> bool + 'static) -> Self {
+        self.disabled_days = Some(Rc::new(disabled));
+        self
+    }
+
+    /// Sets the first day of the week, the first column. Default is `Weekday::Monday`.
+    pub fn week_start(mut self, week_start: Weekday) -> Self {
+        self.week_start = week_start;
+        self
+    }
+
+    /// Called when the user picks a date, or the second end of a range.
+    pub fn on_change(
+        mut self,
+        handler: impl Fn(&DateSelection, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_change = Some(Box::new(handler));
+        self
+    }
+
+    pub fn selection(&self) -> Option<DateSelection> {
+        self.selection
+    }
+
+    /// Replaces the selection, without calling `on_change`, and shows its month.
+    pub fn set_selection(&mut self, selection: Option<DateSelection>, cx: &mut Context<Self>) {
+        self.selection = selection;
+        self.range_start = None;
+        if let Some(selection) = selection {
+            self.month = selection.start().first_of_month();
+        }
+        cx.notify();
+    }
+
+    /// Picks a range of dates instead of a single date, see [`Calendar::range`].
+    pub fn set_range(&mut self, range: bool, cx: &mut Context<Self>) {
+        self.range = range;
+        self.range_start = None;
+        cx.notify();
+    }
+
+    /// Disables the days before `min` and after `max`, see [`Calendar::min`] and
+    /// [`Calendar::max`].
+    pub fn set_bounds(&mut self, min: Option<Date>, max: Option<Date>, cx: &mut Context<Self>) {
+        self.min = min;
+        self.max = max;
+        cx.notify();
+    }
+
+    /// Disables the days the function returns `true` for, see [`Calendar::disabled_days`].
+    pub fn set_disabled_days(
+        &mut self,
+        disabled: impl Fn(Date) -> bool + 'static,
+        cx: &mut Context<Self>,
+    ) {
+        self.disabled_days = Some(Rc::new(disabled));
+        cx.notify();
+    }
+
+    pub fn set_week_start(&mut self, week_start: Weekday, cx: &mut Context<Self>) {
+        self.week_start = week_start;
+        cx.notify();
+    }
+
+    /// Shows the month of the date.
+    pub fn show_month(&mut self, date: Date, cx: &mut Context<Self>) {
+        self.month = date.first_of_month();
+        self.view = CalendarView::Days;
+        cx.notify();
+    }
+
+    /// Whether the day is out of the min and max bounds, or disabled by `disabled_days`.
+    pub fn is_disabled(&self, date: Date) -> bool {
+        self.min.is_some_and(|min| date < min)
+            || self.max.is_some_and(|max| date > max)
+            || self
+                .disabled_days
+                .as_ref()
+                .is_some_and(|disabled| disabled(date))
+    }
+
+    /// Whether the month of the date has no day within the min and max bounds.
+    fn is_month_disabled(&self, month: Date) -> bool {
+        let last = month.add_months(1).add_days(-1);
+        self.min.is_some_and(|min| last < min) || self.max.is_some_and(|max| month > max)
+    }
+
+    fn pick(&mut self, date: Date, window: &mut Window, cx: &mut Context<Self>) {
+        if self.is_disabled(date) {
+            return;
+        }
+        self.month = date.first_of_month();
+        let selection = match self.range_start.take() {
+            _ if !self.range => DateSelection::Single(date),
+            Some(start) => DateSelection::range(start, date),
+            None => {
+                self.range_start = Some(date);
+                self.hovered = Some(date);
+                cx.notify();
+                return;
+            }
+        };
+        self.selection = Some(selection);
+        if let Some(on_change) = self.on_change.as_ref() {
+            on_change(&selection, window, cx);
+        }
+        cx.emit(CalendarEvent::Change(selection));
+        cx.notify();
+    }
+
+    fn navigate(&mut self, months: i32, cx: &mut Context<Self>) {
+        self.month = self.month.add_months(months);
+        cx.notify();
+    }
+
+    /// The selection to show, following the mouse while a range is being picked.
+    fn shown_selection(&self) -> Option<DateSelection> {
+        match self.range_start {
+            Some(start) => Some(DateSelection::range(start, self.hovered.unwrap_or(start))),
+            None => self.selection,
+        }
+    }
+
+    fn render_header(
+        &self,
+        title: SharedString,
+        months: i32,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let previous = self.month.add_months(-months);
+        let next = self.month.add_months(months);
+        let (previous_disabled, next_disabled) = match self.view {
+            CalendarView::Days => (
+                self.is_month_disabled(previous),
+                self.is_month_disabled(next),
+            ),
+            CalendarView::Months => (
+                self.min.is_some_and(|min| previous.year() < min.year()),
+                self.max.is_some_and(|max| next.year() > max.year()),
+            ),
+        };
+
+        h_flex()
+            .justify_between()
+            .child(
+                Button::new("previous")
+                    .icon(IconName::ChevronLeft)
+                    .ghost()
+                    .size(Size::Small)
+                    .disabled(previous_disabled)
+                    .on_click(cx.listener(move |this, _, _, cx| this.navigate(-months, cx))),
+            )
+            .child(
+                Button::new("title")
+                    .text(title)
+                    .ghost()
+                    .size(Size::Small)
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.view = match this.view {
+                            CalendarView::Days => CalendarView::Months,
+                            CalendarView::Months => CalendarView::Days,
+                        };
+                        cx.notify();
+                    })),
+            )
+            .child(
+                Button::new("next")
+                    .icon(IconName::ChevronRight)
+                    .ghost()
+                    .size(Size::Small)
+                    .disabled(next_disabled)
+                    .on_click(cx.listener(move |this, _, _, cx| this.navigate(months, cx))),
+            )
+    }
+
+    fn render_days(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let colors = cx.theme().colors;
+        let cell = rems(2.25);
+        let today = Date::today();
+        let start = grid_start(self.month, self.week_start);
+        let selection = self.shown_selection();
+        let picking = self.range_start.is_some();
+
+        let weekdays = h_flex().children((0..7).map(|ix| {
+            div()
+                .w(cell)
+                .flex()
+                .justify_center()
+                .text_xs()
+                .text_color(colors.text_muted)
+                .child(self.week_start.add_days(ix).short_name())
+        }));
+
+        let weeks = (0..GRID_DAYS / 7).map(|week| {
+            h_flex().children((0..7).map(|weekday| {
+                let ix = week * 7 + weekday;
+                let date = start.add_days(ix as i64);
+                let is_end = selection.is_some_and(|selection| selection.is_end(date));
+                let in_range = selection.is_some_and(|selection| selection.contains(date));
+                let outside = date.month() != self.month.month();
+                let text = Text::new(date.day().to_string())
+                    .when(outside && !is_end, |this| this.color(colors.text_muted));
+
+                let day = Button::new(("day", ix))
+                    .text(text)
+                    .ghost()
+                    .size(Size::Custom(cell))
+                    .disabled(self.is_disabled(date))
+                    .map(|this| {
+                        if is_end {
+                            this.solid().primary()
+                        } else if in_range {
+                            this.bg(colors.primary.opacity(0.15))
+                        } else if date == today {
+                            this.border_color(colors.primary)
+                        } else {
+                            this
+                        }
+                    })
+                    .on_click(cx.listener(move |this, _, window, cx| this.pick(date, window, cx)));
+
+                div()
+                    .id(("day-cell", ix))
+                    .when(picking, |this| {
+                        this.on_hover(cx.listener(move |this, hovered: &bool, _, cx| {
+                            if *hovered {
+                                this.hovered = Some(date);
+                                cx.notify();
+                            }
+                        }))
+                    })
+                    .child(day)
+            }))
+        });
+
+        v_flex().gap_1().child(weekdays).children(weeks)
+    }
+
+    fn render_months(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let year = self.month.year();
+        let rows = MONTH_NAMES.chunks(3).enumerate().map(|(row, names)| {
+            h_flex()
+                .gap_1()
+                .children(names.iter().enumerate().map(|(column, name)| {
+                    let month = (row * 3 + column) as u32 + 1;
+                    let date = Date::new(year, month, 1).unwrap_or(self.month);
+                    Button::new(("month", month as usize))
+                        .text(name[..3].to_string())
+                        .ghost()
+                        .size(Size::Small)
+                        .flex_1()
+                        .disabled(self.is_month_disabled(date))
+                        .when(month == self.month.month(), |this| this.solid().primary())
+                        .on_click(cx.listener(move |this, _, _, cx| this.show_month(date, cx)))
+                }))
+        });
+
+        v_flex().gap_1().children(rows)
+    }
+}
+
+/// The first day of the grid of the month, the week start on or before the first day.
+fn grid_start(month: Date, week_start: Weekday) -> Date {
+    let first = month.first_of_month();
+    let offset = (first.weekday().days_from_monday() + 7 - week_start.days_from_monday()) % 7;
+    first.add_days(-(offset as i64))
+}
+
+impl Render for Calendar {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let colors = cx.theme().colors;
+
+        v_flex()
+            .w(rems(2.25 * 7.))
+            .gap_2()
+            .text_sm()
+            .text_color(colors.text)
+            .map(|this| match self.view {
+                CalendarView::Days => {
+                    let title = format!(
+                        "{} {}",
+                        MONTH_NAMES[self.month.month() as usize - 1],
+                        self.month.year()
+                    );
+                    this.child(self.render_header(title.into(), 1, cx))
+                        .child(self.render_days(cx))
+                }
+                CalendarView::Months => this
+                    .child(self.render_header(self.month.year().to_string().into(), 12, cx))
+                    .child(self.render_months(cx)),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_start() {
+        // March 2025 starts on a Saturday.
+        let march = Date::new(2025, 3, 1).unwrap();
+        assert_eq!(
+            grid_start(march, Weekday::Monday),
+            Date::new(2025, 2, 24).unwrap()
+        );
+        assert_eq!(
+            grid_start(march, Weekday::Sunday),
+            Date::new(2025, 2, 23).unwrap()
+        );
+        assert_eq!(grid_start(march, Weekday::Saturday), march);
+    }
+}
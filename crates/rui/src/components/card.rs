@@ -1,4 +1,4 @@
-use crate::{box_shadow, prelude::*, Direction, Text};
+use crate::{prelude::*, Direction, ElevationIndex, Text};
 use gpui::{FontWeight, Pixels};
 
 #[derive(IntoElement)]
@@ -10,8 +10,8 @@ pub struct Card {
     radius: Pixels,
     border_width: Pixels,
     border_color: Option<Hsla>,
-    shadow: bool,
-    shadow_hover: bool,
+    elevation: Option<ElevationIndex>,
+    hover_elevation: Option<ElevationIndex>,
 }
 
 impl Card {
@@ -24,8 +24,8 @@ impl Card {
             radius: px(4.0),
             border_width: px(1.0),
             border_color: None,
-            shadow: false,
-            shadow_hover: false,
+            elevation: None,
+            hover_elevation: None,
         }
     }
 
@@ -75,6 +75,18 @@ impl Card {
         self.border_color = Some(color.into());
         self
     }
+
+    /// Renders the card at an elevation level, with the background and shadow of the theme.
+    pub fn elevation(mut self, elevation: ElevationIndex) -> Self {
+        self.elevation = Some(elevation);
+        self
+    }
+
+    /// Raises the shadow of the card to an elevation level when hovered.
+    pub fn hover_elevation(mut self, elevation: ElevationIndex) -> Self {
+        self.hover_elevation = Some(elevation);
+        self
+    }
 }
 
 impl Styled for Card {
@@ -102,27 +114,12 @@ impl RenderOnce for Card {
                 self.border_color
                     .unwrap_or(cx.theme().colors.border_variant),
             )
-            .when(self.shadow, |this| {
-                // this.shadow_sm()
-                this.shadow(smallvec::smallvec![box_shadow(
-                    0.,
-                    4.,
-                    20.,
-                    0.,
-                    crate::rgba(0, 0, 0, 0.1)
-                )])
+            .when_some(self.elevation, |this, elevation| {
+                this.bg(elevation.bg(cx)).shadow(elevation.shadow(cx))
             })
-            .when(self.shadow_hover, |this| {
-                this.hover(|this| {
-                    // this.shadow_md()
-                    this.shadow(smallvec::smallvec![box_shadow(
-                        0.,
-                        8.,
-                        30.,
-                        0.,
-                        crate::rgba(0, 0, 0, 0.2)
-                    )])
-                })
+            .when_some(self.hover_elevation, |this, elevation| {
+                let shadow = elevation.shadow(cx);
+                this.hover(|this| this.shadow(shadow))
             })
     }
 }
@@ -1,7 +1,5 @@
 mod checkbox;
 mod checkbox_group;
-// mod checkbox_group_propagation;
 
 pub use checkbox::*;
 pub use checkbox_group::*;
-// pub use checkbox_group_propagation::*;
@@ -1,4 +1,4 @@
-use crate::{prelude::*, Icon, IconName, IconSize, Text};
+use crate::{prelude::*, Color, Icon, IconName, IconSize, Text};
 use gpui::{px, AnyView};
 
 /// # Checkbox
@@ -69,8 +69,8 @@ impl Styled for Checkbox {
 
 impl RenderOnce for Checkbox {
     fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
-        let thumb_color = cx.theme().colors.bg;
-        // let thumb_color = gpui::white();
+        let scale = Color::Primary.scale(cx);
+        let thumb_color = Color::foreground_on(scale.solid());
         let group_id = format!("checkbox_group_{:?}", self.id);
         let icon = match self.state {
             ToggleState::Selected => Some(
@@ -88,12 +88,16 @@ impl RenderOnce for Checkbox {
 
         let bg_color = match self.state {
             ToggleState::Unselected => cx.theme().colors.bg,
-            _ => cx.theme().colors.primary,
+            _ => scale.solid(),
         };
 
         let border_color = match self.state {
             ToggleState::Unselected => cx.theme().colors.border,
-            _ => cx.theme().colors.primary,
+            _ => scale.solid(),
+        };
+        let hover_border_color = match self.state {
+            ToggleState::Unselected => scale.hover_border(),
+            _ => scale.solid_hover(),
         };
 
         let checkbox = h_flex()
@@ -118,7 +122,7 @@ impl RenderOnce for Checkbox {
                         false => this
                             .border_color(border_color)
                             .group_hover(group_id.clone(), |this| {
-                                this.border_color(cx.theme().colors.element_hover)
+                                this.border_color(hover_border_color)
                             }),
                     })
                     .children(icon),
@@ -1,28 +1,59 @@
-use super::Checkbox;
-use crate::{prelude::*, Direction};
-use std::cell::RefCell;
 use std::rc::Rc;
 
+use super::Checkbox;
+use crate::{prelude::*, Direction, Text};
+
+/// An option of a [`CheckboxGroup`].
+pub struct CheckboxOption<T> {
+    value: T,
+    label: SharedString,
+    disabled: bool,
+}
+
+impl<T> CheckboxOption<T> {
+    pub fn new(value: T, label: impl Into<SharedString>) -> Self {
+        Self {
+            value,
+            label: label.into(),
+            disabled: false,
+        }
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
 /// A Checkbox group element.
+///
+/// With [`CheckboxGroup::min`] and [`CheckboxGroup::max`], the options that would break the
+/// limit are disabled: the checked ones at the minimum, the unchecked ones at the maximum.
 #[derive(IntoElement)]
-pub struct CheckboxGroup {
-    base: Div,
-    checkboxes: Vec<Checkbox>,
+pub struct CheckboxGroup<T: 'static> {
+    id: ElementId,
+    options: Vec<CheckboxOption<T>>,
     direction: Direction,
-    checked_indexes: Vec<usize>,
+    checked: Vec<T>,
     disabled: bool,
-    on_change: Option<Rc<dyn Fn(&Vec<usize>, &mut Window, &mut App) + 'static>>,
+    min: usize,
+    max: Option<usize>,
+    select_all: Option<SharedString>,
+    on_change: Option<Rc<dyn Fn(&[T], &mut Window, &mut App) + 'static>>,
 }
 
-impl CheckboxGroup {
-    pub fn new() -> Self {
+impl<T: Clone + PartialEq + 'static> CheckboxGroup<T> {
+    pub fn new(id: impl Into<ElementId>) -> Self {
         Self {
-            base: div(),
-            on_change: None,
+            id: id.into(),
+            options: Vec::new(),
             direction: Direction::Horizontal,
-            checked_indexes: vec![],
+            checked: Vec::new(),
             disabled: false,
-            checkboxes: vec![],
+            min: 0,
+            max: None,
+            select_all: None,
+            on_change: None,
         }
     }
 
@@ -34,23 +65,27 @@ impl CheckboxGroup {
 
     /// Sets the direction of the Checkbox group to vertical.
     /// This is a convenience method for setting the direction to vertical without passing an argument.
-    pub fn vertical(mut self) -> Self {
+    pub fn direction_vertical(mut self) -> Self {
         self.direction = Direction::Vertical;
         self
     }
 
-    /// Listen to the change event.
-    pub fn on_change(
-        mut self,
-        handler: impl Fn(&Vec<usize>, &mut Window, &mut App) + 'static,
-    ) -> Self {
+    /// Sets the direction of the Checkbox group to horizontal.
+    /// This is a convenience method for setting the direction to horizontal without passing an argument.
+    pub fn direction_horizontal(mut self) -> Self {
+        self.direction = Direction::Horizontal;
+        self
+    }
+
+    /// Listen to the change event, with the values of the checked options in option order.
+    pub fn on_change(mut self, handler: impl Fn(&[T], &mut Window, &mut App) + 'static) -> Self {
         self.on_change = Some(Rc::new(handler));
         self
     }
 
-    /// Set the selected index.
-    pub fn checked_indexes(mut self, indexes: Vec<usize>) -> Self {
-        self.checked_indexes = indexes;
+    /// Set the checked values.
+    pub fn checked(mut self, values: impl IntoIterator<Item = T>) -> Self {
+        self.checked = values.into_iter().collect();
         self
     }
 
@@ -60,56 +95,179 @@ impl CheckboxGroup {
         self
     }
 
-    /// Add a child Checkbox element.
-    pub fn child(mut self, child: impl Into<Checkbox>) -> Self {
-        self.checkboxes.push(child.into());
+    /// Set the minimum number of checked options. Default is 0.
+    pub fn min(mut self, min: usize) -> Self {
+        self.min = min;
+        self
+    }
+
+    /// Set the maximum number of checked options. Default is no limit.
+    pub fn max(mut self, max: usize) -> Self {
+        self.max = Some(max);
         self
     }
 
-    /// Add multiple child Checkbox elements.
-    pub fn children(mut self, children: impl IntoIterator<Item = impl Into<Checkbox>>) -> Self {
-        self.checkboxes.extend(children.into_iter().map(Into::into));
+    /// Adds a parent checkbox above the options that checks or unchecks all of them.
+    ///
+    /// It is indeterminate while only some options are checked. Disabled options keep their
+    /// state, and the parent is disabled when toggling would break the min or max limit.
+    pub fn select_all(mut self, label: impl Into<SharedString>) -> Self {
+        self.select_all = Some(label.into());
+        self
+    }
+
+    /// Add an option.
+    pub fn option(mut self, option: CheckboxOption<T>) -> Self {
+        self.options.push(option);
+        self
+    }
+
+    /// Add multiple options.
+    pub fn options(mut self, options: impl IntoIterator<Item = CheckboxOption<T>>) -> Self {
+        self.options.extend(options);
         self
     }
 }
 
-impl RenderOnce for CheckboxGroup {
+/// Returns whether the option at `ix` can be toggled without breaking the limits.
+fn can_toggle(checked: &[bool], ix: usize, min: usize, max: Option<usize>) -> bool {
+    let count = checked.iter().filter(|checked| **checked).count();
+    if checked[ix] {
+        count > min
+    } else {
+        max.is_none_or(|max| count < max)
+    }
+}
+
+/// Returns the state of the select-all checkbox and the checked flags after clicking it, or
+/// `None` for the flags when the click would break the limits.
+fn toggle_all(
+    checked: &[bool],
+    enabled: &[bool],
+    min: usize,
+    max: Option<usize>,
+) -> (ToggleState, Option<Vec<bool>>) {
+    let mut enabled_checked = checked.iter().zip(enabled).filter(|(_, enabled)| **enabled);
+    let any = enabled_checked.clone().any(|(checked, _)| *checked);
+    let all = enabled.contains(&true) && enabled_checked.all(|(checked, _)| *checked);
+    let state = ToggleState::from_any_and_all(any, all);
+
+    let next: Vec<bool> = checked
+        .iter()
+        .zip(enabled)
+        .map(|(checked, enabled)| if *enabled { !all } else { *checked })
+        .collect();
+    let count = next.iter().filter(|checked| **checked).count();
+    let valid = next != checked && count >= min && max.is_none_or(|max| count <= max);
+    (state, valid.then_some(next))
+}
+
+impl<T: Clone + PartialEq + 'static> RenderOnce for CheckboxGroup<T> {
     fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+        let values: Vec<T> = self.options.iter().map(|o| o.value.clone()).collect();
+        let checked: Vec<bool> = values.iter().map(|v| self.checked.contains(v)).collect();
+        let enabled: Vec<bool> = self
+            .options
+            .iter()
+            .map(|o| !self.disabled && !o.disabled)
+            .collect();
+        let (min, max) = (self.min, self.max);
         let on_change = self.on_change;
-        let disabled = self.disabled;
-        let checked_indexes = Rc::new(RefCell::new(self.checked_indexes));
-
-        let children = self
-            .checkboxes
-            .into_iter()
-            .enumerate()
-            .map(|(index, checkbox)| {
-                let checked_indexes = Rc::clone(&checked_indexes);
-                let checked = checked_indexes.borrow().contains(&index);
-
-                checkbox.disabled(disabled).checked(checked).when_some(
-                    on_change.clone(),
-                    move |this, on_change| {
-                        this.on_click(move |_, window, cx| {
-                            let mut checked_indexes = checked_indexes.borrow_mut();
-                            if let Some(i) = checked_indexes.iter().position(|&i| i == index) {
-                                checked_indexes.remove(i);
-                            } else {
-                                checked_indexes.push(index);
-                            }
-
-                            on_change(&checked_indexes, window, cx);
-                        })
-                    },
-                )
-            });
-
-        self.base
-            .map(|this| match self.direction {
-                Direction::Vertical => this.v_flex(),
-                Direction::Horizontal => this.h_flex().flex_wrap(),
-            })
+
+        // Calls `on_change` with the values of the options checked in `next`.
+        let emit = Rc::new(move |next: &[bool], window: &mut Window, cx: &mut App| {
+            if let Some(on_change) = on_change.as_ref() {
+                let values: Vec<T> = values
+                    .iter()
+                    .zip(next)
+                    .filter(|(_, checked)| **checked)
+                    .map(|(value, _)| value.clone())
+                    .collect();
+                on_change(&values, window, cx);
+            }
+        });
+
+        let select_all = self.select_all.map(|label| {
+            let (state, next) = toggle_all(&checked, &enabled, min, max);
+            let emit = emit.clone();
+            Checkbox::new("select-all")
+                .text(Text::new(label))
+                .checked(state)
+                .disabled(next.is_none())
+                .when_some(next, |this, next| {
+                    this.on_click(move |_, window, cx| emit(&next, window, cx))
+                })
+        });
+
+        let checked = Rc::new(checked);
+        let children = self.options.into_iter().enumerate().map(|(ix, option)| {
+            let disabled = !enabled[ix] || !can_toggle(&checked, ix, min, max);
+            let checked = checked.clone();
+            let emit = emit.clone();
+            Checkbox::new(("checkbox", ix))
+                .text(Text::new(option.label))
+                .checked(checked[ix])
+                .disabled(disabled)
+                .on_click(move |_, window, cx| {
+                    let mut next = checked.to_vec();
+                    next[ix] = !next[ix];
+                    emit(&next, window, cx);
+                })
+        });
+
+        v_flex()
+            .id(self.id.clone())
             .gap_3()
-            .children(children)
+            .children(select_all)
+            .child(
+                div()
+                    .map(|this| match self.direction {
+                        Direction::Vertical => this.v_flex(),
+                        Direction::Horizontal => this.h_flex().flex_wrap(),
+                    })
+                    .gap_3()
+                    .children(children),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_can_toggle() {
+        let checked = [true, true, false];
+        assert!(can_toggle(&checked, 0, 0, None));
+        assert!(!can_toggle(&checked, 0, 2, None));
+        assert!(can_toggle(&checked, 2, 0, Some(3)));
+        assert!(!can_toggle(&checked, 2, 0, Some(2)));
+    }
+
+    #[test]
+    fn test_toggle_all() {
+        let enabled = [true, true, true];
+        let (state, next) = toggle_all(&[false, false, false], &enabled, 0, None);
+        assert_eq!(state, ToggleState::Unselected);
+        assert_eq!(next, Some(vec![true, true, true]));
+
+        let (state, next) = toggle_all(&[true, false, false], &enabled, 0, None);
+        assert_eq!(state, ToggleState::Indeterminate);
+        assert_eq!(next, Some(vec![true, true, true]));
+
+        let (state, next) = toggle_all(&[true, true, true], &enabled, 0, None);
+        assert_eq!(state, ToggleState::Selected);
+        assert_eq!(next, Some(vec![false, false, false]));
+
+        // Disabled options keep their state.
+        let (state, next) = toggle_all(&[true, true, true], &[true, false, true], 0, None);
+        assert_eq!(state, ToggleState::Selected);
+        assert_eq!(next, Some(vec![false, true, false]));
+
+        // The limits block the toggle.
+        let (_, next) = toggle_all(&[true, true, true], &enabled, 1, None);
+        assert_eq!(next, None);
+        let (_, next) = toggle_all(&[true, false, false], &enabled, 0, Some(2));
+        assert_eq!(next, None);
     }
 }
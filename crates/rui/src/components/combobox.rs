@@ -0,0 +1,683 @@
+use std::{cmp::Reverse, future::Future, ops::Range, time::Duration};
+
+use gpui::{
+    actions, canvas, percentage, uniform_list, Animation, AnimationExt, App, Context, DismissEvent,
+    Entity, EventEmitter, FocusHandle, Focusable, FontWeight, HighlightStyle, KeyBinding,
+    MouseButton, Pixels, Render, ScrollStrategy, StyledText, Subscription, Task, Transformation,
+    UniformListScrollHandle, Window,
+};
+
+use crate::{
+    fuzzy_match, prelude::*, ControlledPopover, Icon, IconName, IconSize, InputEvent, TextInput,
+};
+
+actions!(combobox, [HighlightPrevious, HighlightNext, Dismiss]);
+
+const CONTEXT: &str = "Combobox";
+
+/// How long typing pauses before the options are loaded from a provider.
+const LOAD_DELAY: Duration = Duration::from_millis(150);
+const ROW_HEIGHT: Pixels = px(32.);
+/// The menu scrolls past this many options.
+const MAX_VISIBLE_ROWS: usize = 8;
+
+type Provider<T> = Box<dyn Fn(SharedString, &App) -> Task<Vec<ComboboxOption<T>>>>;
+
+/// An option of a [`Combobox`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComboboxOption<T> {
+    value: T,
+    label: SharedString,
+    disabled: bool,
+}
+
+impl<T> ComboboxOption<T> {
+    pub fn new(value: T, label: impl Into<SharedString>) -> Self {
+        Self {
+            value,
+            label: label.into(),
+            disabled: false,
+        }
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    pub fn label(&self) -> &SharedString {
+        &self.label
+    }
+}
+
+/// Events emitted by a [`Combobox`].
+#[derive(Debug, Clone)]
+pub enum ComboboxEvent<T> {
+    /// The selection changed, with all the selected values.
+    Change(Vec<T>),
+}
+
+/// An option matching the query, with the ranges of its label to highlight.
+struct OptionMatch {
+    ix: usize,
+    ranges: Vec<Range<usize>>,
+}
+
+/// A text input filtering a list of options as the user types.
+///
+/// Options are matched with [`fuzzy_match`] against the query, best matches first and with
+/// the matched chars highlighted. Only the visible options are rendered, so the list can hold
+/// thousands of them. With [`Combobox::provider`], options are instead loaded for the query
+/// on the background executor while a spinner is shown. The menu closes on escape, a click
+/// outside or when the input loses the focus.
+///
+/// In [`Combobox::multiple`] mode, the selected options are shown as removable chips below
+/// the input and the menu stays open to pick more.
+///
+/// Call [`bind_combobox_keys`] and [`TextInput::bind_keys`] once at startup to bind the
+/// navigation and editing keys.
+pub struct Combobox<T> {
+    input: Entity<TextInput>,
+    options: Vec<ComboboxOption<T>>,
+    matches: Vec<OptionMatch>,
+    selected: Vec<ComboboxOption<T>>,
+    /// The index of the highlighted option, in `matches`.
+    highlighted: Option<usize>,
+    open: bool,
+    multiple: bool,
+    placeholder: SharedString,
+    size: Size,
+    disabled: bool,
+    provider: Option<Provider<T>>,
+    loading: bool,
+    on_change: Option<Box<dyn Fn(&[T], &mut Window, &mut App) + 'static>>,
+    scroll_handle: UniformListScrollHandle,
+    field_width: Pixels,
+    _load_task: Option<Task<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl<T: 'static> EventEmitter<ComboboxEvent<T>> for Combobox<T> {}
+
+impl<T: 'static> Focusable for Combobox<T> {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.input.focus_handle(cx)
+    }
+}
+
+/// Binds the navigation keys of all the [`Combobox`]es, whatever their value type.
+pub fn bind_combobox_keys(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("up", HighlightPrevious, Some(CONTEXT)),
+        KeyBinding::new("down", HighlightNext, Some(CONTEXT)),
+        KeyBinding::new("escape", Dismiss, Some(CONTEXT)),
+    ]);
+}
+
+impl<T: Clone + PartialEq + 'static> Combobox<T> {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let input = cx.new(|cx| TextInput::new(cx).suffix(IconName::ChevronDown));
+        let subscriptions = vec![
+            cx.subscribe_in(&input, window, |this, _, event, window, cx| match event {
+                InputEvent::Change(_) => this.query_changed(cx),
+                InputEvent::Submit(_) => this.confirm(window, cx),
+            }),
+        ];
+        // The builders are applied once `new` returns, push them to the input.
+        cx.defer(|this, cx| this.sync_input(cx));
+
+        Self {
+            input,
+            options: Vec::new(),
+            matches: Vec::new(),
+            selected: Vec::new(),
+            highlighted: None,
+            open: false,
+            multiple: false,
+            placeholder: SharedString::default(),
+            size: Size::default(),
+            disabled: false,
+            provider: None,
+            loading: false,
+            on_change: None,
+            scroll_handle: UniformListScrollHandle::new(),
+            field_width: px(0.),
+            _load_task: None,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    pub fn option(mut self, option: ComboboxOption<T>) -> Self {
+        self.options.push(option);
+        self
+    }
+
+    pub fn options(mut self, options: impl IntoIterator<Item = ComboboxOption<T>>) -> Self {
+        self.options.extend(options);
+        self
+    }
+
+    /// Loads the options matching the query instead, on the background executor.
+    ///
+    /// The provider is called when the menu opens and after typing pauses. The options it
+    /// returns are still ranked and highlighted with [`fuzzy_match`].
+    pub fn provider<F, Fut>(mut self, provider: F) -> Self
+    where
+        F: Fn(SharedString) -> Fut + 'static,
+        Fut: Future<Output = Vec<ComboboxOption<T>>> + Send + 'static,
+        T: Send,
+    {
+        self.provider = Some(Box::new(move |query, cx| {
+            cx.background_executor().spawn(provider(query))
+        }));
+        self
+    }
+
+    /// Allows selecting several options, shown as chips below the input.
+    pub fn multiple(mut self, multiple: bool) -> Self {
+        self.multiple = multiple;
+        self
+    }
+
+    pub fn placeholder(mut self, placeholder: impl Into<SharedString>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    pub fn size(mut self, size: Size) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Called with all the selected values when the user changes the selection.
+    pub fn on_change(mut self, handler: impl Fn(&[T], &mut Window, &mut App) + 'static) -> Self {
+        self.on_change = Some(Box::new(handler));
+        self
+    }
+
+    pub fn selected_values(&self) -> Vec<T> {
+        self.selected
+            .iter()
+            .map(|option| option.value.clone())
+            .collect()
+    }
+
+    /// Replaces the selection, without calling `on_change`.
+    pub fn set_selected(
+        &mut self,
+        selected: impl IntoIterator<Item = ComboboxOption<T>>,
+        cx: &mut Context<Self>,
+    ) {
+        self.selected = selected.into_iter().collect();
+        if !self.multiple {
+            self.selected.truncate(1);
+            self.reset_query(cx);
+        }
+        self.sync_input(cx);
+        cx.notify();
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.loading
+    }
+
+    /// The placeholder of the input, the selected label while the query is edited.
+    fn input_placeholder(&self) -> SharedString {
+        match self.selected.len() {
+            0 => self.placeholder.clone(),
+            _ if !self.multiple => self.selected[0].label.clone(),
+            _ => SharedString::default(),
+        }
+    }
+
+    /// Pushes the size, the disabled state and the placeholder to the input, called again when
+    /// the selection changes the placeholder.
+    fn sync_input(&mut self, cx: &mut Context<Self>) {
+        let (size, disabled) = (self.size, self.disabled);
+        let placeholder = self.input_placeholder();
+        self.input.update(cx, |input, cx| {
+            input.set_size(size, cx);
+            input.set_disabled(disabled, cx);
+            input.set_placeholder(placeholder, cx);
+        });
+    }
+
+    fn is_selected(&self, value: &T) -> bool {
+        self.selected.iter().any(|option| &option.value == value)
+    }
+
+    fn query_changed(&mut self, cx: &mut Context<Self>) {
+        if self.disabled {
+            return;
+        }
+        self.open = true;
+        if self.provider.is_some() {
+            self.load(cx);
+        } else {
+            self.filter(cx);
+        }
+    }
+
+    fn load(&mut self, cx: &mut Context<Self>) {
+        self.loading = true;
+        cx.notify();
+        self._load_task = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(LOAD_DELAY).await;
+            let Ok(Some(task)) = this.update(cx, |this, cx| {
+                let query = this.input.read(cx).text();
+                this.provider.as_ref().map(|provider| provider(query, cx))
+            }) else {
+                return;
+            };
+            let options = task.await;
+            this.update(cx, |this, cx| {
+                this.options = options;
+                this.loading = false;
+                this.filter(cx);
+            })
+            .ok();
+        }));
+    }
+
+    fn filter(&mut self, cx: &mut Context<Self>) {
+        let query = self.input.read(cx).text();
+        let mut matches: Vec<_> = self
+            .options
+            .iter()
+            .enumerate()
+            .filter_map(|(ix, option)| {
+                let m = fuzzy_match(&query, &option.label)?;
+                Some((m.score, ix, m.ranges(&option.label)))
+            })
+            .collect();
+        matches.sort_by_key(|(score, ix, _)| (Reverse(*score), *ix));
+        self.matches = matches
+            .into_iter()
+            .map(|(_, ix, ranges)| OptionMatch { ix, ranges })
+            .collect();
+
+        let highlighted = self.next_enabled(None, true);
+        self.highlight(highlighted, cx);
+    }
+
+    /// Shows the selected label in the input, or clears it in multiple mode.
+    fn reset_query(&mut self, cx: &mut Context<Self>) {
+        let text = match self.selected.first() {
+            Some(option) if !self.multiple => option.label.clone(),
+            _ => SharedString::default(),
+        };
+        self.input.update(cx, |input, cx| input.set_text(text, cx));
+    }
+
+    fn match_option(&self, ix: usize) -> Option<&ComboboxOption<T>> {
+        self.matches.get(ix).map(|m| &self.options[m.ix])
+    }
+
+    fn next_enabled(&self, from: Option<usize>, forward: bool) -> Option<usize> {
+        let enabled = |ix: &usize| {
+            self.match_option(*ix)
+                .is_some_and(|option| !option.disabled)
+        };
+        let len = self.matches.len();
+        match (from, forward) {
+            (None, true) => (0..len).find(enabled),
+            (None, false) => (0..len).rev().find(enabled),
+            (Some(from), true) => (from + 1..len).find(enabled).or(Some(from)),
+            (Some(from), false) => (0..from).rev().find(enabled).or(Some(from)),
+        }
+    }
+
+    fn highlight(&mut self, ix: Option<usize>, cx: &mut Context<Self>) {
+        self.highlighted = ix;
+        if let Some(ix) = ix {
+            self.scroll_handle.scroll_to_item(ix, ScrollStrategy::Top);
+        }
+        cx.notify();
+    }
+
+    fn close(&mut self, cx: &mut Context<Self>) {
+        self.open = false;
+        self.highlighted = None;
+        self._load_task = None;
+        self.loading = false;
+        self.reset_query(cx);
+        cx.notify();
+    }
+
+    fn confirm(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.highlighted.filter(|_| self.open) {
+            self.select(ix, window, cx);
+        }
+    }
+
+    /// Selects the option at `ix` in `matches`, or deselects it in multiple mode.
+    fn select(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(option) = self.match_option(ix).filter(|option| !option.disabled) else {
+            return;
+        };
+        let option = option.clone();
+        if self.multiple {
+            if self.is_selected(&option.value) {
+                self.selected
+                    .retain(|selected| selected.value != option.value);
+            } else {
+                self.selected.push(option);
+            }
+            if self.input.read(cx).text().is_empty() {
+                cx.notify();
+            } else {
+                self.reset_query(cx);
+                self.query_changed(cx);
+            }
+        } else {
+            let changed = !self.is_selected(&option.value);
+            self.selected = vec![option];
+            self.close(cx);
+            if !changed {
+                return;
+            }
+        }
+        self.emit_change(window, cx);
+    }
+
+    fn remove(&mut self, value: &T, window: &mut Window, cx: &mut Context<Self>) {
+        self.selected.retain(|option| &option.value != value);
+        cx.notify();
+        self.emit_change(window, cx);
+    }
+
+    fn emit_change(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.sync_input(cx);
+        let values = self.selected_values();
+        if let Some(on_change) = self.on_change.as_ref() {
+            on_change(&values, window, cx);
+        }
+        cx.emit(ComboboxEvent::Change(values));
+    }
+
+    fn highlight_previous(
+        &mut self,
+        _: &HighlightPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.open {
+            let ix = self.next_enabled(self.highlighted, false);
+            self.highlight(ix, cx);
+        } else {
+            self.query_changed(cx);
+        }
+    }
+
+    fn highlight_next(&mut self, _: &HighlightNext, _: &mut Window, cx: &mut Context<Self>) {
+        if self.open {
+            let ix = self.next_enabled(self.highlighted, true);
+            self.highlight(ix, cx);
+        } else {
+            self.query_changed(cx);
+        }
+    }
+
+    fn dismiss(&mut self, _: &Dismiss, _: &mut Window, cx: &mut Context<Self>) {
+        if self.open {
+            self.close(cx);
+        } else {
+            cx.propagate();
+        }
+    }
+
+    fn render_match(&self, ix: usize, cx: &mut Context<Self>) -> impl IntoElement {
+        let colors = cx.theme().colors;
+        let m = &self.matches[ix];
+        let option = &self.options[m.ix];
+        let highlight = HighlightStyle {
+            color: Some(colors.primary),
+            font_weight: Some(FontWeight::SEMIBOLD),
+            ..Default::default()
+        };
+
+        h_flex()
+            .id(ix)
+            .h(ROW_HEIGHT)
+            .gap_2()
+            .px_2()
+            .rounded(cx.theme().styles.radius)
+            .when(self.highlighted == Some(ix), |this| {
+                this.bg(colors.element_hover)
+            })
+            .map(|this| {
+                if option.disabled {
+                    this.opacity(0.5).cursor_not_allowed()
+                } else {
+                    this.cursor_pointer()
+                        .on_mouse_move(cx.listener(move |this, _, _, cx| {
+                            if this.highlighted != Some(ix) {
+                                this.highlighted = Some(ix);
+                                cx.notify();
+                            }
+                        }))
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            this.select(ix, window, cx);
+                        }))
+                }
+            })
+            .child(
+                div().flex_1().min_w_0().truncate().child(
+                    StyledText::new(option.label.clone())
+                        .with_highlights(m.ranges.iter().map(|range| (range.clone(), highlight))),
+                ),
+            )
+            .when(self.is_selected(&option.value), |this| {
+                this.child(
+                    Icon::new(IconName::Check)
+                        .size(IconSize::Small)
+                        .color(colors.primary),
+                )
+            })
+    }
+
+    fn render_menu(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let colors = cx.theme().colors;
+        let visible_rows = self.matches.len().clamp(1, MAX_VISIBLE_ROWS);
+
+        v_flex()
+            .id("combobox-menu")
+            .w(self.field_width)
+            .p_1()
+            .elevation_2(cx)
+            .text_color(colors.text)
+            .when(self.loading, |this| {
+                this.child(
+                    h_flex()
+                        .h(ROW_HEIGHT)
+                        .gap_2()
+                        .px_2()
+                        .text_color(colors.text_muted)
+                        .child(
+                            Icon::new(IconName::Loading)
+                                .size(IconSize::Small)
+                                .with_animation(
+                                    "loading",
+                                    Animation::new(Duration::from_millis(800)).repeat(),
+                                    |this, delta| {
+                                        this.transform(Transformation::rotate(percentage(delta)))
+                                    },
+                                ),
+                        )
+                        .child("Loading..."),
+                )
+            })
+            .map(|this| {
+                if self.matches.is_empty() {
+                    this.when(!self.loading, |this| {
+                        this.child(
+                            div()
+                                .h(ROW_HEIGHT)
+                                .flex()
+                                .items_center()
+                                .px_2()
+                                .text_color(colors.text_muted)
+                                .child("No results"),
+                        )
+                    })
+                } else {
+                    this.child(
+                        uniform_list(
+                            "combobox-options",
+                            self.matches.len(),
+                            cx.processor(|this, range: Range<usize>, _, cx| {
+                                range
+                                    .map(|ix| this.render_match(ix, cx))
+                                    .collect::<Vec<_>>()
+                            }),
+                        )
+                        .track_scroll(self.scroll_handle.clone())
+                        .h(ROW_HEIGHT * visible_rows as f32),
+                    )
+                }
+            })
+    }
+
+    fn render_chip(
+        &self,
+        ix: usize,
+        option: &ComboboxOption<T>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let value = option.value.clone();
+
+        h_flex()
+            .h_6()
+            .gap_1()
+            .pl_2()
+            .pr_1()
+            .rounded(cx.theme().styles.radius)
+            .bg(cx.theme().colors.element_bg)
+            .text_xs()
+            .child(option.label.clone())
+            .when(!self.disabled, |this| {
+                this.child(
+                    div()
+                        .id(("remove", ix))
+                        .rounded_sm()
+                        .cursor_pointer()
+                        .text_color(cx.theme().colors.text_muted)
+                        .hover(|this| this.bg(cx.theme().colors.element_hover))
+                        .child(Icon::new(IconName::X).size(IconSize::XSmall))
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            this.remove(&value, window, cx);
+                        })),
+                )
+            })
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> Render for Combobox<T> {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let entity = cx.entity();
+
+        v_flex()
+            .key_context(CONTEXT)
+            .on_action(cx.listener(Self::highlight_previous))
+            .on_action(cx.listener(Self::highlight_next))
+            .on_action(cx.listener(Self::dismiss))
+            .w_full()
+            .gap_1()
+            .child(
+                ControlledPopover::new(
+                    "combobox-popover",
+                    div()
+                        .id("combobox-field")
+                        .relative()
+                        .on_mouse_down(
+                            MouseButton::Left,
+                            cx.listener(|this, _, _, cx| {
+                                if !this.open {
+                                    this.query_changed(cx);
+                                }
+                            }),
+                        )
+                        .child(self.input.clone())
+                        .child(
+                            canvas(
+                                move |bounds, _, cx| {
+                                    entity
+                                        .update(cx, |this, _| this.field_width = bounds.size.width)
+                                },
+                                |_, _, _, _| {},
+                            )
+                            .absolute()
+                            .size_full(),
+                        ),
+                    cx.listener(|this, _: &DismissEvent, _, cx| this.close(cx)),
+                )
+                .focus_handle(&self.input.focus_handle(cx))
+                .when(self.open, |this| this.content(self.render_menu(cx))),
+            )
+            .when(self.multiple && !self.selected.is_empty(), |this| {
+                this.child(
+                    h_flex().flex_wrap().gap_1().children(
+                        self.selected
+                            .iter()
+                            .enumerate()
+                            .map(|(ix, option)| self.render_chip(ix, option, cx)),
+                    ),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{TestAppContext, VisualTestContext};
+
+    use super::*;
+    use crate::Theme;
+
+    fn placeholder(combobox: &Entity<Combobox<u32>>, cx: &mut VisualTestContext) -> SharedString {
+        cx.read(|cx| combobox.read(cx).input_placeholder())
+    }
+
+    #[gpui::test]
+    fn test_input_follows_selection(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            Theme::init(cx, None, None);
+            TextInput::bind_keys(cx);
+            bind_combobox_keys(cx);
+        });
+        let (combobox, cx) = cx.add_window_view(|window, cx| {
+            Combobox::new(window, cx)
+                .options([ComboboxOption::new(1, "One"), ComboboxOption::new(2, "Two")])
+                .placeholder("Pick a number")
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            placeholder(&combobox, cx),
+            SharedString::from("Pick a number")
+        );
+
+        combobox.update(cx, |combobox, cx| {
+            combobox.set_selected([ComboboxOption::new(2, "Two")], cx)
+        });
+        assert_eq!(placeholder(&combobox, cx), SharedString::from("Two"));
+        assert_eq!(
+            cx.read(|cx| combobox.read(cx).input.read(cx).text()),
+            SharedString::from("Two")
+        );
+
+        combobox.update(cx, |combobox, cx| combobox.set_selected([], cx));
+        assert_eq!(
+            placeholder(&combobox, cx),
+            SharedString::from("Pick a number")
+        );
+    }
+}
@@ -0,0 +1,432 @@
+use std::rc::Rc;
+
+use gpui::{
+    actions, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, KeyBinding,
+    MouseButton, Render, Subscription,
+};
+
+use crate::{
+    prelude::*, Calendar, CalendarEvent, ControlledPopover, Date, DateSelection, IconName,
+    InputEvent, TextInput, Weekday,
+};
+
+actions!(date_picker, [CloseCalendar]);
+
+const CONTEXT: &str = "DatePicker";
+
+/// Separates the two dates of a range in the input.
+const RANGE_SEPARATOR: &str = " - ";
+
+/// Events emitted by a [`DatePicker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatePickerEvent {
+    /// The date was picked or typed, `None` when the input was cleared.
+    Change(Option<DateSelection>),
+}
+
+/// A text input to type a date, with a [`Calendar`] to pick it from below.
+///
+/// Dates are typed as ISO 8601, `2025-03-09`, and ranges as two dates separated by
+/// ` - `. The calendar opens when the input is focused or clicked, and closes on escape, a
+/// click outside, when the input loses the focus or once a date is picked.
+///
+/// Call [`DatePicker::bind_keys`] and [`TextInput::bind_keys`] once at startup.
+pub struct DatePicker {
+    input: Entity<TextInput>,
+    calendar: Entity<Calendar>,
+    selection: Option<DateSelection>,
+    open: bool,
+    range: bool,
+    min: Option<Date>,
+    max: Option<Date>,
+    disabled_days: Option<Rc<dyn Fn(Date) -> bool + 'static>>,
+    week_start: Weekday,
+    placeholder: Option<SharedString>,
+    size: Size,
+    disabled: bool,
+    on_change: Option<Box<dyn Fn(Option<DateSelection>, &mut Window, &mut App) + 'static>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl EventEmitter<DatePickerEvent> for DatePicker {}
+
+impl Focusable for DatePicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.input.focus_handle(cx)
+    }
+}
+
+impl DatePicker {
+    pub fn bind_keys(cx: &mut App) {
+        cx.bind_keys([KeyBinding::new("escape", CloseCalendar, Some(CONTEXT))]);
+    }
+
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let input = cx.new(|cx| TextInput::new(cx).suffix(IconName::Calendar));
+        let calendar = cx.new(|_| Calendar::new());
+        let focus_handle = input.focus_handle(cx);
+        let subscriptions = vec![
+            cx.subscribe_in(&input, window, |this, _, event, window, cx| match event {
+                InputEvent::Change(text) => this.parse(text, window, cx),
+                InputEvent::Submit(_) => this.close(cx),
+            }),
+            cx.subscribe_in(&calendar, window, |this, _, event, window, cx| {
+                let CalendarEvent::Change(selection) = event;
+                this.change(Some(*selection), window, cx);
+                this.show_selection(cx);
+                this.close(cx);
+            }),
+            cx.on_focus(&focus_handle, window, |this, _, cx| this.open(cx)),
+        ];
+        // The builders are applied once `new` returns, push them to the input and the calendar.
+        cx.defer(|this, cx| this.sync(cx));
+
+        Self {
+            input,
+            calendar,
+            selection: None,
+            open: false,
+            range: false,
+            min: None,
+            max: None,
+            disabled_days: None,
+            week_start: Weekday::Monday,
+            placeholder: None,
+            size: Size::default(),
+            disabled: false,
+            on_change: None,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    /// Selects the date.
+    pub fn selected(mut self, date: Date) -> Self {
+        self.selection = Some(DateSelection::Single(date));
+        self
+    }
+
+    /// Picks a range of dates instead of a single date.
+    pub fn range(mut self, range: bool) -> Self {
+        self.range = range;
+        self
+    }
+
+    /// Selects the range in range mode.
+    pub fn selected_range(mut self, start: Date, end: Date) -> Self {
+        self.range = true;
+        self.selection = Some(DateSelection::range(start, end));
+        self
+    }
+
+    /// Disables the days before this date, see [`Calendar::min`].
+    pub fn min(mut self, min: Date) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    /// Disables the days after this date, see [`Calendar::max`].
+    pub fn max(mut self, max: Date) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Disables the days the function returns `true` for, see [`Calendar::disabled_days`].
+    pub fn disabled_days(mut self, disabled: impl Fn(Date) -> bool + 'static) -> Self {
+        self.disabled_days = Some(Rc::new(disabled));
+        self
+    }
+
+    /// Sets the first day of the week. Default is `Weekday::Monday`.
+    pub fn week_start(mut self, week_start: Weekday) -> Self {
+        self.week_start = week_start;
+        self
+    }
+
+    /// Sets the placeholder. Default is the expected format, `YYYY-MM-DD`.
+    pub fn placeholder(mut self, placeholder: impl Into<SharedString>) -> Self {
+        self.placeholder = Some(placeholder.into());
+        self
+    }
+
+    pub fn size(mut self, size: Size) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Called when the user picks or types a date, or clears the input.
+    pub fn on_change(
+        mut self,
+        handler: impl Fn(Option<DateSelection>, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_change = Some(Box::new(handler));
+        self
+    }
+
+    pub fn selection(&self) -> Option<DateSelection> {
+        self.selection
+    }
+
+    /// Replaces the selection, without calling `on_change`.
+    pub fn set_selection(&mut self, selection: Option<DateSelection>, cx: &mut Context<Self>) {
+        self.selection = selection;
+        self.show_selection(cx);
+        cx.notify();
+    }
+
+    /// Shows the input as invalid.
+    pub fn set_invalid(&mut self, invalid: bool, cx: &mut Context<Self>) {
+        self.input
+            .update(cx, |input, cx| input.set_invalid(invalid, cx));
+    }
+
+    /// Pushes the builders to the input and the calendar.
+    fn sync(&mut self, cx: &mut Context<Self>) {
+        let (size, disabled) = (self.size, self.disabled);
+        let placeholder = self.placeholder.clone().unwrap_or_else(|| {
+            match self.range {
+                true => format!("YYYY-MM-DD{RANGE_SEPARATOR}YYYY-MM-DD"),
+                false => "YYYY-MM-DD".to_string(),
+            }
+            .into()
+        });
+        self.input.update(cx, |input, cx| {
+            input.set_size(size, cx);
+            input.set_disabled(disabled, cx);
+            input.set_placeholder(placeholder, cx);
+        });
+        let (range, min, max, week_start) = (self.range, self.min, self.max, self.week_start);
+        let disabled_days = self.disabled_days.clone();
+        self.calendar.update(cx, |calendar, cx| {
+            calendar.set_range(range, cx);
+            calendar.set_bounds(min, max, cx);
+            calendar.set_week_start(week_start, cx);
+            if let Some(disabled_days) = disabled_days {
+                calendar.set_disabled_days(move |date| disabled_days(date), cx);
+            }
+        });
+        self.show_selection(cx);
+    }
+
+    /// Writes the selection in the input and selects it in the calendar.
+    fn show_selection(&mut self, cx: &mut Context<Self>) {
+        let selection = self.selection;
+        let text = selection.map(format_selection).unwrap_or_default();
+        self.input.update(cx, |input, cx| {
+            input.set_text(text, cx);
+            input.set_invalid(false, cx);
+        });
+        self.calendar
+            .update(cx, |calendar, cx| calendar.set_selection(selection, cx));
+    }
+
+    fn open(&mut self, cx: &mut Context<Self>) {
+        if self.open || self.disabled {
+            return;
+        }
+        self.open = true;
+        if let Some(selection) = self.selection {
+            self.calendar.update(cx, |calendar, cx| {
+                calendar.show_month(selection.start(), cx)
+            });
+        }
+        cx.notify();
+    }
+
+    fn close(&mut self, cx: &mut Context<Self>) {
+        self.open = false;
+        cx.notify();
+    }
+
+    fn close_calendar(&mut self, _: &CloseCalendar, _: &mut Window, cx: &mut Context<Self>) {
+        if self.open {
+            self.close(cx);
+        } else {
+            cx.propagate();
+        }
+    }
+
+    /// Selects the typed date, or shows the input as invalid if it isn't one.
+    fn parse(&mut self, text: &str, window: &mut Window, cx: &mut Context<Self>) {
+        if text.trim().is_empty() {
+            self.set_invalid(false, cx);
+            self.calendar
+                .update(cx, |calendar, cx| calendar.set_selection(None, cx));
+            self.change(None, window, cx);
+            return;
+        }
+        let selection = match text.split_once(RANGE_SEPARATOR) {
+            _ if !self.range => Date::parse(text).map(DateSelection::Single),
+            Some((start, end)) => Date::parse(start)
+                .zip(Date::parse(end))
+                .map(|(start, end)| DateSelection::range(start, end)),
+            None => None,
+        };
+        let calendar = self.calendar.read(cx);
+        let selection = selection.filter(|selection| {
+            !calendar.is_disabled(selection.start()) && !calendar.is_disabled(selection.end())
+        });
+        self.set_invalid(selection.is_none(), cx);
+        if let Some(selection) = selection {
+            self.calendar.update(cx, |calendar, cx| {
+                calendar.set_selection(Some(selection), cx)
+            });
+            self.change(Some(selection), window, cx);
+        }
+    }
+
+    fn change(
+        &mut self,
+        selection: Option<DateSelection>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.selection == selection {
+            return;
+        }
+        self.selection = selection;
+        if let Some(on_change) = self.on_change.as_ref() {
+            on_change(selection, window, cx);
+        }
+        cx.emit(DatePickerEvent::Change(selection));
+        cx.notify();
+    }
+}
+
+/// Formats the selection the way the input parses it.
+fn format_selection(selection: DateSelection) -> String {
+    match selection {
+        DateSelection::Single(date) => date.to_string(),
+        DateSelection::Range(start, end) => format!("{start}{RANGE_SEPARATOR}{end}"),
+    }
+}
+
+impl Render for DatePicker {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context(CONTEXT)
+            .on_action(cx.listener(Self::close_calendar))
+            .w_full()
+            .child(
+                ControlledPopover::new(
+                    "date-picker-popover",
+                    div()
+                        .id("date-picker-field")
+                        .on_mouse_down(
+                            MouseButton::Left,
+                            cx.listener(|this, _, _, cx| this.open(cx)),
+                        )
+                        .child(self.input.clone()),
+                    cx.listener(|this, _: &DismissEvent, _, cx| this.close(cx)),
+                )
+                .focus_handle(&self.input.focus_handle(cx))
+                .when(self.open, |this| {
+                    this.content(
+                        div()
+                            .id("date-picker-calendar")
+                            .p_3()
+                            .elevation_2(cx)
+                            .child(self.calendar.clone()),
+                    )
+                }),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{TestAppContext, VisualTestContext};
+
+    use super::*;
+    use crate::Theme;
+
+    fn date(year: i32, month: u32, day: u32) -> Date {
+        Date::new(year, month, day).unwrap()
+    }
+
+    fn build_picker(
+        cx: &mut TestAppContext,
+        build: impl FnOnce(DatePicker) -> DatePicker + 'static,
+    ) -> (Entity<DatePicker>, &mut VisualTestContext) {
+        cx.update(|cx| {
+            Theme::init(cx, None, None);
+            TextInput::bind_keys(cx);
+            DatePicker::bind_keys(cx);
+        });
+        let (picker, cx) = cx.add_window_view(|window, cx| build(DatePicker::new(window, cx)));
+        cx.run_until_parked();
+        (picker, cx)
+    }
+
+    fn text(picker: &Entity<DatePicker>, cx: &mut VisualTestContext) -> String {
+        cx.read(|cx| picker.read(cx).input.read(cx).text().to_string())
+    }
+
+    fn calendar_selection(
+        picker: &Entity<DatePicker>,
+        cx: &mut VisualTestContext,
+    ) -> Option<DateSelection> {
+        cx.read(|cx| picker.read(cx).calendar.read(cx).selection())
+    }
+
+    #[gpui::test]
+    fn test_builders_are_applied(cx: &mut TestAppContext) {
+        let (picker, cx) = build_picker(cx, |picker| {
+            picker
+                .selected_range(date(2025, 3, 12), date(2025, 3, 9))
+                .min(date(2025, 3, 1))
+        });
+        let selection = DateSelection::Range(date(2025, 3, 9), date(2025, 3, 12));
+
+        assert_eq!(text(&picker, cx), "2025-03-09 - 2025-03-12");
+        assert_eq!(calendar_selection(&picker, cx), Some(selection));
+        cx.read(|cx| {
+            let calendar = picker.read(cx).calendar.read(cx);
+            assert!(calendar.is_disabled(date(2025, 2, 28)));
+            assert!(!calendar.is_disabled(date(2025, 3, 1)));
+        });
+    }
+
+    #[gpui::test]
+    fn test_set_selection(cx: &mut TestAppContext) {
+        let (picker, cx) = build_picker(cx, |picker| picker);
+        assert_eq!(text(&picker, cx), "");
+
+        let selection = DateSelection::Single(date(2024, 2, 29));
+        picker.update(cx, |picker, cx| picker.set_selection(Some(selection), cx));
+        assert_eq!(text(&picker, cx), "2024-02-29");
+        assert_eq!(calendar_selection(&picker, cx), Some(selection));
+
+        picker.update(cx, |picker, cx| picker.set_selection(None, cx));
+        assert_eq!(text(&picker, cx), "");
+        assert_eq!(calendar_selection(&picker, cx), None);
+    }
+
+    #[gpui::test]
+    fn test_typed_date(cx: &mut TestAppContext) {
+        let (picker, cx) = build_picker(cx, |picker| {
+            picker.disabled_days(|date| date.weekday() == Weekday::Sunday)
+        });
+        let parse = |text: &str, cx: &mut VisualTestContext| {
+            picker.update_in(cx, |picker, window, cx| picker.parse(text, window, cx));
+        };
+
+        parse("2025-03-10", cx);
+        let selection = Some(DateSelection::Single(date(2025, 3, 10)));
+        cx.read(|cx| assert_eq!(picker.read(cx).selection(), selection));
+        assert_eq!(calendar_selection(&picker, cx), selection);
+
+        // A disabled day, or text that isn't a date, keeps the selection.
+        parse("2025-03-09", cx);
+        parse("2025-03-1x", cx);
+        cx.read(|cx| assert_eq!(picker.read(cx).selection(), selection));
+
+        parse("", cx);
+        cx.read(|cx| assert_eq!(picker.read(cx).selection(), None));
+        assert_eq!(calendar_selection(&picker, cx), None);
+    }
+}
@@ -0,0 +1,7 @@
+mod form;
+mod form_control;
+mod form_field;
+
+pub use form::*;
+pub use form_control::*;
+pub use form_field::*;
@@ -0,0 +1,476 @@
+use gpui::{AnyView, Context, Entity, EventEmitter, Focusable, Pixels, Render, Task};
+
+use super::{form_field::DEFAULT_LABEL_WIDTH, FormControl, FormField};
+use crate::{prelude::*, Direction};
+
+/// Events emitted by a [`Form`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormEvent {
+    /// Every field was valid on submit.
+    Submit,
+    /// The fields were restored to their initial values.
+    Reset,
+}
+
+/// The operations a [`Form`] needs on a field, whatever the value of its control.
+trait AnyFormField {
+    fn view(&self) -> AnyView;
+    fn set_layout(&self, direction: Direction, label_width: Pixels, cx: &mut App);
+    fn validate(&self, cx: &mut App) -> Task<bool>;
+    fn reset(&self, cx: &mut App);
+    fn is_dirty(&self, cx: &App) -> bool;
+    /// Whether the field has no error and no validation running, for its current value.
+    fn is_valid(&self, cx: &App) -> bool;
+    fn focus(&self, window: &mut Window, cx: &App);
+}
+
+impl<C: FormControl> AnyFormField for Entity<FormField<C>> {
+    fn view(&self) -> AnyView {
+        self.clone().into()
+    }
+
+    fn set_layout(&self, direction: Direction, label_width: Pixels, cx: &mut App) {
+        self.update(cx, |field, cx| field.set_layout(direction, label_width, cx));
+    }
+
+    fn validate(&self, cx: &mut App) -> Task<bool> {
+        self.update(cx, |field, cx| {
+            field.mark_touched();
+            field.validate(cx)
+        })
+    }
+
+    fn reset(&self, cx: &mut App) {
+        self.update(cx, |field, cx| field.reset(cx));
+    }
+
+    fn is_dirty(&self, cx: &App) -> bool {
+        self.read(cx).is_dirty()
+    }
+
+    fn is_valid(&self, cx: &App) -> bool {
+        let field = self.read(cx);
+        field.error().is_none() && !field.is_validating()
+    }
+
+    fn focus(&self, window: &mut Window, cx: &App) {
+        window.focus(&self.read(cx).control().focus_handle(cx));
+    }
+}
+
+/// Lays out [`FormField`]s in a column and validates them together.
+///
+/// In the vertical direction, the default, the labels are above the controls, in the
+/// horizontal direction they are in a column on the left. [`Form::submit`] validates every
+/// field and focuses the first invalid one, or calls `on_submit` when all are valid.
+pub struct Form {
+    fields: Vec<Box<dyn AnyFormField>>,
+    direction: Direction,
+    label_width: Pixels,
+    submitting: bool,
+    on_submit: Option<Box<dyn Fn(&mut Window, &mut App) + 'static>>,
+    _submit_task: Option<Task<()>>,
+}
+
+impl EventEmitter<FormEvent> for Form {}
+
+impl Form {
+    pub fn new(cx: &mut Context<Self>) -> Self {
+        // The builders are applied once `new` returns, push the layout to their fields.
+        cx.defer(|this, cx| this.push_layout(cx));
+
+        Self {
+            fields: Vec::new(),
+            direction: Direction::Vertical,
+            label_width: DEFAULT_LABEL_WIDTH,
+            submitting: false,
+            on_submit: None,
+            _submit_task: None,
+        }
+    }
+
+    /// Set the direction of the labels and controls. Default is `Direction::Vertical`.
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Puts the labels above the controls.
+    /// This is a convenience method for setting the direction to vertical without passing an argument.
+    pub fn direction_vertical(mut self) -> Self {
+        self.direction = Direction::Vertical;
+        self
+    }
+
+    /// Puts the labels in a column on the left of the controls.
+    /// This is a convenience method for setting the direction to horizontal without passing an argument.
+    pub fn direction_horizontal(mut self) -> Self {
+        self.direction = Direction::Horizontal;
+        self
+    }
+
+    /// Sets the width of the label column in the horizontal direction.
+    pub fn label_width(mut self, width: impl Into<Pixels>) -> Self {
+        self.label_width = width.into();
+        self
+    }
+
+    /// Add a field.
+    pub fn field<C: FormControl>(mut self, field: Entity<FormField<C>>) -> Self {
+        self.fields.push(Box::new(field));
+        self
+    }
+
+    /// Called when the form is submitted with every field valid.
+    pub fn on_submit(mut self, handler: impl Fn(&mut Window, &mut App) + 'static) -> Self {
+        self.on_submit = Some(Box::new(handler));
+        self
+    }
+
+    /// Adds a field after the form is built.
+    pub fn add_field<C: FormControl>(
+        &mut self,
+        field: Entity<FormField<C>>,
+        cx: &mut Context<Self>,
+    ) {
+        let field: Box<dyn AnyFormField> = Box::new(field);
+        field.set_layout(self.direction, self.label_width, cx);
+        self.fields.push(field);
+        cx.notify();
+    }
+
+    pub fn set_direction(&mut self, direction: Direction, cx: &mut Context<Self>) {
+        self.direction = direction;
+        self.push_layout(cx);
+        cx.notify();
+    }
+
+    pub fn set_label_width(&mut self, width: impl Into<Pixels>, cx: &mut Context<Self>) {
+        self.label_width = width.into();
+        self.push_layout(cx);
+        cx.notify();
+    }
+
+    fn push_layout(&self, cx: &mut App) {
+        for field in &self.fields {
+            field.set_layout(self.direction, self.label_width, cx);
+        }
+    }
+
+    /// Whether a submit is waiting for async validators.
+    pub fn is_submitting(&self) -> bool {
+        self.submitting
+    }
+
+    /// Whether any field differs from its initial value.
+    pub fn is_dirty(&self, cx: &App) -> bool {
+        self.fields.iter().any(|field| field.is_dirty(cx))
+    }
+
+    /// Validates every field, then focuses the first invalid one or submits the form.
+    pub fn submit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.submitting {
+            return;
+        }
+        let tasks: Vec<_> = self.fields.iter().map(|field| field.validate(cx)).collect();
+        self.submitting = true;
+        cx.notify();
+        self._submit_task = Some(cx.spawn_in(window, async move |this, cx| {
+            let mut results = Vec::with_capacity(tasks.len());
+            for task in tasks {
+                results.push(task.await);
+            }
+            this.update_in(cx, |this, window, cx| {
+                this.submitting = false;
+                cx.notify();
+                // A value changed while validating is validated again, it isn't submitted as is.
+                let invalid = results
+                    .iter()
+                    .zip(&this.fields)
+                    .position(|(valid, field)| !valid || !field.is_valid(cx));
+                if let Some(field) = invalid.and_then(|ix| this.fields.get(ix)) {
+                    field.focus(window, cx);
+                    return;
+                }
+                if let Some(on_submit) = this.on_submit.as_ref() {
+                    on_submit(window, cx);
+                }
+                cx.emit(FormEvent::Submit);
+            })
+            .ok();
+        }));
+    }
+
+    /// Restores every field to its initial value, and cancels a pending submit.
+    pub fn reset(&mut self, cx: &mut Context<Self>) {
+        self._submit_task = None;
+        self.submitting = false;
+        for field in &self.fields {
+            field.reset(cx);
+        }
+        cx.emit(FormEvent::Reset);
+        cx.notify();
+    }
+}
+
+impl Render for Form {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .gap_4()
+            .children(self.fields.iter().map(|field| field.view()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc, time::Duration};
+
+    use gpui::{px, TestAppContext, VisualTestContext};
+
+    use super::*;
+    use crate::{TextInput, Theme};
+
+    fn build_form(
+        cx: &mut TestAppContext,
+        build: impl FnOnce(Form, &mut Window, &mut App) -> Form + 'static,
+    ) -> (Entity<Form>, &mut VisualTestContext) {
+        cx.update(|cx| {
+            Theme::init(cx, None, None);
+            TextInput::bind_keys(cx);
+        });
+        let (form, cx) = cx.add_window_view(|window, cx| build(Form::new(cx), window, cx));
+        cx.run_until_parked();
+        (form, cx)
+    }
+
+    fn text_field(
+        text: &str,
+        window: &mut Window,
+        cx: &mut App,
+        build: impl FnOnce(FormField<TextInput>) -> FormField<TextInput>,
+    ) -> Entity<FormField<TextInput>> {
+        let text = SharedString::from(text.to_string());
+        let input = cx.new(|cx| {
+            let mut input = TextInput::new(cx);
+            input.set_text(text, cx);
+            input
+        });
+        cx.new(|cx| build(FormField::new(input, window, cx)))
+    }
+
+    fn field(
+        form: &Entity<Form>,
+        ix: usize,
+        cx: &mut VisualTestContext,
+    ) -> Entity<FormField<TextInput>> {
+        cx.read(|cx| form.read(cx).fields[ix].view().downcast().ok().unwrap())
+    }
+
+    fn error(field: &Entity<FormField<TextInput>>, cx: &mut VisualTestContext) -> Option<String> {
+        cx.read(|cx| field.read(cx).error().map(|error| error.to_string()))
+    }
+
+    fn set_text(field: &Entity<FormField<TextInput>>, text: &str, cx: &mut VisualTestContext) {
+        let input = cx.read(|cx| field.read(cx).control().clone());
+        let text = SharedString::from(text.to_string());
+        input.update(cx, |input, cx| input.set_text(text, cx));
+        cx.run_until_parked();
+    }
+
+    fn is_focused(field: &Entity<FormField<TextInput>>, cx: &mut VisualTestContext) -> bool {
+        cx.update(|window, cx| {
+            let input = field.read(cx).control().clone();
+            input.focus_handle(cx).is_focused(window)
+        })
+    }
+
+    fn record_events(
+        form: &Entity<Form>,
+        cx: &mut VisualTestContext,
+    ) -> Rc<RefCell<Vec<FormEvent>>> {
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let recorded = events.clone();
+        cx.update(|_, cx| {
+            cx.subscribe(form, move |_, event: &FormEvent, _| {
+                recorded.borrow_mut().push(*event);
+            })
+            .detach();
+        });
+        events
+    }
+
+    fn submit(form: &Entity<Form>, cx: &mut VisualTestContext) {
+        form.update_in(cx, |form, window, cx| form.submit(window, cx));
+        cx.run_until_parked();
+    }
+
+    #[gpui::test]
+    fn test_layout_is_pushed(cx: &mut TestAppContext) {
+        let (form, cx) = build_form(cx, |form, window, cx| {
+            let field = text_field("", window, cx, |field| field);
+            form.direction_horizontal()
+                .label_width(px(200.))
+                .field(field)
+        });
+        let layout = |field: &Entity<FormField<TextInput>>, cx: &mut VisualTestContext| {
+            cx.read(|cx| (field.read(cx).direction, field.read(cx).label_width))
+        };
+        let first = field(&form, 0, cx);
+        assert_eq!(layout(&first, cx), (Direction::Horizontal, px(200.)));
+
+        let second = cx.update(|window, cx| text_field("", window, cx, |field| field));
+        form.update(cx, |form, cx| form.add_field(second.clone(), cx));
+        assert_eq!(layout(&second, cx), (Direction::Horizontal, px(200.)));
+
+        form.update(cx, |form, cx| {
+            form.set_direction(Direction::Vertical, cx);
+            form.set_label_width(px(120.), cx);
+        });
+        assert_eq!(layout(&first, cx), (Direction::Vertical, px(120.)));
+        assert_eq!(layout(&second, cx), (Direction::Vertical, px(120.)));
+    }
+
+    #[gpui::test]
+    fn test_required(cx: &mut TestAppContext) {
+        let (form, cx) = build_form(cx, |form, window, cx| {
+            form.field(text_field(" ", window, cx, |field| field.required(true)))
+        });
+        let events = record_events(&form, cx);
+        let name = field(&form, 0, cx);
+
+        submit(&form, cx);
+        assert_eq!(error(&name, cx), Some("This field is required".to_string()));
+        assert!(events.borrow().is_empty());
+
+        set_text(&name, "Jane", cx);
+        assert_eq!(error(&name, cx), None);
+        submit(&form, cx);
+        assert_eq!(*events.borrow(), vec![FormEvent::Submit]);
+    }
+
+    #[gpui::test]
+    fn test_validator(cx: &mut TestAppContext) {
+        let (form, cx) = build_form(cx, |form, window, cx| {
+            form.field(text_field("ab", window, cx, |field| {
+                field.validator(|value| {
+                    if value.len() < 3 {
+                        Err("Too short".into())
+                    } else {
+                        Ok(())
+                    }
+                })
+            }))
+        });
+        let name = field(&form, 0, cx);
+
+        // The value isn't validated before the field is touched.
+        set_text(&name, "a", cx);
+        assert_eq!(error(&name, cx), None);
+
+        let valid = name.update(cx, |field, cx| {
+            field.mark_touched();
+            field.validate(cx)
+        });
+        assert!(!cx.executor().block_test(valid));
+        assert_eq!(error(&name, cx), Some("Too short".to_string()));
+
+        set_text(&name, "abc", cx);
+        assert_eq!(error(&name, cx), None);
+    }
+
+    #[gpui::test]
+    fn test_async_validator(cx: &mut TestAppContext) {
+        let (form, cx) = build_form(cx, |form, window, cx| {
+            form.field(text_field("admin", window, cx, |field| {
+                field
+                    .required(true)
+                    .async_validator(|value: SharedString| async move {
+                        if value == "admin" {
+                            Err("This username is taken".into())
+                        } else {
+                            Ok(())
+                        }
+                    })
+            }))
+        });
+        let name = field(&form, 0, cx);
+
+        let _valid = name.update(cx, |field, cx| field.validate(cx));
+        cx.read(|cx| assert!(name.read(cx).is_validating()));
+        cx.run_until_parked();
+        cx.read(|cx| assert!(!name.read(cx).is_validating()));
+        assert_eq!(error(&name, cx), Some("This username is taken".to_string()));
+
+        // The async validators don't run while a sync check fails.
+        set_text(&name, "", cx);
+        let _valid = name.update(cx, |field, cx| field.validate(cx));
+        cx.read(|cx| assert!(!name.read(cx).is_validating()));
+        assert_eq!(error(&name, cx), Some("This field is required".to_string()));
+    }
+
+    #[gpui::test]
+    fn test_stale_async_result_is_discarded(cx: &mut TestAppContext) {
+        let (form, cx) = build_form(cx, |form, window, cx| {
+            let executor = cx.background_executor().clone();
+            form.field(text_field("taken", window, cx, move |field| {
+                field.async_validator(move |value: SharedString| {
+                    let executor = executor.clone();
+                    async move {
+                        // The outdated value answers last.
+                        let delay = if value == "taken" { 200 } else { 50 };
+                        executor.timer(Duration::from_millis(delay)).await;
+                        if value == "taken" {
+                            Err("This username is taken".into())
+                        } else {
+                            Ok(())
+                        }
+                    }
+                })
+            }))
+        });
+        let name = field(&form, 0, cx);
+
+        let stale = name.update(cx, |field, cx| {
+            field.mark_touched();
+            field.validate(cx)
+        });
+        set_text(&name, "free", cx);
+
+        cx.executor().advance_clock(Duration::from_millis(50));
+        cx.run_until_parked();
+        cx.read(|cx| assert!(!name.read(cx).is_validating()));
+        assert_eq!(error(&name, cx), None);
+
+        cx.executor().advance_clock(Duration::from_millis(150));
+        cx.run_until_parked();
+        assert_eq!(error(&name, cx), None);
+        assert!(!cx.executor().block_test(stale));
+    }
+
+    #[gpui::test]
+    fn test_submit_focuses_first_invalid_field(cx: &mut TestAppContext) {
+        let (form, cx) = build_form(cx, |form, window, cx| {
+            form.field(text_field("Jane", window, cx, |field| field.required(true)))
+                .field(text_field("", window, cx, |field| field.required(true)))
+                .field(text_field("", window, cx, |field| field.required(true)))
+        });
+        let events = record_events(&form, cx);
+        let fields: Vec<_> = (0..3).map(|ix| field(&form, ix, cx)).collect();
+
+        submit(&form, cx);
+        assert!(!is_focused(&fields[0], cx));
+        assert!(is_focused(&fields[1], cx));
+        assert!(!is_focused(&fields[2], cx));
+        assert!(events.borrow().is_empty());
+
+        set_text(&fields[1], "Doe", cx);
+        submit(&form, cx);
+        assert!(is_focused(&fields[2], cx));
+        assert!(events.borrow().is_empty());
+
+        set_text(&fields[2], "jane@example.com", cx);
+        submit(&form, cx);
+        cx.read(|cx| assert!(!form.read(cx).is_submitting()));
+        assert_eq!(*events.borrow(), vec![FormEvent::Submit]);
+    }
+}
@@ -0,0 +1,142 @@
+use gpui::{Context, Focusable, Render};
+
+use crate::{
+    prelude::*, DatePicker, DateSelection, NumberInput, Select, Slider, SliderValue, TextArea,
+    TextInput,
+};
+
+/// A control a [`FormField`](crate::FormField) can wrap, to read, reset and validate its value.
+///
+/// Implemented for the inputs of this crate, implement it for a custom control to use it in
+/// a [`Form`](crate::Form). The field watches the control with `cx.observe`, so the control
+/// must notify when its value changes.
+pub trait FormControl: Render + Focusable {
+    type Value: Clone + PartialEq + 'static;
+
+    fn value(&self, cx: &App) -> Self::Value;
+
+    /// Sets the value, without calling the change handlers of the control.
+    fn set_value(&mut self, value: Self::Value, cx: &mut Context<Self>);
+
+    /// Whether the value counts as missing for a required field.
+    fn is_empty(&self, _cx: &App) -> bool {
+        false
+    }
+
+    /// The error of the control itself, like text that doesn't parse, which fails the field
+    /// whatever its value.
+    fn error(&self, _cx: &App) -> Option<SharedString> {
+        None
+    }
+
+    /// Shows the control as invalid. Does nothing by default, the field shows the error.
+    fn set_invalid(&mut self, _invalid: bool, _cx: &mut Context<Self>) {}
+}
+
+impl FormControl for TextInput {
+    type Value = SharedString;
+
+    fn value(&self, _cx: &App) -> SharedString {
+        self.text()
+    }
+
+    fn set_value(&mut self, value: SharedString, cx: &mut Context<Self>) {
+        self.set_text(value, cx);
+    }
+
+    fn is_empty(&self, _cx: &App) -> bool {
+        self.text().trim().is_empty()
+    }
+
+    fn set_invalid(&mut self, invalid: bool, cx: &mut Context<Self>) {
+        TextInput::set_invalid(self, invalid, cx);
+    }
+}
+
+impl FormControl for TextArea {
+    type Value = SharedString;
+
+    fn value(&self, _cx: &App) -> SharedString {
+        self.text()
+    }
+
+    fn set_value(&mut self, value: SharedString, cx: &mut Context<Self>) {
+        self.set_text(value, cx);
+    }
+
+    fn is_empty(&self, _cx: &App) -> bool {
+        self.text().trim().is_empty()
+    }
+}
+
+impl FormControl for NumberInput {
+    type Value = Option<f64>;
+
+    fn value(&self, _cx: &App) -> Option<f64> {
+        self.get_value()
+    }
+
+    fn set_value(&mut self, value: Option<f64>, cx: &mut Context<Self>) {
+        match value {
+            Some(value) => NumberInput::set_value(self, value, cx),
+            None => self.clear(cx),
+        }
+    }
+
+    fn is_empty(&self, _cx: &App) -> bool {
+        self.get_value().is_none()
+    }
+
+    /// Invalid text keeps the last valid value, the error tells the value is outdated.
+    fn error(&self, _cx: &App) -> Option<SharedString> {
+        NumberInput::error(self).cloned()
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> FormControl for Select<T> {
+    type Value = Option<T>;
+
+    fn value(&self, _cx: &App) -> Option<T> {
+        self.selected_value().cloned()
+    }
+
+    fn set_value(&mut self, value: Option<T>, cx: &mut Context<Self>) {
+        self.set_selected(value, cx);
+    }
+
+    fn is_empty(&self, _cx: &App) -> bool {
+        self.selected_value().is_none()
+    }
+}
+
+impl FormControl for Slider {
+    type Value = SliderValue;
+
+    fn value(&self, _cx: &App) -> SliderValue {
+        self.get_value()
+    }
+
+    fn set_value(&mut self, value: SliderValue, cx: &mut Context<Self>) {
+        Slider::set_value(self, value, cx);
+    }
+}
+
+impl FormControl for DatePicker {
+    type Value = Option<DateSelection>;
+
+    fn value(&self, _cx: &App) -> Option<DateSelection> {
+        self.selection()
+    }
+
+    fn set_value(&mut self, value: Option<DateSelection>, cx: &mut Context<Self>) {
+        self.set_selection(value, cx);
+    }
+
+    fn is_empty(&self, _cx: &App) -> bool {
+        self.selection().is_none()
+    }
+
+    fn set_invalid(&mut self, invalid: bool, cx: &mut Context<Self>) {
+        DatePicker::set_invalid(self, invalid, cx);
+    }
+}
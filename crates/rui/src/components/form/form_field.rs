@@ -0,0 +1,298 @@
+use std::{future::Future, time::Duration};
+
+use gpui::{
+    percentage, Animation, AnimationExt, Context, Entity, Focusable, Pixels, Render, Subscription,
+    Task, Transformation,
+};
+
+use super::FormControl;
+use crate::{prelude::*, Direction, Icon, IconName, IconSize, Text};
+
+/// The width of the label column in the horizontal layout.
+pub(crate) const DEFAULT_LABEL_WIDTH: Pixels = px(140.);
+
+type Validator<V> = Box<dyn Fn(&V) -> Result<(), SharedString> + 'static>;
+type AsyncValidator<V> = Box<dyn Fn(V, &mut App) -> Task<Result<(), SharedString>> + 'static>;
+
+/// A labelled control with help text and a validation error, usually added to a [`Form`].
+///
+/// The field tracks whether the value differs from the initial one (dirty) and whether the
+/// control lost focus once (touched). Validation runs on blur, then on every change once the
+/// field is touched: the sync validators first, then the async ones if they all passed.
+///
+/// [`Form`]: crate::Form
+pub struct FormField<C: FormControl> {
+    control: Entity<C>,
+    label: Option<SharedString>,
+    help: Option<SharedString>,
+    required: bool,
+    pub(super) direction: Direction,
+    pub(super) label_width: Pixels,
+    initial: C::Value,
+    value: C::Value,
+    touched: bool,
+    error: Option<SharedString>,
+    validating: bool,
+    /// Bumped on every validation, so an outdated async result is ignored.
+    validation_id: usize,
+    validators: Vec<Validator<C::Value>>,
+    async_validators: Vec<AsyncValidator<C::Value>>,
+    _validate_task: Option<Task<bool>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl<C: FormControl> FormField<C> {
+    /// Wraps the control, its current value is the initial value the field resets to.
+    pub fn new(control: Entity<C>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let value = control.read(cx).value(cx);
+        let focus_handle = control.focus_handle(cx);
+        let subscriptions = vec![
+            cx.observe(&control, |this, control, cx| {
+                let value = control.read(cx).value(cx);
+                if value == this.value {
+                    return;
+                }
+                this.value = value;
+                if this.touched {
+                    this._validate_task = Some(this.validate(cx));
+                }
+                cx.notify();
+            }),
+            cx.on_blur(&focus_handle, window, |this, _, cx| {
+                this.touched = true;
+                this._validate_task = Some(this.validate(cx));
+            }),
+        ];
+
+        Self {
+            control,
+            label: None,
+            help: None,
+            required: false,
+            direction: Direction::Vertical,
+            label_width: DEFAULT_LABEL_WIDTH,
+            initial: value.clone(),
+            value,
+            touched: false,
+            error: None,
+            validating: false,
+            validation_id: 0,
+            validators: Vec::new(),
+            async_validators: Vec::new(),
+            _validate_task: None,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    pub fn label(mut self, label: impl Into<SharedString>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Sets the help text, shown below the control while there is no error.
+    pub fn help(mut self, help: impl Into<SharedString>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// Marks the label with an asterisk and fails validation while the control is empty.
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    /// Adds a validator, returning the error message when the value is invalid.
+    pub fn validator(
+        mut self,
+        validator: impl Fn(&C::Value) -> Result<(), SharedString> + 'static,
+    ) -> Self {
+        self.validators.push(Box::new(validator));
+        self
+    }
+
+    /// Adds a validator running on the background executor, for checks like a server query.
+    ///
+    /// Async validators only run once all the sync validators passed.
+    pub fn async_validator<F, Fut>(mut self, validator: F) -> Self
+    where
+        F: Fn(C::Value) -> Fut + 'static,
+        Fut: Future<Output = Result<(), SharedString>> + Send + 'static,
+        C::Value: Send,
+    {
+        self.async_validators.push(Box::new(move |value, cx| {
+            cx.background_executor().spawn(validator(value))
+        }));
+        self
+    }
+
+    pub fn control(&self) -> &Entity<C> {
+        &self.control
+    }
+
+    /// Whether the value differs from the initial value.
+    pub fn is_dirty(&self) -> bool {
+        self.value != self.initial
+    }
+
+    /// Whether the control lost focus since the field was created or reset.
+    pub fn is_touched(&self) -> bool {
+        self.touched
+    }
+
+    pub fn is_validating(&self) -> bool {
+        self.validating
+    }
+
+    /// Returns the error of the last validation, if any.
+    pub fn error(&self) -> Option<&SharedString> {
+        self.error.as_ref()
+    }
+
+    /// Validates the current value, the task resolves to whether it is valid.
+    ///
+    /// It resolves to `false` when the value changed while the async validators ran, as the
+    /// validated value is outdated.
+    pub fn validate(&mut self, cx: &mut Context<Self>) -> Task<bool> {
+        self.validation_id += 1;
+        let control = self.control.read(cx);
+        let error = if let Some(error) = control.error(cx) {
+            Some(error)
+        } else if self.required && control.is_empty(cx) {
+            Some(SharedString::from("This field is required"))
+        } else {
+            self.validators
+                .iter()
+                .find_map(|validator| validator(&self.value).err())
+        };
+        if error.is_some() || self.async_validators.is_empty() {
+            let valid = error.is_none();
+            self.validating = false;
+            self.set_error(error, cx);
+            return Task::ready(valid);
+        }
+
+        let validation_id = self.validation_id;
+        let tasks: Vec<_> = self
+            .async_validators
+            .iter()
+            .map(|validator| validator(self.value.clone(), cx))
+            .collect();
+        self.validating = true;
+        cx.notify();
+        cx.spawn(async move |this, cx| {
+            let mut error = None;
+            for task in tasks {
+                if let Err(message) = task.await {
+                    error = Some(message);
+                    break;
+                }
+            }
+            this.update(cx, |this, cx| {
+                if this.validation_id != validation_id {
+                    return false;
+                }
+                let valid = error.is_none();
+                this.validating = false;
+                this.set_error(error, cx);
+                valid
+            })
+            .unwrap_or(false)
+        })
+    }
+
+    /// Restores the initial value and clears the touched state and the error.
+    pub fn reset(&mut self, cx: &mut Context<Self>) {
+        self.validation_id += 1;
+        self._validate_task = None;
+        self.validating = false;
+        self.touched = false;
+        // Set before the control, so the observer doesn't see a change.
+        self.value = self.initial.clone();
+        let value = self.initial.clone();
+        self.control
+            .update(cx, |control, cx| control.set_value(value, cx));
+        self.set_error(None, cx);
+    }
+
+    pub(crate) fn mark_touched(&mut self) {
+        self.touched = true;
+    }
+
+    /// Lays out the label and the control, set by the [`Form`](crate::Form) of the field.
+    pub(super) fn set_layout(
+        &mut self,
+        direction: Direction,
+        label_width: Pixels,
+        cx: &mut Context<Self>,
+    ) {
+        self.direction = direction;
+        self.label_width = label_width;
+        cx.notify();
+    }
+
+    fn set_error(&mut self, error: Option<SharedString>, cx: &mut Context<Self>) {
+        let invalid = error.is_some();
+        self.error = error;
+        self.control
+            .update(cx, |control, cx| control.set_invalid(invalid, cx));
+        cx.notify();
+    }
+}
+
+impl<C: FormControl> Render for FormField<C> {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let colors = cx.theme().colors;
+        let label = self.label.clone().map(|label| {
+            h_flex()
+                .gap_0p5()
+                .child(Text::new(label))
+                .when(self.required, |this| {
+                    this.child(Text::new("*").color(colors.danger))
+                })
+        });
+
+        let message = if let Some(error) = self.error.clone() {
+            Some(Text::new(error).color(colors.danger).into_any_element())
+        } else if self.validating {
+            Some(
+                h_flex()
+                    .gap_1()
+                    .text_color(colors.text_muted)
+                    .child(
+                        Icon::new(IconName::Loading)
+                            .size(IconSize::XSmall)
+                            .with_animation(
+                                "validating",
+                                Animation::new(Duration::from_millis(800)).repeat(),
+                                |this, delta| {
+                                    this.transform(Transformation::rotate(percentage(delta)))
+                                },
+                            ),
+                    )
+                    .child("Validating...")
+                    .into_any_element(),
+            )
+        } else {
+            self.help
+                .clone()
+                .map(|help| Text::new(help).color(colors.text_muted).into_any_element())
+        };
+        let message = message.map(|message| div().text_xs().child(message));
+        let control = self.control.clone();
+
+        match self.direction {
+            Direction::Vertical => v_flex()
+                .gap_1p5()
+                .text_sm()
+                .children(label)
+                .child(control)
+                .children(message),
+            Direction::Horizontal => h_flex()
+                .items_start()
+                .gap_4()
+                .text_sm()
+                .child(div().flex_none().w(self.label_width).pt_1().children(label))
+                .child(v_flex().flex_1().gap_1p5().child(control).children(message)),
+        }
+    }
+}
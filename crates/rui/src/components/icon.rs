@@ -51,6 +51,7 @@ impl IconSize {
 }
 
 /// The source of an icon.
+#[derive(Clone)]
 enum IconSource {
     /// An SVG embedded in the Zed binary.
     Svg(SharedString),
@@ -75,7 +76,7 @@ impl IconSource {
     }
 }
 
-#[derive(IntoElement)]
+#[derive(IntoElement, Clone)]
 pub struct Icon {
     source: IconSource,
     color: Option<Hsla>,
@@ -181,6 +182,7 @@ pub enum IconName {
     Book,
     BookCopy,
     BookPlus,
+    Calendar,
     CaseSensitive,
     Check,
     ChevronDown,
@@ -0,0 +1,13 @@
+mod buffer;
+mod editing;
+mod number_format;
+mod number_input;
+mod text_area;
+mod text_area_element;
+mod text_element;
+mod text_input;
+
+pub use number_format::NumberFormat;
+pub use number_input::{NumberInput, NumberInputEvent, NumberMode};
+pub use text_area::TextArea;
+pub use text_input::{InputEvent, TextInput};
@@ -0,0 +1,424 @@
+use std::ops::Range;
+
+/// The maximum number of undo steps kept by a [`TextBuffer`].
+const MAX_HISTORY: usize = 100;
+
+/// The text and selection of an input, with an undo history.
+///
+/// Offsets are byte offsets in UTF-8, always on a char boundary.
+/// The platform input handler works with UTF-16 offsets, see [`TextBuffer::range_from_utf16`].
+#[derive(Debug, Default, Clone)]
+pub(crate) struct TextBuffer {
+    text: String,
+    selected_range: Range<usize>,
+    /// Whether the cursor is at the start of the selection.
+    selection_reversed: bool,
+    /// The range of the text being composed with an IME.
+    marked_range: Option<Range<usize>>,
+    history: History,
+}
+
+/// A state of the buffer that can be restored by undo or redo.
+#[derive(Debug, Clone)]
+struct Snapshot {
+    text: String,
+    selected_range: Range<usize>,
+}
+
+#[derive(Debug, Default, Clone)]
+struct History {
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+    /// Whether the last edit was typing, consecutive typing is undone at once.
+    typing: bool,
+}
+
+impl TextBuffer {
+    pub fn new(text: impl Into<String>) -> Self {
+        let text = text.into();
+        let end = text.len();
+        Self {
+            text,
+            selected_range: end..end,
+            ..Default::default()
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Replaces the whole text, moving the cursor to the end. Can be undone.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        let text = text.into();
+        if text == self.text {
+            return;
+        }
+        self.record(false);
+        let end = text.len();
+        self.text = text;
+        self.selected_range = end..end;
+        self.selection_reversed = false;
+        self.marked_range = None;
+    }
+
+    pub fn selected_range(&self) -> Range<usize> {
+        self.selected_range.clone()
+    }
+
+    pub fn selected_text(&self) -> &str {
+        &self.text[self.selected_range.clone()]
+    }
+
+    pub fn marked_range(&self) -> Option<Range<usize>> {
+        self.marked_range.clone()
+    }
+
+    pub fn unmark(&mut self) {
+        self.marked_range = None;
+    }
+
+    /// The offset of the cursor, the moving end of the selection.
+    pub fn cursor(&self) -> usize {
+        if self.selection_reversed {
+            self.selected_range.start
+        } else {
+            self.selected_range.end
+        }
+    }
+
+    /// Moves the cursor to `offset`, collapsing the selection.
+    pub fn move_to(&mut self, offset: usize) {
+        let offset = self.clamp(offset);
+        self.selected_range = offset..offset;
+        self.selection_reversed = false;
+        self.history.typing = false;
+    }
+
+    /// Moves the cursor to `offset`, extending the selection.
+    pub fn select_to(&mut self, offset: usize) {
+        let offset = self.clamp(offset);
+        if self.selection_reversed {
+            self.selected_range.start = offset;
+        } else {
+            self.selected_range.end = offset;
+        }
+        if self.selected_range.end < self.selected_range.start {
+            self.selection_reversed = !self.selection_reversed;
+            self.selected_range = self.selected_range.end..self.selected_range.start;
+        }
+        self.history.typing = false;
+    }
+
+    pub fn select_all(&mut self) {
+        self.selected_range = 0..self.text.len();
+        self.selection_reversed = false;
+        self.history.typing = false;
+    }
+
+    /// Selects the word at `offset`, or the whitespace around it.
+    pub fn select_word(&mut self, offset: usize) {
+        let offset = self.clamp(offset);
+        let is_word = |c: char| c.is_alphanumeric() || c == '_';
+        let kind = self.text[offset..]
+            .chars()
+            .next()
+            .or_else(|| self.text[..offset].chars().next_back())
+            .map(is_word);
+        let Some(kind) = kind else {
+            return;
+        };
+
+        let start = self.text[..offset]
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| is_word(*c) == kind)
+            .last()
+            .map_or(offset, |(ix, _)| ix);
+        let end = self.text[offset..]
+            .char_indices()
+            .find(|(_, c)| is_word(*c) != kind)
+            .map_or(self.text.len(), |(ix, _)| offset + ix);
+        self.selected_range = start..end;
+        self.selection_reversed = false;
+        self.history.typing = false;
+    }
+
+    /// Replaces `range`, or the marked text, or the selection, with `new_text`
+    /// and moves the cursor after it.
+    pub fn replace(&mut self, range: Option<Range<usize>>, new_text: &str) {
+        let range = self.replaced_range(range);
+        if self.marked_range.is_some() {
+            // Committing a composition, recorded when it started.
+            self.history.redo.clear();
+            self.history.typing = false;
+        } else {
+            let typing = range.is_empty() && !new_text.is_empty() && !new_text.contains(' ');
+            self.record(typing);
+        }
+
+        self.text.replace_range(range.clone(), new_text);
+        let cursor = range.start + new_text.len();
+        self.selected_range = cursor..cursor;
+        self.selection_reversed = false;
+        self.marked_range = None;
+    }
+
+    /// Replaces `range`, or the marked text, or the selection, with `new_text` being composed
+    /// with an IME, and selects `new_selected_range`, relative to `new_text`.
+    pub fn replace_and_mark(
+        &mut self,
+        range: Option<Range<usize>>,
+        new_text: &str,
+        new_selected_range: Option<Range<usize>>,
+    ) {
+        let range = self.replaced_range(range);
+        // The composition is recorded once, when it starts.
+        if self.marked_range.is_none() {
+            self.record(false);
+        }
+
+        self.text.replace_range(range.clone(), new_text);
+        self.marked_range =
+            (!new_text.is_empty()).then(|| range.start..range.start + new_text.len());
+        self.selected_range = new_selected_range
+            .map(|selected| range.start + selected.start..range.start + selected.end)
+            .unwrap_or_else(|| {
+                let cursor = range.start + new_text.len();
+                cursor..cursor
+            });
+        self.selection_reversed = false;
+        self.history.typing = false;
+    }
+
+    fn replaced_range(&self, range: Option<Range<usize>>) -> Range<usize> {
+        let range = range
+            .or_else(|| self.marked_range.clone())
+            .unwrap_or_else(|| self.selected_range.clone());
+        self.clamp(range.start)..self.clamp(range.end)
+    }
+
+    /// Undoes the last edit, returns `false` if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(snapshot) = self.history.undo.pop() else {
+            return false;
+        };
+        let current = self.restore(snapshot);
+        self.history.redo.push(current);
+        true
+    }
+
+    /// Redoes the last undone edit, returns `false` if there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        let Some(snapshot) = self.history.redo.pop() else {
+            return false;
+        };
+        let current = self.restore(snapshot);
+        self.history.undo.push(current);
+        true
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) -> Snapshot {
+        let current = self.snapshot();
+        self.text = snapshot.text;
+        self.selected_range = snapshot.selected_range;
+        self.selection_reversed = false;
+        self.marked_range = None;
+        self.history.typing = false;
+        current
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            text: self.text.clone(),
+            selected_range: self.selected_range.clone(),
+        }
+    }
+
+    /// Records the current state before an edit, consecutive typing is recorded once.
+    fn record(&mut self, typing: bool) {
+        if !(typing && self.history.typing) {
+            let snapshot = self.snapshot();
+            self.history.undo.push(snapshot);
+            if self.history.undo.len() > MAX_HISTORY {
+                self.history.undo.remove(0);
+            }
+        }
+        self.history.redo.clear();
+        self.history.typing = typing;
+    }
+
+    /// Clamps `offset` to the text, on the previous char boundary.
+    fn clamp(&self, offset: usize) -> usize {
+        let mut offset = offset.min(self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        offset
+    }
+
+    /// The offset of the char before `offset`.
+    pub fn previous_boundary(&self, offset: usize) -> usize {
+        self.text[..self.clamp(offset)]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(ix, _)| ix)
+    }
+
+    /// The offset of the char after `offset`.
+    pub fn next_boundary(&self, offset: usize) -> usize {
+        let offset = self.clamp(offset);
+        self.text[offset..]
+            .chars()
+            .next()
+            .map_or(offset, |c| offset + c.len_utf8())
+    }
+
+    /// The start of the word before `offset`, skipping whitespace and punctuation.
+    pub fn previous_word_start(&self, offset: usize) -> usize {
+        let offset = self.clamp(offset);
+        let mut in_word = false;
+        for (ix, c) in self.text[..offset].char_indices().rev() {
+            let is_word = c.is_alphanumeric() || c == '_';
+            if in_word && !is_word {
+                return ix + c.len_utf8();
+            }
+            in_word |= is_word;
+        }
+        0
+    }
+
+    /// The end of the word after `offset`, skipping whitespace and punctuation.
+    pub fn next_word_end(&self, offset: usize) -> usize {
+        let offset = self.clamp(offset);
+        let mut in_word = false;
+        for (ix, c) in self.text[offset..].char_indices() {
+            let is_word = c.is_alphanumeric() || c == '_';
+            if in_word && !is_word {
+                return offset + ix;
+            }
+            in_word |= is_word;
+        }
+        self.text.len()
+    }
+
+    pub fn offset_from_utf16(&self, offset: usize) -> usize {
+        let mut utf8_offset = 0;
+        let mut utf16_count = 0;
+        for c in self.text.chars() {
+            if utf16_count >= offset {
+                break;
+            }
+            utf16_count += c.len_utf16();
+            utf8_offset += c.len_utf8();
+        }
+        utf8_offset
+    }
+
+    pub fn offset_to_utf16(&self, offset: usize) -> usize {
+        self.text[..self.clamp(offset)]
+            .chars()
+            .map(char::len_utf16)
+            .sum()
+    }
+
+    pub fn range_to_utf16(&self, range: &Range<usize>) -> Range<usize> {
+        self.offset_to_utf16(range.start)..self.offset_to_utf16(range.end)
+    }
+
+    pub fn range_from_utf16(&self, range: &Range<usize>) -> Range<usize> {
+        self.offset_from_utf16(range.start)..self.offset_from_utf16(range.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_and_select() {
+        let mut buffer = TextBuffer::new("hello");
+        assert_eq!(buffer.cursor(), 5);
+
+        buffer.replace(None, " world");
+        assert_eq!(buffer.text(), "hello world");
+        assert_eq!(buffer.cursor(), 11);
+
+        buffer.select_to(6);
+        assert_eq!(buffer.selected_text(), "world");
+        assert_eq!(buffer.cursor(), 6);
+        buffer.select_to(2);
+        assert_eq!(buffer.selected_text(), "llo world");
+        buffer.select_to(0);
+        assert_eq!(buffer.selected_range(), 0..11);
+
+        buffer.move_to(5);
+        buffer.select_to(8);
+        buffer.replace(None, "");
+        assert_eq!(buffer.text(), "hellorld");
+        assert_eq!(buffer.selected_range(), 5..5);
+
+        buffer.select_word(1);
+        assert_eq!(buffer.selected_text(), "hellorld");
+    }
+
+    #[test]
+    fn test_boundaries() {
+        let buffer = TextBuffer::new("héllo, wörld_2  end");
+        assert_eq!(buffer.next_boundary(1), 3);
+        assert_eq!(buffer.previous_boundary(3), 1);
+        assert_eq!(buffer.previous_boundary(0), 0);
+        assert_eq!(buffer.next_word_end(0), 6);
+        assert_eq!(buffer.next_word_end(6), 16);
+        assert_eq!(buffer.previous_word_start(16), 8);
+        assert_eq!(buffer.previous_word_start(8), 0);
+        assert_eq!(buffer.previous_word_start(buffer.text().len()), 18);
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let mut buffer = TextBuffer::new("");
+        for c in ["a", "b", "c"] {
+            buffer.replace(None, c);
+        }
+        buffer.replace(None, " ");
+        buffer.replace(None, "d");
+        assert_eq!(buffer.text(), "abc d");
+
+        assert!(buffer.undo());
+        assert_eq!(buffer.text(), "abc ");
+        assert!(buffer.undo());
+        assert_eq!(buffer.text(), "abc");
+        assert!(buffer.undo());
+        assert_eq!(buffer.text(), "");
+        assert!(!buffer.undo());
+
+        assert!(buffer.redo());
+        assert_eq!(buffer.text(), "abc");
+        assert_eq!(buffer.cursor(), 3);
+        buffer.replace(None, "!");
+        assert!(!buffer.redo());
+    }
+
+    #[test]
+    fn test_ime_and_utf16() {
+        let mut buffer = TextBuffer::new("a😀");
+        assert_eq!(buffer.offset_to_utf16(5), 3);
+        assert_eq!(buffer.offset_from_utf16(3), 5);
+        assert_eq!(buffer.range_from_utf16(&(1..3)), 1..5);
+
+        buffer.replace_and_mark(None, "n", None);
+        buffer.replace_and_mark(None, "ni", Some(2..2));
+        assert_eq!(buffer.marked_range(), Some(5..7));
+        buffer.replace(None, "你");
+        assert_eq!(buffer.text(), "a😀你");
+        assert_eq!(buffer.marked_range(), None);
+
+        assert!(buffer.undo());
+        assert_eq!(buffer.text(), "a😀");
+    }
+}
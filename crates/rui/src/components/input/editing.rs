@@ -0,0 +1,299 @@
+use std::ops::Range;
+
+use gpui::{
+    Bounds, ClipboardItem, Context, Focusable, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
+    Pixels, Point, UTF16Selection, Window,
+};
+
+use super::{
+    buffer::TextBuffer,
+    text_input::{
+        Backspace, Copy, Cut, Delete, End, Home, Left, Paste, Redo, Right, SelectAll, SelectLeft,
+        SelectRight, SelectToEnd, SelectToHome, SelectWordLeft, SelectWordRight,
+        ShowCharacterPalette, Undo, WordLeft, WordRight,
+    },
+};
+use crate::MASKED;
+
+/// The editing shared by [`TextInput`](super::TextInput) and [`TextArea`](super::TextArea)
+/// over their [`TextBuffer`]: the action and mouse handlers, and the parts of the platform
+/// input handler that don't depend on the layout.
+pub(super) trait TextEditing: Focusable + Sized + 'static {
+    fn buffer(&self) -> &TextBuffer;
+
+    fn buffer_mut(&mut self) -> &mut TextBuffer;
+
+    /// Whether the text can be edited, it can always be selected and copied.
+    fn editable(&self) -> bool;
+
+    /// Whether every char is painted as the mask glyph. The words of a masked text can't be
+    /// selected, copied or cut.
+    fn is_masked(&self) -> bool {
+        false
+    }
+
+    fn is_selecting(&self) -> bool;
+
+    fn set_selecting(&mut self, selecting: bool);
+
+    /// The text offset closest to a position in the window.
+    fn index_for_position(&self, position: Point<Pixels>) -> usize;
+
+    /// The bounds of the text at the last paint.
+    fn text_bounds(&self) -> Option<Bounds<Pixels>>;
+
+    /// The start and end of the line of the cursor, for home and end.
+    fn line_range(&self) -> (usize, usize) {
+        (0, self.buffer().text().len())
+    }
+
+    /// Applies an edit made through the buffer, notifying listeners if the text changed.
+    fn edit(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        f: impl FnOnce(&mut TextBuffer),
+    );
+
+    /// Inserts pasted `text` in place of the selection.
+    fn insert(&mut self, text: &str, window: &mut Window, cx: &mut Context<Self>);
+
+    fn move_to(&mut self, offset: usize, cx: &mut Context<Self>);
+
+    fn select_to(&mut self, offset: usize, cx: &mut Context<Self>);
+
+    /// Word movement jumps over the whole text when masked, to not reveal the words.
+    fn previous_word_start(&self) -> usize {
+        if self.is_masked() {
+            0
+        } else {
+            self.buffer().previous_word_start(self.buffer().cursor())
+        }
+    }
+
+    fn next_word_end(&self) -> usize {
+        if self.is_masked() {
+            self.buffer().text().len()
+        } else {
+            self.buffer().next_word_end(self.buffer().cursor())
+        }
+    }
+
+    fn left(&mut self, _: &Left, _: &mut Window, cx: &mut Context<Self>) {
+        let range = self.buffer().selected_range();
+        if range.is_empty() {
+            self.move_to(self.buffer().previous_boundary(range.start), cx);
+        } else {
+            self.move_to(range.start, cx);
+        }
+    }
+
+    fn right(&mut self, _: &Right, _: &mut Window, cx: &mut Context<Self>) {
+        let range = self.buffer().selected_range();
+        if range.is_empty() {
+            self.move_to(self.buffer().next_boundary(range.end), cx);
+        } else {
+            self.move_to(range.end, cx);
+        }
+    }
+
+    fn select_left(&mut self, _: &SelectLeft, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_to(self.buffer().previous_boundary(self.buffer().cursor()), cx);
+    }
+
+    fn select_right(&mut self, _: &SelectRight, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_to(self.buffer().next_boundary(self.buffer().cursor()), cx);
+    }
+
+    fn word_left(&mut self, _: &WordLeft, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_to(self.previous_word_start(), cx);
+    }
+
+    fn word_right(&mut self, _: &WordRight, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_to(self.next_word_end(), cx);
+    }
+
+    fn select_word_left(&mut self, _: &SelectWordLeft, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_to(self.previous_word_start(), cx);
+    }
+
+    fn select_word_right(&mut self, _: &SelectWordRight, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_to(self.next_word_end(), cx);
+    }
+
+    fn select_all(&mut self, _: &SelectAll, _: &mut Window, cx: &mut Context<Self>) {
+        self.buffer_mut().select_all();
+        cx.notify();
+    }
+
+    fn home(&mut self, _: &Home, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_to(self.line_range().0, cx);
+    }
+
+    fn end(&mut self, _: &End, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_to(self.line_range().1, cx);
+    }
+
+    fn select_to_home(&mut self, _: &SelectToHome, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_to(self.line_range().0, cx);
+    }
+
+    fn select_to_end(&mut self, _: &SelectToEnd, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_to(self.line_range().1, cx);
+    }
+
+    fn backspace(&mut self, _: &Backspace, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.editable() {
+            return;
+        }
+        let buffer = self.buffer_mut();
+        if buffer.selected_range().is_empty() {
+            buffer.select_to(buffer.previous_boundary(buffer.cursor()));
+        }
+        self.edit(window, cx, |buffer| buffer.replace(None, ""));
+    }
+
+    fn delete(&mut self, _: &Delete, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.editable() {
+            return;
+        }
+        let buffer = self.buffer_mut();
+        if buffer.selected_range().is_empty() {
+            buffer.select_to(buffer.next_boundary(buffer.cursor()));
+        }
+        self.edit(window, cx, |buffer| buffer.replace(None, ""));
+    }
+
+    fn copy(&mut self, _: &Copy, _: &mut Window, cx: &mut Context<Self>) {
+        if self.is_masked() || self.buffer().selected_range().is_empty() {
+            return;
+        }
+        cx.write_to_clipboard(ClipboardItem::new_string(
+            self.buffer().selected_text().to_string(),
+        ));
+    }
+
+    fn cut(&mut self, _: &Cut, window: &mut Window, cx: &mut Context<Self>) {
+        if self.is_masked() || !self.editable() || self.buffer().selected_range().is_empty() {
+            return;
+        }
+        cx.write_to_clipboard(ClipboardItem::new_string(
+            self.buffer().selected_text().to_string(),
+        ));
+        self.edit(window, cx, |buffer| buffer.replace(None, ""));
+    }
+
+    fn paste(&mut self, _: &Paste, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.editable() {
+            return;
+        }
+        let Some(text) = cx.read_from_clipboard().and_then(|item| item.text()) else {
+            return;
+        };
+        self.insert(&text, window, cx);
+    }
+
+    fn undo(&mut self, _: &Undo, window: &mut Window, cx: &mut Context<Self>) {
+        if self.editable() {
+            self.edit(window, cx, |buffer| {
+                buffer.undo();
+            });
+        }
+    }
+
+    fn redo(&mut self, _: &Redo, window: &mut Window, cx: &mut Context<Self>) {
+        if self.editable() {
+            self.edit(window, cx, |buffer| {
+                buffer.redo();
+            });
+        }
+    }
+
+    fn show_character_palette(
+        &mut self,
+        _: &ShowCharacterPalette,
+        window: &mut Window,
+        _: &mut Context<Self>,
+    ) {
+        window.show_character_palette();
+    }
+
+    fn on_mouse_down(
+        &mut self,
+        event: &MouseDownEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        window.focus(&self.focus_handle(cx));
+        let offset = self.index_for_position(event.position);
+        match event.click_count {
+            1 => {
+                self.set_selecting(true);
+                if event.modifiers.shift {
+                    self.select_to(offset, cx);
+                } else {
+                    self.move_to(offset, cx);
+                }
+            }
+            2 if !self.is_masked() => {
+                self.buffer_mut().select_word(offset);
+                cx.notify();
+            }
+            _ => {
+                self.buffer_mut().select_all();
+                cx.notify();
+            }
+        }
+    }
+
+    fn on_mouse_up(&mut self, _: &MouseUpEvent, _: &mut Window, _: &mut Context<Self>) {
+        self.set_selecting(false);
+    }
+
+    fn on_mouse_move(&mut self, event: &MouseMoveEvent, _: &mut Window, cx: &mut Context<Self>) {
+        if self.is_selecting() {
+            self.select_to(self.index_for_position(event.position), cx);
+        }
+    }
+
+    /// The text in a UTF-16 range for the input handler, masked like it is painted.
+    fn text_for_utf16_range(
+        &self,
+        range_utf16: &Range<usize>,
+        actual_range: &mut Option<Range<usize>>,
+    ) -> String {
+        let buffer = self.buffer();
+        let range = buffer.range_from_utf16(range_utf16);
+        actual_range.replace(buffer.range_to_utf16(&range));
+        if self.is_masked() {
+            MASKED.repeat(buffer.text()[range].chars().count())
+        } else {
+            buffer.text()[range].to_string()
+        }
+    }
+
+    fn utf16_selection(&self) -> UTF16Selection {
+        let buffer = self.buffer();
+        UTF16Selection {
+            range: buffer.range_to_utf16(&buffer.selected_range()),
+            reversed: buffer.cursor() < buffer.selected_range().end,
+        }
+    }
+
+    fn marked_utf16_range(&self) -> Option<Range<usize>> {
+        let buffer = self.buffer();
+        buffer
+            .marked_range()
+            .map(|range| buffer.range_to_utf16(&range))
+    }
+
+    /// The UTF-16 offset of the char at `point`, if it is in the text bounds.
+    fn utf16_index_for_point(&self, point: Point<Pixels>) -> Option<usize> {
+        let bounds = self.text_bounds()?;
+        if !bounds.contains(&point) {
+            return None;
+        }
+        let offset = self.index_for_position(point);
+        Some(self.buffer().offset_to_utf16(offset))
+    }
+}
@@ -0,0 +1,156 @@
+/// How numbers are written in a locale: the decimal separator and the thousands separator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberFormat {
+    pub decimal_separator: char,
+    /// Separates groups of three digits, `None` to not group digits.
+    pub group_separator: Option<char>,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            decimal_separator: '.',
+            group_separator: Some(','),
+        }
+    }
+}
+
+impl NumberFormat {
+    /// Returns the format of a locale like `de-DE`, `fr_FR.UTF-8` or `en`.
+    ///
+    /// Unknown locales use the English format.
+    pub fn from_locale(locale: &str) -> Self {
+        let locale = locale
+            .split('.')
+            .next()
+            .unwrap_or_default()
+            .replace('-', "_");
+        let language = locale.split('_').next().unwrap_or_default().to_lowercase();
+        let (decimal_separator, group_separator) = match language.as_str() {
+            _ if locale.eq_ignore_ascii_case("de_ch") => ('.', '’'),
+            "de" | "nl" | "es" | "it" | "pt" | "da" | "tr" | "el" | "id" => (',', '.'),
+            "fr" => (',', '\u{202f}'),
+            "ru" | "uk" | "pl" | "cs" | "sk" | "sv" | "fi" | "nb" | "no" | "hu" | "bg" => {
+                (',', '\u{a0}')
+            }
+            _ => return Self::default(),
+        };
+        Self {
+            decimal_separator,
+            group_separator: Some(group_separator),
+        }
+    }
+
+    /// Returns the format of the locale set in the `LC_ALL`, `LC_NUMERIC` or `LANG`
+    /// environment variables, the first one set.
+    ///
+    /// The platform locale settings, like the region format of macOS or Windows, are not read:
+    /// use [`NumberFormat::from_locale`] to follow them. Without these variables, the English
+    /// format is used.
+    pub fn from_env() -> Self {
+        ["LC_ALL", "LC_NUMERIC", "LANG"]
+            .into_iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|locale| !locale.is_empty())
+            .map(|locale| Self::from_locale(&locale))
+            .unwrap_or_default()
+    }
+
+    /// Returns this format without digit grouping, for editing.
+    pub fn without_grouping(self) -> Self {
+        Self {
+            group_separator: None,
+            ..self
+        }
+    }
+
+    /// Formats `value`, rounded to `precision` decimals if any.
+    pub fn format(&self, value: f64, precision: Option<usize>) -> String {
+        let digits = match precision {
+            Some(precision) => format!("{:.precision$}", value.abs()),
+            None => value.abs().to_string(),
+        };
+        let (integer, fraction) = digits.split_once('.').unwrap_or((&digits, ""));
+
+        let mut text = String::new();
+        if value < 0. && digits.chars().any(|c| c.is_ascii_digit() && c != '0') {
+            text.push('-');
+        }
+        for (ix, c) in integer.chars().enumerate() {
+            let remaining = integer.len() - ix;
+            if ix > 0 && remaining % 3 == 0 {
+                text.extend(self.group_separator);
+            }
+            text.push(c);
+        }
+        if !fraction.is_empty() {
+            text.push(self.decimal_separator);
+            text.push_str(fraction);
+        }
+        text
+    }
+
+    /// Parses a number written in this format, digit grouping is optional.
+    pub fn parse(&self, text: &str) -> Option<f64> {
+        let mut normalized = String::with_capacity(text.len());
+        for c in text.trim().chars() {
+            if Some(c) == self.group_separator || c.is_whitespace() && self.groups_with_space() {
+                continue;
+            }
+            match c {
+                '0'..='9' | '-' | '+' => normalized.push(c),
+                c if c == self.decimal_separator => normalized.push('.'),
+                _ => return None,
+            }
+        }
+        normalized
+            .parse()
+            .ok()
+            .filter(|value: &f64| value.is_finite())
+    }
+
+    fn groups_with_space(&self) -> bool {
+        self.group_separator.is_some_and(char::is_whitespace)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format() {
+        let en = NumberFormat::default();
+        assert_eq!(en.format(1234567.891, None), "1,234,567.891");
+        assert_eq!(en.format(-1234.5, Some(2)), "-1,234.50");
+        assert_eq!(en.format(999., Some(0)), "999");
+        assert_eq!(en.format(-0.001, Some(2)), "0.00");
+        assert_eq!(en.without_grouping().format(12345., None), "12345");
+
+        let de = NumberFormat::from_locale("de-DE");
+        assert_eq!(de.format(1234.5, None), "1.234,5");
+        let ch = NumberFormat::from_locale("de_CH.UTF-8");
+        assert_eq!(ch.format(1234.5, None), "1’234.5");
+        let fr = NumberFormat::from_locale("fr_FR");
+        assert_eq!(fr.format(1234.5, None), "1\u{202f}234,5");
+    }
+
+    #[test]
+    fn test_parse() {
+        let en = NumberFormat::default();
+        assert_eq!(en.parse("1,234.5"), Some(1234.5));
+        assert_eq!(en.parse(" -12 "), Some(-12.));
+        assert_eq!(en.parse("1.2.3"), None);
+        assert_eq!(en.parse("abc"), None);
+        assert_eq!(en.parse("inf"), None);
+        assert_eq!(en.parse(""), None);
+
+        let de = NumberFormat::from_locale("de");
+        assert_eq!(de.parse("1.234,5"), Some(1234.5));
+        assert_eq!(de.parse("1234,5"), Some(1234.5));
+
+        let fr = NumberFormat::from_locale("fr");
+        assert_eq!(fr.parse("1 234,5"), Some(1234.5));
+        assert_eq!(fr.parse("1\u{202f}234,5"), Some(1234.5));
+    }
+}
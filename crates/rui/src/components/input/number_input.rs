@@ -0,0 +1,629 @@
+use std::time::Duration;
+
+use gpui::{
+    actions, App, Context, Entity, EventEmitter, FocusHandle, Focusable, KeyBinding, MouseButton,
+    MouseDownEvent, Pixels, Render, ScrollWheelEvent, Subscription, Task, Window,
+};
+
+use super::{number_format::NumberFormat, text_input::TextInput};
+use crate::{prelude::*, Button, IconName, InputEvent, Text};
+
+actions!(number_input, [Increment, Decrement]);
+
+const CONTEXT: &str = "NumberInput";
+
+/// How long a stepper button is held before the value repeats stepping.
+const REPEAT_DELAY: Duration = Duration::from_millis(400);
+/// How often the value steps while a stepper button is held.
+const REPEAT_INTERVAL: Duration = Duration::from_millis(60);
+
+/// Whether a [`NumberInput`] accepts decimals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NumberMode {
+    Integer,
+    #[default]
+    Decimal,
+}
+
+/// Events emitted by a [`NumberInput`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumberInputEvent {
+    /// The value changed to a valid number.
+    Change(f64),
+}
+
+/// A numeric input with increment and decrement buttons.
+///
+/// The value steps with the buttons, which repeat while held, the up and down keys and the
+/// scroll wheel while focused. The number is shown in the [`NumberFormat`] of the locale set in
+/// the environment, see [`NumberFormat::from_env`], and invalid or out of range text is shown as
+/// an error below the input.
+///
+/// Call [`NumberInput::bind_keys`] and [`TextInput::bind_keys`] once at startup.
+pub struct NumberInput {
+    input: Entity<TextInput>,
+    value: Option<f64>,
+    mode: NumberMode,
+    min: Option<f64>,
+    max: Option<f64>,
+    step: f64,
+    precision: Option<usize>,
+    format: NumberFormat,
+    size: Size,
+    disabled: bool,
+    placeholder: SharedString,
+    error: Option<SharedString>,
+    focused: bool,
+    on_change: Option<Box<dyn Fn(f64, &mut Window, &mut App) + 'static>>,
+    /// The scrolled distance not yet turned into steps.
+    wheel_delta: Pixels,
+    _repeat_task: Option<Task<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl EventEmitter<NumberInputEvent> for NumberInput {}
+
+impl Focusable for NumberInput {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.input.focus_handle(cx)
+    }
+}
+
+impl NumberInput {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let input = cx.new(|cx| TextInput::new(cx));
+        let focus_handle = input.focus_handle(cx);
+        let subscriptions = vec![
+            cx.subscribe_in(&input, window, |this, _, event, window, cx| match event {
+                InputEvent::Change(text) => this.validate(text, window, cx),
+                InputEvent::Submit(_) => this.format_text(cx),
+            }),
+            cx.on_focus(&focus_handle, window, |this, _, cx| {
+                this.focused = true;
+                this.format_text(cx);
+            }),
+            cx.on_blur(&focus_handle, window, |this, _, cx| {
+                this.focused = false;
+                this.format_text(cx);
+            }),
+        ];
+        // The builders are applied once `new` returns, push them to the input.
+        cx.defer(|this, cx| {
+            this.sync_input(cx);
+            this.format_text(cx);
+        });
+
+        Self {
+            input,
+            value: None,
+            mode: NumberMode::default(),
+            min: None,
+            max: None,
+            step: 1.,
+            precision: None,
+            format: NumberFormat::from_env(),
+            size: Size::default(),
+            disabled: false,
+            placeholder: SharedString::default(),
+            error: None,
+            focused: false,
+            on_change: None,
+            wheel_delta: px(0.),
+            _repeat_task: None,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    pub fn bind_keys(cx: &mut App) {
+        cx.bind_keys([
+            KeyBinding::new("up", Increment, Some(CONTEXT)),
+            KeyBinding::new("down", Decrement, Some(CONTEXT)),
+        ]);
+    }
+
+    /// Sets the initial value.
+    pub fn value(mut self, value: f64) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    pub fn mode(mut self, mode: NumberMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Only accepts whole numbers.
+    pub fn integer(mut self) -> Self {
+        self.mode = NumberMode::Integer;
+        self
+    }
+
+    pub fn min(mut self, min: f64) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    pub fn max(mut self, max: f64) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Sets how much the value changes per step, default: 1.
+    pub fn step(mut self, step: f64) -> Self {
+        self.step = step.abs();
+        self
+    }
+
+    /// Shows the value with `precision` decimals, by default as many as needed.
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Sets the number format, default: [`NumberFormat::from_env`].
+    pub fn format(mut self, format: NumberFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn size(mut self, size: Size) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    pub fn placeholder(mut self, placeholder: impl Into<SharedString>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Called with the value when it changes to a valid number.
+    pub fn on_change(mut self, handler: impl Fn(f64, &mut Window, &mut App) + 'static) -> Self {
+        self.on_change = Some(Box::new(handler));
+        self
+    }
+
+    /// Returns the last valid value, `None` if the input is empty.
+    pub fn get_value(&self) -> Option<f64> {
+        self.value
+    }
+
+    /// Sets the value, clamped to the bounds, without calling `on_change`.
+    pub fn set_value(&mut self, value: f64, cx: &mut Context<Self>) {
+        self.value = Some(self.clamp(value));
+        self.set_error(None, cx);
+        self.format_text(cx);
+    }
+
+    /// Empties the input, without calling `on_change`.
+    pub fn clear(&mut self, cx: &mut Context<Self>) {
+        self.value = None;
+        self.set_error(None, cx);
+        self.format_text(cx);
+    }
+
+    /// Returns the validation error of the text, if any.
+    pub fn error(&self) -> Option<&SharedString> {
+        self.error.as_ref()
+    }
+
+    fn sync_input(&mut self, cx: &mut Context<Self>) {
+        let (size, disabled, placeholder) = (self.size, self.disabled, self.placeholder.clone());
+        self.input.update(cx, |input, cx| {
+            input.set_size(size, cx);
+            input.set_disabled(disabled, cx);
+            input.set_placeholder(placeholder, cx);
+        });
+    }
+
+    /// The number of decimals values are rounded to.
+    fn decimals(&self) -> Option<usize> {
+        match self.mode {
+            NumberMode::Integer => Some(0),
+            NumberMode::Decimal => self.precision,
+        }
+    }
+
+    fn clamp(&self, value: f64) -> f64 {
+        let value = self.min.map_or(value, |min| value.max(min));
+        self.max.map_or(value, |max| value.min(max))
+    }
+
+    /// Rounds `value` to the decimals of the precision or of the step, to avoid float drift.
+    fn round(&self, value: f64) -> f64 {
+        let decimals = self.decimals().unwrap_or_else(|| {
+            self.step
+                .to_string()
+                .split_once('.')
+                .map_or(0, |(_, fraction)| fraction.len())
+        });
+        let factor = 10f64.powi(decimals.min(15) as i32);
+        (value * factor).round() / factor
+    }
+
+    /// Writes the value in the input, without digit grouping while editing.
+    fn format_text(&mut self, cx: &mut Context<Self>) {
+        if self.error.is_some() {
+            return;
+        }
+        let format = if self.focused {
+            self.format.without_grouping()
+        } else {
+            self.format
+        };
+        let text = self
+            .value
+            .map(|value| format.format(value, self.decimals()))
+            .unwrap_or_default();
+        self.input.update(cx, |input, cx| {
+            if input.text().as_ref() != text {
+                input.set_text(text, cx);
+            }
+        });
+        cx.notify();
+    }
+
+    /// Validates the edited text, updating the value when it is a valid number.
+    fn validate(&mut self, text: &str, window: &mut Window, cx: &mut Context<Self>) {
+        let error = if text.trim().is_empty() {
+            self.value = None;
+            None
+        } else {
+            match self.format.parse(text) {
+                None => Some("Enter a valid number".into()),
+                Some(value) if self.mode == NumberMode::Integer && value.fract() != 0. => {
+                    Some("Enter a whole number".into())
+                }
+                Some(value) if self.min.is_some_and(|min| value < min) => {
+                    Some(format!("Must be at least {}", self.format_bound(self.min)).into())
+                }
+                Some(value) if self.max.is_some_and(|max| value > max) => {
+                    Some(format!("Must be at most {}", self.format_bound(self.max)).into())
+                }
+                Some(value) => {
+                    self.change(value, window, cx);
+                    None
+                }
+            }
+        };
+        self.set_error(error, cx);
+    }
+
+    fn format_bound(&self, bound: Option<f64>) -> String {
+        bound
+            .map(|bound| self.format.format(bound, self.decimals()))
+            .unwrap_or_default()
+    }
+
+    fn set_error(&mut self, error: Option<SharedString>, cx: &mut Context<Self>) {
+        let invalid = error.is_some();
+        self.error = error;
+        self.input
+            .update(cx, |input, cx| input.set_invalid(invalid, cx));
+        cx.notify();
+    }
+
+    fn change(&mut self, value: f64, window: &mut Window, cx: &mut Context<Self>) {
+        if self.value == Some(value) {
+            return;
+        }
+        self.value = Some(value);
+        if let Some(on_change) = self.on_change.as_ref() {
+            on_change(value, window, cx);
+        }
+        cx.emit(NumberInputEvent::Change(value));
+    }
+
+    /// Changes the value by `steps` steps, within the bounds.
+    fn step_by(&mut self, steps: f64, window: &mut Window, cx: &mut Context<Self>) {
+        if self.disabled {
+            return;
+        }
+        let value = match self.value {
+            Some(value) => self.round(value + steps * self.step),
+            None => 0.,
+        };
+        let clamped = self.clamp(value);
+        if clamped != value {
+            // Holding a stepper at a bound has nothing left to do.
+            self.stop_stepping();
+        }
+        self.set_error(None, cx);
+        self.change(clamped, window, cx);
+        self.format_text(cx);
+    }
+
+    fn increment(&mut self, _: &Increment, window: &mut Window, cx: &mut Context<Self>) {
+        self.step_by(1., window, cx);
+    }
+
+    fn decrement(&mut self, _: &Decrement, window: &mut Window, cx: &mut Context<Self>) {
+        self.step_by(-1., window, cx);
+    }
+
+    /// Steps once, then repeatedly while the stepper button is held.
+    fn start_stepping(&mut self, steps: f64, window: &mut Window, cx: &mut Context<Self>) {
+        // Keep the focus in the input.
+        window.prevent_default();
+        window.focus(&self.input.focus_handle(cx));
+        self.step_by(steps, window, cx);
+        self._repeat_task = Some(cx.spawn_in(window, async move |this, cx| {
+            cx.background_executor().timer(REPEAT_DELAY).await;
+            loop {
+                if this
+                    .update_in(cx, |this, window, cx| this.step_by(steps, window, cx))
+                    .is_err()
+                {
+                    break;
+                }
+                cx.background_executor().timer(REPEAT_INTERVAL).await;
+            }
+        }));
+    }
+
+    fn stop_stepping(&mut self) {
+        self._repeat_task = None;
+    }
+
+    fn on_scroll_wheel(
+        &mut self,
+        event: &ScrollWheelEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.input.focus_handle(cx).is_focused(window) {
+            return;
+        }
+        cx.stop_propagation();
+        let line_height = window.line_height();
+        self.wheel_delta += event.delta.pixel_delta(line_height).y;
+        let steps = (self.wheel_delta / line_height).trunc();
+        if steps != 0. {
+            self.wheel_delta -= line_height * steps;
+            self.step_by(steps, window, cx);
+        }
+    }
+
+    fn stepper(
+        &self,
+        id: &'static str,
+        icon: IconName,
+        steps: f64,
+        disabled: bool,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        div()
+            .when(!disabled, |this| {
+                this.on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(move |this, _: &MouseDownEvent, window, cx| {
+                        this.start_stepping(steps, window, cx)
+                    }),
+                )
+            })
+            // Reaching a bound while held disables the stepper, it must still stop on release.
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(|this, _, _, _| this.stop_stepping()),
+            )
+            .on_mouse_up_out(
+                MouseButton::Left,
+                cx.listener(|this, _, _, _| this.stop_stepping()),
+            )
+            .child(
+                Button::new(id)
+                    .icon(icon)
+                    .outline()
+                    .size(self.size)
+                    .disabled(disabled),
+            )
+    }
+}
+
+impl Render for NumberInput {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let disabled = self.disabled;
+        let at_min = self
+            .value
+            .zip(self.min)
+            .is_some_and(|(value, min)| value <= min);
+        let at_max = self
+            .value
+            .zip(self.max)
+            .is_some_and(|(value, max)| value >= max);
+
+        v_flex()
+            .w_full()
+            .gap_1()
+            .child(
+                h_flex()
+                    .key_context(CONTEXT)
+                    .on_action(cx.listener(Self::increment))
+                    .on_action(cx.listener(Self::decrement))
+                    .on_scroll_wheel(cx.listener(Self::on_scroll_wheel))
+                    .gap_1()
+                    .child(div().flex_1().child(self.input.clone()))
+                    .child(self.stepper(
+                        "decrement",
+                        IconName::ChevronDown,
+                        -1.,
+                        disabled || at_min,
+                        cx,
+                    ))
+                    .child(self.stepper(
+                        "increment",
+                        IconName::ChevronUp,
+                        1.,
+                        disabled || at_max,
+                        cx,
+                    )),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(Text::new(error).text_xs().color(cx.theme().colors.danger))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use gpui::{TestAppContext, VisualTestContext};
+
+    use super::*;
+    use crate::Theme;
+
+    fn build_input(
+        cx: &mut TestAppContext,
+        build: impl FnOnce(NumberInput) -> NumberInput + 'static,
+    ) -> (Entity<NumberInput>, &mut VisualTestContext) {
+        cx.update(|cx| {
+            Theme::init(cx, None, None);
+            TextInput::bind_keys(cx);
+            NumberInput::bind_keys(cx);
+        });
+        let (input, cx) = cx.add_window_view(|window, cx| {
+            build(NumberInput::new(window, cx).format(NumberFormat::default()))
+        });
+        cx.run_until_parked();
+        (input, cx)
+    }
+
+    fn text(input: &Entity<NumberInput>, cx: &mut VisualTestContext) -> String {
+        cx.read(|cx| input.read(cx).input.read(cx).text().to_string())
+    }
+
+    fn value(input: &Entity<NumberInput>, cx: &mut VisualTestContext) -> Option<f64> {
+        cx.read(|cx| input.read(cx).get_value())
+    }
+
+    fn error(input: &Entity<NumberInput>, cx: &mut VisualTestContext) -> Option<SharedString> {
+        cx.read(|cx| input.read(cx).error().cloned())
+    }
+
+    fn step(input: &Entity<NumberInput>, steps: f64, cx: &mut VisualTestContext) {
+        input.update_in(cx, |input, window, cx| input.step_by(steps, window, cx));
+    }
+
+    fn validate(input: &Entity<NumberInput>, text: &str, cx: &mut VisualTestContext) {
+        input.update_in(cx, |input, window, cx| input.validate(text, window, cx));
+    }
+
+    #[gpui::test]
+    fn test_builders_are_applied(cx: &mut TestAppContext) {
+        let (input, cx) = build_input(cx, |input| {
+            input
+                .value(1234.5)
+                .precision(2)
+                .disabled(true)
+                .placeholder("Amount")
+        });
+        assert_eq!(text(&input, cx), "1,234.50");
+        cx.read(|cx| {
+            let text_input = input.read(cx).input.read(cx);
+            assert!(text_input.disabled);
+            assert_eq!(text_input.placeholder, SharedString::from("Amount"));
+        });
+    }
+
+    #[gpui::test]
+    fn test_step(cx: &mut TestAppContext) {
+        let (input, cx) = build_input(cx, |input| input.value(0.1).step(0.2));
+
+        step(&input, 1., cx);
+        // Rounded to the decimals of the step, without float drift.
+        assert_eq!(value(&input, cx), Some(0.3));
+        assert_eq!(text(&input, cx), "0.3");
+
+        step(&input, -3., cx);
+        assert_eq!(value(&input, cx), Some(-0.3));
+
+        input.update(cx, |input, cx| input.clear(cx));
+        step(&input, 1., cx);
+        assert_eq!(value(&input, cx), Some(0.));
+    }
+
+    #[gpui::test]
+    fn test_step_with_keys(cx: &mut TestAppContext) {
+        let (input, cx) = build_input(cx, |input| input.value(5.));
+        cx.update(|window, cx| window.focus(&input.focus_handle(cx)));
+
+        cx.simulate_keystrokes("up up");
+        assert_eq!(value(&input, cx), Some(7.));
+        cx.simulate_keystrokes("down");
+        assert_eq!(value(&input, cx), Some(6.));
+    }
+
+    #[gpui::test]
+    fn test_step_disabled(cx: &mut TestAppContext) {
+        let (input, cx) = build_input(cx, |input| input.value(5.).disabled(true));
+        step(&input, 1., cx);
+        assert_eq!(value(&input, cx), Some(5.));
+    }
+
+    #[gpui::test]
+    fn test_clamp(cx: &mut TestAppContext) {
+        let (input, cx) = build_input(cx, |input| input.min(0.).max(10.).value(8.).step(5.));
+
+        step(&input, 1., cx);
+        assert_eq!(value(&input, cx), Some(10.));
+        step(&input, -3., cx);
+        assert_eq!(value(&input, cx), Some(0.));
+
+        input.update(cx, |input, cx| input.set_value(20., cx));
+        assert_eq!(value(&input, cx), Some(10.));
+        assert_eq!(text(&input, cx), "10");
+
+        // Out of range text is an error, the value is kept.
+        validate(&input, "11", cx);
+        assert_eq!(value(&input, cx), Some(10.));
+        assert_eq!(error(&input, cx), Some("Must be at most 10".into()));
+        validate(&input, "-1", cx);
+        assert_eq!(error(&input, cx), Some("Must be at least 0".into()));
+    }
+
+    #[gpui::test]
+    fn test_parse_localized(cx: &mut TestAppContext) {
+        let (input, cx) = build_input(cx, |input| {
+            input.format(NumberFormat::from_locale("de-DE")).value(1.)
+        });
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        cx.update(|_, cx| {
+            let changes = changes.clone();
+            cx.subscribe(&input, move |_, event: &NumberInputEvent, _| {
+                changes.borrow_mut().push(*event)
+            })
+            .detach();
+        });
+
+        validate(&input, "1.234,5", cx);
+        assert_eq!(value(&input, cx), Some(1234.5));
+        assert_eq!(error(&input, cx), None);
+        assert_eq!(*changes.borrow(), [NumberInputEvent::Change(1234.5)]);
+
+        // English text is not a valid German number.
+        validate(&input, "1,234.5", cx);
+        assert_eq!(value(&input, cx), Some(1234.5));
+        assert_eq!(error(&input, cx), Some("Enter a valid number".into()));
+
+        input.update(cx, |input, cx| input.set_value(9876.5, cx));
+        assert_eq!(text(&input, cx), "9.876,5");
+    }
+
+    #[gpui::test]
+    fn test_integer_mode(cx: &mut TestAppContext) {
+        let (input, cx) = build_input(cx, |input| input.integer());
+
+        validate(&input, "1.5", cx);
+        assert_eq!(value(&input, cx), None);
+        assert_eq!(error(&input, cx), Some("Enter a whole number".into()));
+
+        validate(&input, "", cx);
+        assert_eq!(value(&input, cx), None);
+        assert_eq!(error(&input, cx), None);
+    }
+}
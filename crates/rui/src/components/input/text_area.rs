@@ -0,0 +1,641 @@
+use std::{
+    ops::Range,
+    time::{Duration, Instant},
+};
+
+use gpui::{
+    actions, point, App, Bounds, Context, EntityInputHandler, EventEmitter, FocusHandle, Focusable,
+    KeyBinding, MouseButton, Pixels, Point, Render, ScrollWheelEvent, Task, UTF16Selection, Window,
+};
+
+use super::{
+    buffer::TextBuffer,
+    editing::TextEditing,
+    text_area_element::{TextAreaElement, TextAreaLayout},
+    text_input::{editing_bindings, range_from_utf16, Enter},
+};
+use crate::{prelude::*, InputEvent, Text};
+
+actions!(text_area, [Up, Down, SelectUp, SelectDown, Newline]);
+
+const CONTEXT: &str = "TextArea";
+
+/// How long the scrollbar stays visible after scrolling, with [`ScrollbarShow::Scrolling`].
+///
+/// [`ScrollbarShow::Scrolling`]: crate::ScrollbarShow::Scrolling
+const SCROLLBAR_HIDE_DELAY: Duration = Duration::from_secs(1);
+
+/// A multi-line text input that wraps its text and grows with it between
+/// [`min_rows`](TextArea::min_rows) and [`max_rows`](TextArea::max_rows), then scrolls.
+///
+/// Enter inserts a newline, `secondary-enter` submits. Call [`TextArea::bind_keys`] once at
+/// startup to bind the editing keys.
+pub struct TextArea {
+    pub(super) focus_handle: FocusHandle,
+    pub(super) buffer: TextBuffer,
+    pub(super) placeholder: SharedString,
+    size: Size,
+    pub(super) disabled: bool,
+    read_only: bool,
+    pub(super) min_rows: usize,
+    pub(super) max_rows: Option<usize>,
+    max_length: Option<usize>,
+    show_count: bool,
+    on_change: Option<Box<dyn Fn(&SharedString, &mut Window, &mut App) + 'static>>,
+    on_submit: Option<Box<dyn Fn(&SharedString, &mut Window, &mut App) + 'static>>,
+    pub(super) scroll_y: Pixels,
+    /// Whether to scroll to the cursor on the next paint.
+    pub(super) autoscroll: bool,
+    pub(super) hovered: bool,
+    scrolled_at: Option<Instant>,
+    pub(super) last_layout: Option<TextAreaLayout>,
+    pub(super) last_bounds: Option<Bounds<Pixels>>,
+    /// The x position kept when moving up and down.
+    goal_x: Option<Pixels>,
+    is_selecting: bool,
+    _hide_scrollbar_task: Option<Task<()>>,
+}
+
+impl EventEmitter<InputEvent> for TextArea {}
+
+impl Focusable for TextArea {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl TextArea {
+    pub fn new(cx: &mut App) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            buffer: TextBuffer::default(),
+            placeholder: SharedString::default(),
+            size: Size::default(),
+            disabled: false,
+            read_only: false,
+            min_rows: 2,
+            max_rows: None,
+            max_length: None,
+            show_count: false,
+            on_change: None,
+            on_submit: None,
+            scroll_y: px(0.),
+            autoscroll: false,
+            hovered: false,
+            scrolled_at: None,
+            last_layout: None,
+            last_bounds: None,
+            goal_x: None,
+            is_selecting: false,
+            _hide_scrollbar_task: None,
+        }
+    }
+
+    pub fn bind_keys(cx: &mut App) {
+        cx.bind_keys(editing_bindings(CONTEXT));
+        cx.bind_keys([
+            KeyBinding::new("up", Up, Some(CONTEXT)),
+            KeyBinding::new("down", Down, Some(CONTEXT)),
+            KeyBinding::new("shift-up", SelectUp, Some(CONTEXT)),
+            KeyBinding::new("shift-down", SelectDown, Some(CONTEXT)),
+            KeyBinding::new("enter", Newline, Some(CONTEXT)),
+            KeyBinding::new("shift-enter", Newline, Some(CONTEXT)),
+            KeyBinding::new("secondary-enter", Enter, Some(CONTEXT)),
+        ]);
+    }
+
+    pub fn placeholder(mut self, placeholder: impl Into<SharedString>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Sets the initial text, with the cursor at the end.
+    pub fn default_text(mut self, text: impl Into<SharedString>) -> Self {
+        self.buffer = TextBuffer::new(text.into().to_string());
+        self
+    }
+
+    pub fn size(mut self, size: Size) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// A disabled area can't be focused or edited.
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// A read-only area can be focused, selected and copied, but not edited.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Sets the number of rows shown when the text is shorter, default: 2.
+    pub fn min_rows(mut self, rows: usize) -> Self {
+        self.min_rows = rows;
+        self
+    }
+
+    /// Sets the number of rows the area grows to before scrolling, default: unlimited.
+    pub fn max_rows(mut self, rows: usize) -> Self {
+        self.max_rows = Some(rows);
+        self
+    }
+
+    /// Limits the number of chars that can be entered.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Shows the number of chars, and the max length if any, below the text.
+    pub fn show_count(mut self, show_count: bool) -> Self {
+        self.show_count = show_count;
+        self
+    }
+
+    /// Called with the new text after every edit.
+    pub fn on_change(
+        mut self,
+        handler: impl Fn(&SharedString, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_change = Some(Box::new(handler));
+        self
+    }
+
+    /// Called with the text when `secondary-enter` is pressed.
+    pub fn on_submit(
+        mut self,
+        handler: impl Fn(&SharedString, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_submit = Some(Box::new(handler));
+        self
+    }
+
+    pub fn text(&self) -> SharedString {
+        SharedString::from(self.buffer.text().to_string())
+    }
+
+    /// Replaces the text, truncated to the max length, without calling `on_change`.
+    /// Can be undone.
+    pub fn set_text(&mut self, text: impl Into<SharedString>, cx: &mut Context<Self>) {
+        let text = text.into();
+        let text = self
+            .truncate(&text, 0..self.buffer.text().len())
+            .to_string();
+        self.buffer.set_text(text);
+        self.autoscroll = true;
+        cx.notify();
+    }
+
+    pub fn set_disabled(&mut self, disabled: bool, cx: &mut Context<Self>) {
+        self.disabled = disabled;
+        cx.notify();
+    }
+
+    pub fn set_read_only(&mut self, read_only: bool, cx: &mut Context<Self>) {
+        self.read_only = read_only;
+        cx.notify();
+    }
+
+    /// Whether the area was scrolled recently, to show the scrollbar.
+    pub(super) fn is_scrolling(&self) -> bool {
+        self.scrolled_at
+            .is_some_and(|scrolled_at| scrolled_at.elapsed() < SCROLLBAR_HIDE_DELAY)
+    }
+
+    /// Truncates `text` to fit in the max length, when replacing the selection.
+    fn truncate<'a>(&self, text: &'a str, replaced: Range<usize>) -> &'a str {
+        let Some(max_length) = self.max_length else {
+            return text;
+        };
+        let kept =
+            self.buffer.text().chars().count() - self.buffer.text()[replaced].chars().count();
+        let available = max_length.saturating_sub(kept);
+        text.char_indices()
+            .nth(available)
+            .map_or(text, |(ix, _)| &text[..ix])
+    }
+
+    /// The position of the cursor, relative to the top left of the text.
+    fn cursor_position(&self) -> Option<Point<Pixels>> {
+        if self.buffer.is_empty() {
+            return Some(point(px(0.), px(0.)));
+        }
+        let layout = self.last_layout.as_ref()?;
+        Some(layout.position_for_offset(self.buffer.cursor()))
+    }
+
+    /// The offset one row above or below the cursor, keeping the goal x position.
+    fn vertical_offset(&mut self, rows: f32) -> usize {
+        let (Some(layout), Some(cursor)) = (self.last_layout.as_ref(), self.cursor_position())
+        else {
+            return self.buffer.cursor();
+        };
+        let y = cursor.y + layout.line_height() * (rows + 0.5);
+        if y < px(0.) {
+            return 0;
+        }
+        if y > layout.height() {
+            return self.buffer.text().len();
+        }
+        let x = *self.goal_x.get_or_insert(cursor.x);
+        layout.offset_for_position(point(x, y))
+    }
+
+    /// Moves or selects by `rows`, keeping the goal x position.
+    fn move_vertically(&mut self, rows: f32, select: bool, cx: &mut Context<Self>) {
+        let offset = self.vertical_offset(rows);
+        let goal_x = self.goal_x;
+        if select {
+            self.select_to(offset, cx);
+        } else {
+            self.move_to(offset, cx);
+        }
+        self.goal_x = goal_x;
+    }
+
+    fn up(&mut self, _: &Up, _: &mut Window, cx: &mut Context<Self>) {
+        let range = self.buffer.selected_range();
+        if !range.is_empty() {
+            self.move_to(range.start, cx);
+        }
+        self.move_vertically(-1., false, cx);
+    }
+
+    fn down(&mut self, _: &Down, _: &mut Window, cx: &mut Context<Self>) {
+        let range = self.buffer.selected_range();
+        if !range.is_empty() {
+            self.move_to(range.end, cx);
+        }
+        self.move_vertically(1., false, cx);
+    }
+
+    fn select_up(&mut self, _: &SelectUp, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_vertically(-1., true, cx);
+    }
+
+    fn select_down(&mut self, _: &SelectDown, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_vertically(1., true, cx);
+    }
+
+    fn newline(&mut self, _: &Newline, window: &mut Window, cx: &mut Context<Self>) {
+        if self.editable() {
+            self.insert("\n", window, cx);
+        }
+    }
+
+    fn submit(&mut self, _: &Enter, window: &mut Window, cx: &mut Context<Self>) {
+        let text = self.text();
+        if let Some(on_submit) = self.on_submit.as_ref() {
+            on_submit(&text, window, cx);
+        }
+        cx.emit(InputEvent::Submit(text));
+    }
+
+    fn on_scroll_wheel(
+        &mut self,
+        event: &ScrollWheelEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let (Some(layout), Some(bounds)) = (self.last_layout.as_ref(), self.last_bounds.as_ref())
+        else {
+            return;
+        };
+        let max_scroll = (layout.height() - bounds.size.height).max(px(0.));
+        let delta = event.delta.pixel_delta(window.line_height());
+        let scroll_y = (self.scroll_y - delta.y).clamp(px(0.), max_scroll);
+        if scroll_y == self.scroll_y {
+            return;
+        }
+        // Only take the event while the area can scroll, so its parent scrolls at the ends.
+        cx.stop_propagation();
+        self.scroll_y = scroll_y;
+        self.scrolled_at = Some(Instant::now());
+        self._hide_scrollbar_task = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(SCROLLBAR_HIDE_DELAY).await;
+            this.update(cx, |_, cx| cx.notify()).ok();
+        }));
+        cx.notify();
+    }
+}
+
+impl TextEditing for TextArea {
+    fn buffer(&self) -> &TextBuffer {
+        &self.buffer
+    }
+
+    fn buffer_mut(&mut self) -> &mut TextBuffer {
+        &mut self.buffer
+    }
+
+    fn editable(&self) -> bool {
+        !self.disabled && !self.read_only
+    }
+
+    fn is_selecting(&self) -> bool {
+        self.is_selecting
+    }
+
+    fn set_selecting(&mut self, selecting: bool) {
+        self.is_selecting = selecting;
+    }
+
+    fn index_for_position(&self, position: Point<Pixels>) -> usize {
+        if self.buffer.is_empty() {
+            return 0;
+        }
+        let (Some(bounds), Some(layout)) = (self.last_bounds.as_ref(), self.last_layout.as_ref())
+        else {
+            return 0;
+        };
+        layout.offset_for_position(point(
+            position.x - bounds.left(),
+            position.y - bounds.top() + self.scroll_y,
+        ))
+    }
+
+    fn text_bounds(&self) -> Option<Bounds<Pixels>> {
+        self.last_bounds
+    }
+
+    /// The start and end of the visual row of the cursor.
+    fn line_range(&self) -> (usize, usize) {
+        match self.last_layout.as_ref() {
+            Some(layout) if !self.buffer.is_empty() => layout.row_range(self.buffer.cursor()),
+            _ => (0, self.buffer.text().len()),
+        }
+    }
+
+    fn edit(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        f: impl FnOnce(&mut TextBuffer),
+    ) {
+        let old_text = self.buffer.text().to_string();
+        f(&mut self.buffer);
+        self.goal_x = None;
+        self.autoscroll = true;
+
+        if old_text != self.buffer.text() {
+            let text = self.text();
+            if let Some(on_change) = self.on_change.as_ref() {
+                on_change(&text, window, cx);
+            }
+            cx.emit(InputEvent::Change(text));
+        }
+        cx.notify();
+    }
+
+    /// Inserts `text` in place of the selection, within the max length.
+    fn insert(&mut self, text: &str, window: &mut Window, cx: &mut Context<Self>) {
+        let text = text.replace("\r\n", "\n");
+        let text = self
+            .truncate(&text, self.buffer.selected_range())
+            .to_string();
+        self.edit(window, cx, |buffer| buffer.replace(None, &text));
+    }
+
+    fn move_to(&mut self, offset: usize, cx: &mut Context<Self>) {
+        self.buffer.move_to(offset);
+        self.goal_x = None;
+        self.autoscroll = true;
+        cx.notify();
+    }
+
+    fn select_to(&mut self, offset: usize, cx: &mut Context<Self>) {
+        self.buffer.select_to(offset);
+        self.goal_x = None;
+        self.autoscroll = true;
+        cx.notify();
+    }
+}
+
+impl EntityInputHandler for TextArea {
+    fn text_for_range(
+        &mut self,
+        range_utf16: Range<usize>,
+        actual_range: &mut Option<Range<usize>>,
+        _: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Option<String> {
+        Some(self.text_for_utf16_range(&range_utf16, actual_range))
+    }
+
+    fn selected_text_range(
+        &mut self,
+        _ignore_disabled_input: bool,
+        _: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Option<UTF16Selection> {
+        Some(self.utf16_selection())
+    }
+
+    fn marked_text_range(&self, _: &mut Window, _: &mut Context<Self>) -> Option<Range<usize>> {
+        self.marked_utf16_range()
+    }
+
+    fn unmark_text(&mut self, _: &mut Window, _: &mut Context<Self>) {
+        self.buffer.unmark();
+    }
+
+    fn replace_text_in_range(
+        &mut self,
+        range_utf16: Option<Range<usize>>,
+        new_text: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.editable() {
+            return;
+        }
+        let range = range_utf16.map(|range| self.buffer.range_from_utf16(&range));
+        let replaced = range
+            .clone()
+            .or_else(|| self.buffer.marked_range())
+            .unwrap_or_else(|| self.buffer.selected_range());
+        let new_text = self.truncate(new_text, replaced).to_string();
+        self.edit(window, cx, |buffer| buffer.replace(range, &new_text));
+    }
+
+    fn replace_and_mark_text_in_range(
+        &mut self,
+        range_utf16: Option<Range<usize>>,
+        new_text: &str,
+        new_selected_range_utf16: Option<Range<usize>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.editable() {
+            return;
+        }
+        let range = range_utf16.map(|range| self.buffer.range_from_utf16(&range));
+        let replaced = range
+            .clone()
+            .or_else(|| self.buffer.marked_range())
+            .unwrap_or_else(|| self.buffer.selected_range());
+        let new_selected_range =
+            new_selected_range_utf16.map(|range| range_from_utf16(new_text, &range));
+        // The composed text is truncated like typed text, with its selection kept inside it.
+        let new_text = self.truncate(new_text, replaced);
+        let new_selected_range = new_selected_range
+            .map(|range| range.start.min(new_text.len())..range.end.min(new_text.len()));
+        self.edit(window, cx, |buffer| {
+            buffer.replace_and_mark(range, new_text, new_selected_range)
+        });
+    }
+
+    fn bounds_for_range(
+        &mut self,
+        range_utf16: Range<usize>,
+        bounds: Bounds<Pixels>,
+        window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Option<Bounds<Pixels>> {
+        let layout = self.last_layout.as_ref()?;
+        let range = self.buffer.range_from_utf16(&range_utf16);
+        let start = layout.position_for_offset(range.start);
+        let end = layout.position_for_offset(range.end);
+        let origin = point(bounds.left(), bounds.top() - self.scroll_y);
+        Some(Bounds::from_corners(
+            origin + start,
+            origin + point(end.x, end.y + window.line_height()),
+        ))
+    }
+
+    fn character_index_for_point(
+        &mut self,
+        point: Point<Pixels>,
+        _: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Option<usize> {
+        self.utf16_index_for_point(point)
+    }
+}
+
+impl Render for TextArea {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let colors = cx.theme().colors.clone();
+        let radius = cx.theme().styles.radius;
+        let focused = self.focus_handle.is_focused(window);
+        let count = self.buffer.text().chars().count();
+
+        v_flex()
+            .w_full()
+            .gap_1()
+            .child(
+                div()
+                    .id("text-area")
+                    .key_context(CONTEXT)
+                    .when(!self.disabled, |this| {
+                        this.track_focus(&self.focus_handle)
+                            .cursor_text()
+                            .on_action(cx.listener(Self::up))
+                            .on_action(cx.listener(Self::down))
+                            .on_action(cx.listener(Self::select_up))
+                            .on_action(cx.listener(Self::select_down))
+                            .on_action(cx.listener(Self::left))
+                            .on_action(cx.listener(Self::right))
+                            .on_action(cx.listener(Self::select_left))
+                            .on_action(cx.listener(Self::select_right))
+                            .on_action(cx.listener(Self::word_left))
+                            .on_action(cx.listener(Self::word_right))
+                            .on_action(cx.listener(Self::select_word_left))
+                            .on_action(cx.listener(Self::select_word_right))
+                            .on_action(cx.listener(Self::select_all))
+                            .on_action(cx.listener(Self::home))
+                            .on_action(cx.listener(Self::end))
+                            .on_action(cx.listener(Self::select_to_home))
+                            .on_action(cx.listener(Self::select_to_end))
+                            .on_action(cx.listener(Self::backspace))
+                            .on_action(cx.listener(Self::delete))
+                            .on_action(cx.listener(Self::newline))
+                            .on_action(cx.listener(Self::copy))
+                            .on_action(cx.listener(Self::cut))
+                            .on_action(cx.listener(Self::paste))
+                            .on_action(cx.listener(Self::undo))
+                            .on_action(cx.listener(Self::redo))
+                            .on_action(cx.listener(Self::submit))
+                            .on_action(cx.listener(Self::show_character_palette))
+                            .on_mouse_down(MouseButton::Left, cx.listener(Self::on_mouse_down))
+                            .on_mouse_up(MouseButton::Left, cx.listener(Self::on_mouse_up))
+                            .on_mouse_up_out(MouseButton::Left, cx.listener(Self::on_mouse_up))
+                            .on_mouse_move(cx.listener(Self::on_mouse_move))
+                    })
+                    .on_scroll_wheel(cx.listener(Self::on_scroll_wheel))
+                    .on_hover(cx.listener(|this, hovered: &bool, _, cx| {
+                        this.hovered = *hovered;
+                        cx.notify();
+                    }))
+                    .w_full()
+                    .overflow_hidden()
+                    .rounded(radius)
+                    .border_1()
+                    .border_color(if focused && !self.read_only {
+                        colors.primary
+                    } else {
+                        colors.border
+                    })
+                    .bg(colors.bg)
+                    .text_color(colors.text)
+                    .map(|this| match self.size {
+                        Size::XSmall => this.px_2p5().py_1().text_xs(),
+                        Size::Small => this.px_3p5().py_1p5().text_sm(),
+                        Size::Medium | Size::Custom(_) => this.px_4().py_2(),
+                        Size::Large => this.px_5().py_2p5(),
+                    })
+                    .when(self.disabled, |this| this.cursor_not_allowed().opacity(0.5))
+                    .child(TextAreaElement::new(cx.entity())),
+            )
+            .when(self.show_count, |this| {
+                let over = self.max_length.is_some_and(|max_length| count > max_length);
+                this.child(
+                    h_flex().justify_end().child(
+                        Text::new(match self.max_length {
+                            Some(max_length) => format!("{count} / {max_length}"),
+                            None => count.to_string(),
+                        })
+                        .text_xs()
+                        .color(if over {
+                            colors.danger
+                        } else {
+                            colors.text_muted
+                        }),
+                    ),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{Entity, TestAppContext, VisualTestContext};
+
+    use super::*;
+    use crate::Theme;
+
+    fn build_area(
+        cx: &mut TestAppContext,
+        build: impl FnOnce(TextArea) -> TextArea + 'static,
+    ) -> (Entity<TextArea>, &mut VisualTestContext) {
+        cx.update(|cx| {
+            Theme::init(cx, None, None);
+            TextArea::bind_keys(cx);
+        });
+        cx.add_window_view(|_, cx| build(TextArea::new(cx)))
+    }
+
+    #[gpui::test]
+    fn test_set_text_truncates(cx: &mut TestAppContext) {
+        let (area, cx) = build_area(cx, |area| area.max_length(5));
+        area.update(cx, |area, cx| area.set_text("héllo world", cx));
+        cx.read(|cx| assert_eq!(area.read(cx).text(), SharedString::from("héllo")));
+
+        area.update(cx, |area, cx| area.set_text("bye", cx));
+        cx.read(|cx| assert_eq!(area.read(cx).text(), SharedString::from("bye")));
+    }
+}
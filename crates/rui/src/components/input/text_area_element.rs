@@ -0,0 +1,578 @@
+use gpui::{
+    fill, point, px, relative, size, App, AvailableSpace, Bounds, ContentMask, Element, ElementId,
+    ElementInputHandler, Entity, GlobalElementId, InspectorElementId, IntoElement, LayoutId,
+    PaintQuad, Pixels, Point, SharedString, Style, TextAlign, TextRun, UnderlineStyle, Window,
+    WrappedLine,
+};
+use smallvec::SmallVec;
+
+use crate::{ActiveTheme, ScrollbarShow, TextArea};
+
+/// The width of the cursor.
+const CURSOR_WIDTH: Pixels = px(1.5);
+/// The width of the scrollbar, kept free on the right of the text.
+const SCROLLBAR_WIDTH: Pixels = px(6.);
+/// The minimum height of the scrollbar thumb.
+const MIN_THUMB_HEIGHT: Pixels = px(16.);
+
+/// A visual row of a [`TextAreaLayout`], a hard line is split into rows when it wraps.
+struct Row {
+    line_ix: usize,
+    /// The offset of the row in the text.
+    start: usize,
+    /// The offset of the end of the row in the text, before the newline.
+    end: usize,
+}
+
+/// The text of a [`TextArea`], wrapped to its width.
+pub(crate) struct TextAreaLayout {
+    lines: SmallVec<[WrappedLine; 1]>,
+    /// The offset in the text where each line starts.
+    line_starts: Vec<usize>,
+    rows: Vec<Row>,
+    line_height: Pixels,
+}
+
+impl TextAreaLayout {
+    fn new(
+        text: &SharedString,
+        runs: &[TextRun],
+        font_size: Pixels,
+        wrap_width: Pixels,
+        line_height: Pixels,
+        window: &mut Window,
+    ) -> Self {
+        let lines = window
+            .text_system()
+            .shape_text(text.clone(), font_size, runs, Some(wrap_width), None)
+            .unwrap_or_default();
+
+        let mut line_starts = Vec::with_capacity(lines.len());
+        let mut rows = Vec::new();
+        let mut start = 0;
+        for (line_ix, (line, line_text)) in lines.iter().zip(text.split('\n')).enumerate() {
+            line_starts.push(start);
+            let mut row_start = start;
+            for boundary in line.wrap_boundaries.iter() {
+                let run = &line.unwrapped_layout.runs[boundary.run_ix];
+                let row_end = start + run.glyphs[boundary.glyph_ix].index;
+                rows.push(Row {
+                    line_ix,
+                    start: row_start,
+                    end: row_end,
+                });
+                row_start = row_end;
+            }
+            rows.push(Row {
+                line_ix,
+                start: row_start,
+                end: start + line_text.len(),
+            });
+            start += line_text.len() + 1;
+        }
+
+        Self {
+            lines,
+            line_starts,
+            rows,
+            line_height,
+        }
+    }
+
+    /// The number of visual rows, at least one.
+    pub fn row_count(&self) -> usize {
+        self.rows.len().max(1)
+    }
+
+    pub fn line_height(&self) -> Pixels {
+        self.line_height
+    }
+
+    /// The height of the whole text.
+    pub fn height(&self) -> Pixels {
+        self.line_height * self.row_count() as f32
+    }
+
+    /// Whether the row at `row_ix` wraps, its line continues on the next row.
+    fn wraps(&self, row_ix: usize) -> bool {
+        let line_ix = self.rows[row_ix].line_ix;
+        self.rows
+            .get(row_ix + 1)
+            .is_some_and(|next| next.line_ix == line_ix)
+    }
+
+    /// The row containing `offset`, an offset at a wrap is on the next row.
+    fn row_for_offset(&self, offset: usize) -> usize {
+        self.rows
+            .iter()
+            .enumerate()
+            .position(|(row_ix, row)| {
+                row.start <= offset
+                    && (offset < row.end || offset == row.end && !self.wraps(row_ix))
+            })
+            .unwrap_or(self.rows.len().saturating_sub(1))
+    }
+
+    /// The x position of `offset` in its row.
+    fn x_for_offset(&self, row: &Row, offset: usize) -> Pixels {
+        let line = &self.lines[row.line_ix].unwrapped_layout;
+        let line_start = self.line_starts[row.line_ix];
+        line.x_for_index(offset - line_start) - line.x_for_index(row.start - line_start)
+    }
+
+    /// The position of `offset`, relative to the top left of the text.
+    pub fn position_for_offset(&self, offset: usize) -> Point<Pixels> {
+        if self.rows.is_empty() {
+            return point(px(0.), px(0.));
+        }
+        let row_ix = self.row_for_offset(offset);
+        let row = &self.rows[row_ix];
+        point(
+            self.x_for_offset(row, offset.clamp(row.start, row.end)),
+            self.line_height * row_ix as f32,
+        )
+    }
+
+    /// The offset closest to `position`, relative to the top left of the text.
+    pub fn offset_for_position(&self, position: Point<Pixels>) -> usize {
+        if self.rows.is_empty() {
+            return 0;
+        }
+        let row_ix = if position.y < px(0.) {
+            0
+        } else {
+            ((position.y / self.line_height) as usize).min(self.rows.len() - 1)
+        };
+        let row = &self.rows[row_ix];
+        let line = &self.lines[row.line_ix].unwrapped_layout;
+        let line_start = self.line_starts[row.line_ix];
+        let x = position.x + line.x_for_index(row.start - line_start);
+        (line_start + line.closest_index_for_x(x)).clamp(row.start, row.end)
+    }
+
+    /// The start and end offsets of the row containing `offset`.
+    pub fn row_range(&self, offset: usize) -> (usize, usize) {
+        self.rows
+            .get(self.row_for_offset(offset))
+            .map_or((0, 0), |row| (row.start, row.end))
+    }
+
+    /// Returns the selection rectangles of `range`, relative to the top left of the text.
+    fn selection_bounds(&self, start: usize, end: usize) -> Vec<Bounds<Pixels>> {
+        let mut bounds = Vec::new();
+        for (row_ix, row) in self.rows.iter().enumerate() {
+            if row.end < start || row.start > end {
+                continue;
+            }
+            let left = self.x_for_offset(row, start.max(row.start));
+            let mut right = self.x_for_offset(row, end.min(row.end));
+            // Show the selected newline.
+            if end > row.end && !self.wraps(row_ix) {
+                right += self.line_height / 4.;
+            }
+            if right > left {
+                let top = self.line_height * row_ix as f32;
+                bounds.push(Bounds::from_corners(
+                    point(left, top),
+                    point(right, top + self.line_height),
+                ));
+            }
+        }
+        bounds
+    }
+}
+
+/// Paints the text of a [`TextArea`] wrapped to its width, with its selection, cursor and
+/// scrollbar, and registers the area as the platform input handler.
+///
+/// The element grows with the text between the min and max rows of the area.
+pub(crate) struct TextAreaElement {
+    area: Entity<TextArea>,
+}
+
+impl TextAreaElement {
+    pub fn new(area: Entity<TextArea>) -> Self {
+        Self { area }
+    }
+}
+
+pub(crate) struct RequestLayoutState {
+    text: SharedString,
+    runs: Vec<TextRun>,
+    font_size: Pixels,
+}
+
+pub(crate) struct PrepaintState {
+    layout: Option<TextAreaLayout>,
+    scroll_y: Pixels,
+    cursor: Option<PaintQuad>,
+    selections: Vec<PaintQuad>,
+    scrollbar: Option<PaintQuad>,
+}
+
+impl IntoElement for TextAreaElement {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl Element for TextAreaElement {
+    type RequestLayoutState = RequestLayoutState;
+    type PrepaintState = PrepaintState;
+
+    fn id(&self) -> Option<ElementId> {
+        None
+    }
+
+    fn source_location(&self) -> Option<&'static core::panic::Location<'static>> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let area = self.area.read(cx);
+        let style = window.text_style();
+        let font_size = style.font_size.to_pixels(window.rem_size());
+        let line_height = window.line_height();
+
+        let (text, text_color) = if area.buffer.is_empty() {
+            (area.placeholder.clone(), cx.theme().colors.text_muted)
+        } else {
+            (
+                SharedString::from(area.buffer.text().to_string()),
+                style.color,
+            )
+        };
+        let run = TextRun {
+            len: text.len(),
+            font: style.font(),
+            color: text_color,
+            background_color: None,
+            underline: None,
+            strikethrough: None,
+        };
+        let runs: Vec<TextRun> = match area.buffer.marked_range() {
+            Some(marked_range) if !area.buffer.is_empty() => vec![
+                TextRun {
+                    len: marked_range.start,
+                    ..run.clone()
+                },
+                TextRun {
+                    len: marked_range.end - marked_range.start,
+                    underline: Some(UnderlineStyle {
+                        color: Some(run.color),
+                        thickness: px(1.),
+                        wavy: false,
+                    }),
+                    ..run.clone()
+                },
+                TextRun {
+                    len: text.len() - marked_range.end,
+                    ..run
+                },
+            ]
+            .into_iter()
+            .filter(|run| run.len > 0)
+            .collect(),
+            _ => vec![run],
+        };
+
+        let min_rows = area.min_rows.max(1);
+        let max_rows = area.max_rows.unwrap_or(usize::MAX).max(min_rows);
+        let mut style = Style::default();
+        style.size.width = relative(1.).into();
+        let layout_id = window.request_measured_layout(style, {
+            let text = text.clone();
+            let runs = runs.clone();
+            move |known_dimensions, available_space, window, _cx| {
+                let width = known_dimensions
+                    .width
+                    .unwrap_or(match available_space.width {
+                        AvailableSpace::Definite(width) => width,
+                        AvailableSpace::MinContent | AvailableSpace::MaxContent => px(0.),
+                    });
+                let layout = TextAreaLayout::new(
+                    &text,
+                    &runs,
+                    font_size,
+                    width - SCROLLBAR_WIDTH,
+                    line_height,
+                    window,
+                );
+                let rows = layout.row_count().clamp(min_rows, max_rows);
+                size(width, line_height * rows as f32)
+            }
+        });
+
+        (
+            layout_id,
+            RequestLayoutState {
+                text,
+                runs,
+                font_size,
+            },
+        )
+    }
+
+    fn prepaint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        bounds: Bounds<Pixels>,
+        request_layout: &mut Self::RequestLayoutState,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self::PrepaintState {
+        let line_height = window.line_height();
+        let layout = TextAreaLayout::new(
+            &request_layout.text,
+            &request_layout.runs,
+            request_layout.font_size,
+            bounds.size.width - SCROLLBAR_WIDTH,
+            line_height,
+            window,
+        );
+
+        let area = self.area.read(cx);
+        let colors = &cx.theme().colors;
+        let selected_range = area.buffer.selected_range();
+        let cursor = if area.buffer.is_empty() {
+            point(px(0.), px(0.))
+        } else {
+            layout.position_for_offset(area.buffer.cursor())
+        };
+
+        // Scroll vertically to keep the cursor visible after it moved.
+        let content_height = layout.height();
+        let mut scroll_y = area.scroll_y;
+        if area.autoscroll {
+            if cursor.y < scroll_y {
+                scroll_y = cursor.y;
+            } else if cursor.y + line_height > scroll_y + bounds.size.height {
+                scroll_y = cursor.y + line_height - bounds.size.height;
+            }
+        }
+        scroll_y = scroll_y.clamp(px(0.), (content_height - bounds.size.height).max(px(0.)));
+        let origin = point(bounds.left(), bounds.top() - scroll_y);
+
+        let (selections, cursor) = if selected_range.is_empty() {
+            let cursor = fill(
+                Bounds::new(origin + cursor, size(CURSOR_WIDTH, line_height)),
+                colors.primary,
+            );
+            (Vec::new(), Some(cursor))
+        } else {
+            let selections = layout
+                .selection_bounds(selected_range.start, selected_range.end)
+                .into_iter()
+                .map(|selection| {
+                    fill(
+                        Bounds::new(origin + selection.origin, selection.size),
+                        colors.primary.opacity(0.3),
+                    )
+                })
+                .collect();
+            (selections, None)
+        };
+
+        let show_scrollbar = match cx.theme().styles.scrollbar_show {
+            ScrollbarShow::Always => true,
+            ScrollbarShow::Hover => area.hovered || area.is_scrolling(),
+            ScrollbarShow::Scrolling => area.is_scrolling(),
+        };
+        let scrollbar = (show_scrollbar && content_height > bounds.size.height).then(|| {
+            let viewport = bounds.size.height;
+            let thumb_height = (viewport * (viewport / content_height)).max(MIN_THUMB_HEIGHT);
+            let thumb_top = (viewport - thumb_height) * (scroll_y / (content_height - viewport));
+            fill(
+                Bounds::new(
+                    point(bounds.right() - SCROLLBAR_WIDTH, bounds.top() + thumb_top),
+                    size(SCROLLBAR_WIDTH, thumb_height),
+                ),
+                colors.text_muted.opacity(0.5),
+            )
+            .corner_radii(SCROLLBAR_WIDTH / 2.)
+        });
+
+        PrepaintState {
+            layout: Some(layout),
+            scroll_y,
+            cursor,
+            selections,
+            scrollbar,
+        }
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        prepaint: &mut Self::PrepaintState,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let area = self.area.read(cx);
+        let focus_handle = area.focus_handle.clone();
+        let disabled = area.disabled;
+        if !disabled {
+            window.handle_input(
+                &focus_handle,
+                ElementInputHandler::new(bounds, self.area.clone()),
+                cx,
+            );
+        }
+
+        let Some(layout) = prepaint.layout.take() else {
+            return;
+        };
+        let scroll_y = prepaint.scroll_y;
+        window.with_content_mask(Some(ContentMask { bounds }), |window| {
+            for selection in prepaint.selections.drain(..) {
+                window.paint_quad(selection);
+            }
+            let mut top = bounds.top() - scroll_y;
+            for line in layout.lines.iter() {
+                line.paint(
+                    point(bounds.left(), top),
+                    layout.line_height,
+                    TextAlign::Left,
+                    None,
+                    window,
+                    cx,
+                )
+                .ok();
+                top += line.size(layout.line_height).height;
+            }
+            if !disabled && focus_handle.is_focused(window) {
+                if let Some(cursor) = prepaint.cursor.take() {
+                    window.paint_quad(cursor);
+                }
+            }
+        });
+        if let Some(scrollbar) = prepaint.scrollbar.take() {
+            window.paint_quad(scrollbar);
+        }
+
+        self.area.update(cx, |area, _| {
+            area.last_layout = Some(layout);
+            area.last_bounds = Some(bounds);
+            area.scroll_y = scroll_y;
+            area.autoscroll = false;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{black, TestAppContext};
+
+    use super::*;
+
+    const LINE_HEIGHT: Pixels = px(20.);
+
+    fn shape(text: &str, wrap_width: Pixels, window: &mut Window) -> TextAreaLayout {
+        let text = SharedString::from(text.to_string());
+        let run = TextRun {
+            len: text.len(),
+            font: window.text_style().font(),
+            color: black(),
+            background_color: None,
+            underline: None,
+            strikethrough: None,
+        };
+        TextAreaLayout::new(&text, &[run], px(16.), wrap_width, LINE_HEIGHT, window)
+    }
+
+    #[gpui::test]
+    fn test_hard_lines(cx: &mut TestAppContext) {
+        let cx = cx.add_empty_window();
+        cx.update(|window, _| {
+            let layout = shape("one\ntwo\n", px(1000.), window);
+            assert_eq!(layout.row_count(), 3);
+            assert_eq!(layout.height(), LINE_HEIGHT * 3.);
+            assert_eq!(layout.row_range(5), (4, 7));
+            assert_eq!(layout.row_range(8), (8, 8));
+            assert_eq!(layout.position_for_offset(4), point(px(0.), LINE_HEIGHT));
+            assert_eq!(layout.offset_for_position(point(px(1000.), px(0.))), 3);
+
+            let layout = shape("", px(1000.), window);
+            assert_eq!(layout.row_count(), 1);
+            assert_eq!(layout.position_for_offset(0), point(px(0.), px(0.)));
+        });
+    }
+
+    #[gpui::test]
+    fn test_soft_wrap(cx: &mut TestAppContext) {
+        let cx = cx.add_empty_window();
+        cx.update(|window, _| {
+            let text = "alpha beta gamma delta";
+            let layout = shape(text, px(100.), window);
+            let rows = &layout.rows;
+            assert!(rows.len() > 1);
+            assert_eq!(layout.row_count(), rows.len());
+            assert_eq!(layout.height(), LINE_HEIGHT * rows.len() as f32);
+
+            // The rows of the line follow each other without gaps.
+            assert_eq!(rows[0].start, 0);
+            assert_eq!(rows.last().unwrap().end, text.len());
+            for (row_ix, row) in rows.iter().enumerate() {
+                assert_eq!(row.line_ix, 0);
+                assert!(row.start < row.end);
+                assert_eq!(layout.wraps(row_ix), row_ix + 1 < rows.len());
+                if let Some(next) = rows.get(row_ix + 1) {
+                    assert_eq!(next.start, row.end);
+                }
+            }
+
+            // An offset at a wrap is at the start of the next row.
+            for (row_ix, row) in rows.iter().enumerate() {
+                let top = LINE_HEIGHT * row_ix as f32;
+                assert_eq!(layout.position_for_offset(row.start), point(px(0.), top));
+                assert_eq!(layout.row_range(row.start), (row.start, row.end));
+                assert_eq!(
+                    layout.offset_for_position(point(px(1000.), top)),
+                    if row_ix + 1 < rows.len() {
+                        row.end
+                    } else {
+                        text.len()
+                    }
+                );
+            }
+
+            for offset in 0..=text.len() {
+                let position = layout.position_for_offset(offset);
+                assert_eq!(layout.offset_for_position(position), offset);
+            }
+        });
+    }
+
+    #[gpui::test]
+    fn test_selection_across_wraps(cx: &mut TestAppContext) {
+        let cx = cx.add_empty_window();
+        cx.update(|window, _| {
+            let text = "alpha beta gamma\ndelta";
+            let layout = shape(text, px(100.), window);
+            let bounds = layout.selection_bounds(0, text.len());
+            assert_eq!(bounds.len(), layout.rows.len());
+            for (row_ix, bounds) in bounds.iter().enumerate() {
+                assert_eq!(bounds.top(), LINE_HEIGHT * row_ix as f32);
+                assert_eq!(bounds.size.height, LINE_HEIGHT);
+            }
+
+            // Only the rows touched by the selection are highlighted.
+            let second = &layout.rows[1];
+            let bounds = layout.selection_bounds(second.start + 1, second.end - 1);
+            assert_eq!(bounds.len(), 1);
+            assert_eq!(bounds[0].top(), LINE_HEIGHT);
+        });
+    }
+}
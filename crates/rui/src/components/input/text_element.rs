@@ -0,0 +1,224 @@
+use gpui::{
+    fill, point, px, relative, size, App, Bounds, ContentMask, Element, ElementId,
+    ElementInputHandler, Entity, GlobalElementId, InspectorElementId, IntoElement, LayoutId,
+    PaintQuad, Pixels, ShapedLine, Style, TextAlign, TextRun, UnderlineStyle, Window,
+};
+
+use crate::{ActiveTheme, TextInput};
+
+/// The width of the cursor.
+const CURSOR_WIDTH: Pixels = px(1.5);
+
+/// Paints the text of a [`TextInput`] on a single line, with its selection and cursor, and
+/// registers the input as the platform input handler.
+pub(crate) struct TextElement {
+    input: Entity<TextInput>,
+}
+
+impl TextElement {
+    pub fn new(input: Entity<TextInput>) -> Self {
+        Self { input }
+    }
+}
+
+pub(crate) struct PrepaintState {
+    line: Option<ShapedLine>,
+    scroll_x: Pixels,
+    cursor: Option<PaintQuad>,
+    selection: Option<PaintQuad>,
+}
+
+impl IntoElement for TextElement {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl Element for TextElement {
+    type RequestLayoutState = ();
+    type PrepaintState = PrepaintState;
+
+    fn id(&self) -> Option<ElementId> {
+        None
+    }
+
+    fn source_location(&self) -> Option<&'static core::panic::Location<'static>> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let mut style = Style::default();
+        style.size.width = relative(1.).into();
+        style.size.height = window.line_height().into();
+        (window.request_layout(style, [], cx), ())
+    }
+
+    fn prepaint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self::PrepaintState {
+        let input = self.input.read(cx);
+        let colors = &cx.theme().colors;
+        let style = window.text_style();
+
+        let (display_text, text_color) = if input.buffer.is_empty() {
+            (input.placeholder.clone(), colors.text_muted)
+        } else {
+            (input.display_text(), style.color)
+        };
+
+        let run = TextRun {
+            len: display_text.len(),
+            font: style.font(),
+            color: text_color,
+            background_color: None,
+            underline: None,
+            strikethrough: None,
+        };
+        let runs = match input.buffer.marked_range() {
+            Some(marked_range) if !input.buffer.is_empty() => {
+                let marked_range = input.to_display_offset(marked_range.start)
+                    ..input.to_display_offset(marked_range.end);
+                vec![
+                    TextRun {
+                        len: marked_range.start,
+                        ..run.clone()
+                    },
+                    TextRun {
+                        len: marked_range.end - marked_range.start,
+                        underline: Some(UnderlineStyle {
+                            color: Some(run.color),
+                            thickness: px(1.),
+                            wavy: false,
+                        }),
+                        ..run.clone()
+                    },
+                    TextRun {
+                        len: display_text.len() - marked_range.end,
+                        ..run
+                    },
+                ]
+                .into_iter()
+                .filter(|run| run.len > 0)
+                .collect()
+            }
+            _ => vec![run],
+        };
+
+        let font_size = style.font_size.to_pixels(window.rem_size());
+        let line = window
+            .text_system()
+            .shape_line(display_text, font_size, &runs, None);
+
+        let selected_range = input.buffer.selected_range();
+        let cursor_x = if input.buffer.is_empty() {
+            px(0.)
+        } else {
+            line.x_for_index(input.to_display_offset(input.buffer.cursor()))
+        };
+
+        // Scroll horizontally to keep the cursor visible.
+        let mut scroll_x = input.scroll_x;
+        let width = bounds.size.width - CURSOR_WIDTH;
+        if cursor_x - scroll_x > width {
+            scroll_x = cursor_x - width;
+        } else if cursor_x < scroll_x {
+            scroll_x = cursor_x;
+        }
+        scroll_x = scroll_x.min((line.width - width).max(px(0.)));
+
+        let (selection, cursor) = if selected_range.is_empty() {
+            let cursor = fill(
+                Bounds::new(
+                    point(bounds.left() + cursor_x - scroll_x, bounds.top()),
+                    size(CURSOR_WIDTH, bounds.size.height),
+                ),
+                colors.primary,
+            );
+            (None, Some(cursor))
+        } else {
+            let start = line.x_for_index(input.to_display_offset(selected_range.start));
+            let end = line.x_for_index(input.to_display_offset(selected_range.end));
+            let selection = fill(
+                Bounds::from_corners(
+                    point(bounds.left() + start - scroll_x, bounds.top()),
+                    point(bounds.left() + end - scroll_x, bounds.bottom()),
+                ),
+                colors.primary.opacity(0.3),
+            );
+            (Some(selection), None)
+        };
+
+        PrepaintState {
+            line: Some(line),
+            scroll_x,
+            cursor,
+            selection,
+        }
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        prepaint: &mut Self::PrepaintState,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let input = self.input.read(cx);
+        let focus_handle = input.focus_handle.clone();
+        let disabled = input.disabled;
+        if !disabled {
+            window.handle_input(
+                &focus_handle,
+                ElementInputHandler::new(bounds, self.input.clone()),
+                cx,
+            );
+        }
+
+        let Some(line) = prepaint.line.take() else {
+            return;
+        };
+        window.with_content_mask(Some(ContentMask { bounds }), |window| {
+            if let Some(selection) = prepaint.selection.take() {
+                window.paint_quad(selection);
+            }
+            line.paint(
+                point(bounds.left() - prepaint.scroll_x, bounds.top()),
+                window.line_height(),
+                TextAlign::Left,
+                None,
+                window,
+                cx,
+            )
+            .ok();
+        });
+        if !disabled && focus_handle.is_focused(window) {
+            if let Some(cursor) = prepaint.cursor.take() {
+                window.paint_quad(cursor);
+            }
+        }
+
+        let scroll_x = prepaint.scroll_x;
+        self.input.update(cx, |input, _| {
+            input.last_layout = Some(line);
+            input.last_bounds = Some(bounds);
+            input.scroll_x = scroll_x;
+        });
+    }
+}
@@ -0,0 +1,574 @@
+use std::ops::Range;
+
+use gpui::{
+    actions, point, App, Bounds, Context, EntityInputHandler, EventEmitter, FocusHandle, Focusable,
+    KeyBinding, MouseButton, Pixels, Point, Render, ShapedLine, UTF16Selection, Window,
+};
+
+use super::{buffer::TextBuffer, editing::TextEditing, text_element::TextElement};
+use crate::{prelude::*, Icon, MASKED};
+
+actions!(
+    text_input,
+    [
+        Backspace,
+        Delete,
+        Left,
+        Right,
+        SelectLeft,
+        SelectRight,
+        WordLeft,
+        WordRight,
+        SelectWordLeft,
+        SelectWordRight,
+        SelectAll,
+        Home,
+        End,
+        SelectToHome,
+        SelectToEnd,
+        Copy,
+        Cut,
+        Paste,
+        Undo,
+        Redo,
+        Enter,
+        ShowCharacterPalette
+    ]
+);
+
+const CONTEXT: &str = "TextInput";
+
+/// Events emitted by a [`TextInput`].
+#[derive(Debug, Clone)]
+pub enum InputEvent {
+    /// The text was edited.
+    Change(SharedString),
+    /// Enter was pressed.
+    Submit(SharedString),
+}
+
+/// A single-line text input, with selection, clipboard, undo and IME support.
+///
+/// Call [`TextInput::bind_keys`] once at startup to bind the editing keys.
+pub struct TextInput {
+    pub(super) focus_handle: FocusHandle,
+    pub(super) buffer: TextBuffer,
+    pub(super) placeholder: SharedString,
+    pub(super) masked: bool,
+    size: Size,
+    pub(super) disabled: bool,
+    read_only: bool,
+    invalid: bool,
+    prefix: Option<Icon>,
+    suffix: Option<Icon>,
+    on_change: Option<Box<dyn Fn(&SharedString, &mut Window, &mut App) + 'static>>,
+    on_submit: Option<Box<dyn Fn(&SharedString, &mut Window, &mut App) + 'static>>,
+    pub(super) scroll_x: Pixels,
+    pub(super) last_layout: Option<ShapedLine>,
+    pub(super) last_bounds: Option<Bounds<Pixels>>,
+    is_selecting: bool,
+}
+
+impl EventEmitter<InputEvent> for TextInput {}
+
+impl Focusable for TextInput {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl TextInput {
+    pub fn new(cx: &mut App) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            buffer: TextBuffer::default(),
+            placeholder: SharedString::default(),
+            masked: false,
+            size: Size::default(),
+            disabled: false,
+            read_only: false,
+            invalid: false,
+            prefix: None,
+            suffix: None,
+            on_change: None,
+            on_submit: None,
+            scroll_x: px(0.),
+            last_layout: None,
+            last_bounds: None,
+            is_selecting: false,
+        }
+    }
+
+    pub fn bind_keys(cx: &mut App) {
+        cx.bind_keys(editing_bindings(CONTEXT));
+        cx.bind_keys([KeyBinding::new("enter", Enter, Some(CONTEXT))]);
+    }
+
+    pub fn placeholder(mut self, placeholder: impl Into<SharedString>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Sets the initial text, with the cursor at the end.
+    pub fn default_text(mut self, text: impl Into<SharedString>) -> Self {
+        self.buffer = TextBuffer::new(text.into().to_string());
+        self
+    }
+
+    /// Shows every char as `•`, for passwords. A masked input can't be copied or cut.
+    pub fn masked(mut self, masked: bool) -> Self {
+        self.masked = masked;
+        self
+    }
+
+    pub fn size(mut self, size: Size) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// A disabled input can't be focused or edited.
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// A read-only input can be focused, selected and copied, but not edited.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Shows the input with the danger color, for validation errors.
+    pub fn invalid(mut self, invalid: bool) -> Self {
+        self.invalid = invalid;
+        self
+    }
+
+    /// Sets the icon shown before the text.
+    pub fn prefix(mut self, icon: impl Into<Icon>) -> Self {
+        self.prefix = Some(icon.into());
+        self
+    }
+
+    /// Sets the icon shown after the text.
+    pub fn suffix(mut self, icon: impl Into<Icon>) -> Self {
+        self.suffix = Some(icon.into());
+        self
+    }
+
+    /// Called with the new text after every edit.
+    pub fn on_change(
+        mut self,
+        handler: impl Fn(&SharedString, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_change = Some(Box::new(handler));
+        self
+    }
+
+    /// Called with the text when enter is pressed.
+    pub fn on_submit(
+        mut self,
+        handler: impl Fn(&SharedString, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_submit = Some(Box::new(handler));
+        self
+    }
+
+    pub fn text(&self) -> SharedString {
+        SharedString::from(self.buffer.text().to_string())
+    }
+
+    /// Replaces the text, without calling `on_change`. Can be undone.
+    pub fn set_text(&mut self, text: impl Into<SharedString>, cx: &mut Context<Self>) {
+        self.buffer.set_text(text.into().to_string());
+        cx.notify();
+    }
+
+    pub fn set_placeholder(
+        &mut self,
+        placeholder: impl Into<SharedString>,
+        cx: &mut Context<Self>,
+    ) {
+        self.placeholder = placeholder.into();
+        cx.notify();
+    }
+
+    pub fn set_size(&mut self, size: Size, cx: &mut Context<Self>) {
+        self.size = size;
+        cx.notify();
+    }
+
+    pub fn set_disabled(&mut self, disabled: bool, cx: &mut Context<Self>) {
+        self.disabled = disabled;
+        cx.notify();
+    }
+
+    pub fn set_read_only(&mut self, read_only: bool, cx: &mut Context<Self>) {
+        self.read_only = read_only;
+        cx.notify();
+    }
+
+    pub fn set_invalid(&mut self, invalid: bool, cx: &mut Context<Self>) {
+        self.invalid = invalid;
+        cx.notify();
+    }
+
+    /// The text as painted, with every char replaced by the mask glyph when masked.
+    pub(super) fn display_text(&self) -> SharedString {
+        if self.masked {
+            MASKED.repeat(self.buffer.text().chars().count()).into()
+        } else {
+            self.text()
+        }
+    }
+
+    /// Maps an offset in the text to an offset in the painted text.
+    pub(super) fn to_display_offset(&self, offset: usize) -> usize {
+        if self.masked {
+            self.buffer.text()[..offset].chars().count() * MASKED.len()
+        } else {
+            offset
+        }
+    }
+
+    /// Maps an offset in the painted text to an offset in the text.
+    fn from_display_offset(&self, offset: usize) -> usize {
+        if self.masked {
+            self.buffer
+                .text()
+                .char_indices()
+                .nth(offset / MASKED.len())
+                .map_or(self.buffer.text().len(), |(ix, _)| ix)
+        } else {
+            offset
+        }
+    }
+
+    fn enter(&mut self, _: &Enter, window: &mut Window, cx: &mut Context<Self>) {
+        let text = self.text();
+        if let Some(on_submit) = self.on_submit.as_ref() {
+            on_submit(&text, window, cx);
+        }
+        cx.emit(InputEvent::Submit(text));
+    }
+}
+
+impl TextEditing for TextInput {
+    fn buffer(&self) -> &TextBuffer {
+        &self.buffer
+    }
+
+    fn buffer_mut(&mut self) -> &mut TextBuffer {
+        &mut self.buffer
+    }
+
+    fn editable(&self) -> bool {
+        !self.disabled && !self.read_only
+    }
+
+    fn is_masked(&self) -> bool {
+        self.masked
+    }
+
+    fn is_selecting(&self) -> bool {
+        self.is_selecting
+    }
+
+    fn set_selecting(&mut self, selecting: bool) {
+        self.is_selecting = selecting;
+    }
+
+    fn index_for_position(&self, position: Point<Pixels>) -> usize {
+        if self.buffer.is_empty() {
+            return 0;
+        }
+        let (Some(bounds), Some(line)) = (self.last_bounds.as_ref(), self.last_layout.as_ref())
+        else {
+            return 0;
+        };
+        if position.x < bounds.left() {
+            return 0;
+        }
+        if position.x > bounds.right() && self.scroll_x + bounds.size.width >= line.width {
+            return self.buffer.text().len();
+        }
+        self.from_display_offset(
+            line.closest_index_for_x(position.x - bounds.left() + self.scroll_x),
+        )
+    }
+
+    fn text_bounds(&self) -> Option<Bounds<Pixels>> {
+        self.last_bounds
+    }
+
+    fn edit(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        f: impl FnOnce(&mut TextBuffer),
+    ) {
+        let old_text = self.buffer.text().to_string();
+        f(&mut self.buffer);
+
+        let changed = old_text != self.buffer.text();
+        if changed {
+            let text = self.text();
+            if let Some(on_change) = self.on_change.as_ref() {
+                on_change(&text, window, cx);
+            }
+            cx.emit(InputEvent::Change(text));
+        }
+        cx.notify();
+    }
+
+    /// The text stays on one line, newlines are replaced by spaces.
+    fn insert(&mut self, text: &str, window: &mut Window, cx: &mut Context<Self>) {
+        let text = text.replace(['\r', '\n'], " ");
+        self.edit(window, cx, |buffer| buffer.replace(None, &text));
+    }
+
+    fn move_to(&mut self, offset: usize, cx: &mut Context<Self>) {
+        self.buffer.move_to(offset);
+        cx.notify();
+    }
+
+    fn select_to(&mut self, offset: usize, cx: &mut Context<Self>) {
+        self.buffer.select_to(offset);
+        cx.notify();
+    }
+}
+
+/// The key bindings shared by the text inputs, in the given key context.
+pub(super) fn editing_bindings(context: &'static str) -> Vec<KeyBinding> {
+    let context = Some(context);
+    let mut bindings = vec![
+        KeyBinding::new("backspace", Backspace, context),
+        KeyBinding::new("delete", Delete, context),
+        KeyBinding::new("left", Left, context),
+        KeyBinding::new("right", Right, context),
+        KeyBinding::new("shift-left", SelectLeft, context),
+        KeyBinding::new("shift-right", SelectRight, context),
+        KeyBinding::new("home", Home, context),
+        KeyBinding::new("end", End, context),
+        KeyBinding::new("shift-home", SelectToHome, context),
+        KeyBinding::new("shift-end", SelectToEnd, context),
+        KeyBinding::new("secondary-a", SelectAll, context),
+        KeyBinding::new("secondary-c", Copy, context),
+        KeyBinding::new("secondary-x", Cut, context),
+        KeyBinding::new("secondary-v", Paste, context),
+        KeyBinding::new("secondary-z", Undo, context),
+    ];
+
+    match PlatformStyle::platform() {
+        PlatformStyle::Mac => bindings.extend([
+            KeyBinding::new("alt-left", WordLeft, context),
+            KeyBinding::new("alt-right", WordRight, context),
+            KeyBinding::new("alt-shift-left", SelectWordLeft, context),
+            KeyBinding::new("alt-shift-right", SelectWordRight, context),
+            KeyBinding::new("cmd-left", Home, context),
+            KeyBinding::new("cmd-right", End, context),
+            KeyBinding::new("cmd-shift-left", SelectToHome, context),
+            KeyBinding::new("cmd-shift-right", SelectToEnd, context),
+            KeyBinding::new("cmd-shift-z", Redo, context),
+            KeyBinding::new("ctrl-cmd-space", ShowCharacterPalette, context),
+        ]),
+        PlatformStyle::Linux | PlatformStyle::Windows => bindings.extend([
+            KeyBinding::new("ctrl-left", WordLeft, context),
+            KeyBinding::new("ctrl-right", WordRight, context),
+            KeyBinding::new("ctrl-shift-left", SelectWordLeft, context),
+            KeyBinding::new("ctrl-shift-right", SelectWordRight, context),
+            KeyBinding::new("ctrl-y", Redo, context),
+            KeyBinding::new("ctrl-shift-z", Redo, context),
+        ]),
+    }
+    bindings
+}
+
+/// Converts a UTF-16 range relative to `text` to a byte range.
+pub(super) fn range_from_utf16(text: &str, range: &Range<usize>) -> Range<usize> {
+    let offset = |utf16_offset: usize| {
+        let mut utf16_count = 0;
+        text.char_indices()
+            .find(|(_, c)| {
+                let found = utf16_count >= utf16_offset;
+                utf16_count += c.len_utf16();
+                found
+            })
+            .map_or(text.len(), |(ix, _)| ix)
+    };
+    offset(range.start)..offset(range.end)
+}
+
+impl EntityInputHandler for TextInput {
+    fn text_for_range(
+        &mut self,
+        range_utf16: Range<usize>,
+        actual_range: &mut Option<Range<usize>>,
+        _: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Option<String> {
+        Some(self.text_for_utf16_range(&range_utf16, actual_range))
+    }
+
+    fn selected_text_range(
+        &mut self,
+        _ignore_disabled_input: bool,
+        _: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Option<UTF16Selection> {
+        Some(self.utf16_selection())
+    }
+
+    fn marked_text_range(&self, _: &mut Window, _: &mut Context<Self>) -> Option<Range<usize>> {
+        self.marked_utf16_range()
+    }
+
+    fn unmark_text(&mut self, _: &mut Window, _: &mut Context<Self>) {
+        self.buffer.unmark();
+    }
+
+    fn replace_text_in_range(
+        &mut self,
+        range_utf16: Option<Range<usize>>,
+        new_text: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.editable() {
+            return;
+        }
+        let range = range_utf16.map(|range| self.buffer.range_from_utf16(&range));
+        let new_text = new_text.replace(['\r', '\n'], " ");
+        self.edit(window, cx, |buffer| buffer.replace(range, &new_text));
+    }
+
+    fn replace_and_mark_text_in_range(
+        &mut self,
+        range_utf16: Option<Range<usize>>,
+        new_text: &str,
+        new_selected_range_utf16: Option<Range<usize>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.editable() {
+            return;
+        }
+        let range = range_utf16.map(|range| self.buffer.range_from_utf16(&range));
+        let new_selected_range =
+            new_selected_range_utf16.map(|range| range_from_utf16(new_text, &range));
+        self.edit(window, cx, |buffer| {
+            buffer.replace_and_mark(range, new_text, new_selected_range)
+        });
+    }
+
+    fn bounds_for_range(
+        &mut self,
+        range_utf16: Range<usize>,
+        bounds: Bounds<Pixels>,
+        _: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Option<Bounds<Pixels>> {
+        let line = self.last_layout.as_ref()?;
+        let range = self.buffer.range_from_utf16(&range_utf16);
+        let start = line.x_for_index(self.to_display_offset(range.start)) - self.scroll_x;
+        let end = line.x_for_index(self.to_display_offset(range.end)) - self.scroll_x;
+        Some(Bounds::from_corners(
+            point(bounds.left() + start, bounds.top()),
+            point(bounds.left() + end, bounds.bottom()),
+        ))
+    }
+
+    fn character_index_for_point(
+        &mut self,
+        point: Point<Pixels>,
+        _: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Option<usize> {
+        self.utf16_index_for_point(point)
+    }
+}
+
+impl Render for TextInput {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let colors = cx.theme().colors.clone();
+        let radius = cx.theme().styles.radius;
+        let focused = self.focus_handle.is_focused(window);
+
+        h_flex()
+            .key_context(CONTEXT)
+            .when(!self.disabled, |this| {
+                this.track_focus(&self.focus_handle)
+                    .cursor_text()
+                    .on_action(cx.listener(Self::left))
+                    .on_action(cx.listener(Self::right))
+                    .on_action(cx.listener(Self::select_left))
+                    .on_action(cx.listener(Self::select_right))
+                    .on_action(cx.listener(Self::word_left))
+                    .on_action(cx.listener(Self::word_right))
+                    .on_action(cx.listener(Self::select_word_left))
+                    .on_action(cx.listener(Self::select_word_right))
+                    .on_action(cx.listener(Self::select_all))
+                    .on_action(cx.listener(Self::home))
+                    .on_action(cx.listener(Self::end))
+                    .on_action(cx.listener(Self::select_to_home))
+                    .on_action(cx.listener(Self::select_to_end))
+                    .on_action(cx.listener(Self::backspace))
+                    .on_action(cx.listener(Self::delete))
+                    .on_action(cx.listener(Self::copy))
+                    .on_action(cx.listener(Self::cut))
+                    .on_action(cx.listener(Self::paste))
+                    .on_action(cx.listener(Self::undo))
+                    .on_action(cx.listener(Self::redo))
+                    .on_action(cx.listener(Self::enter))
+                    .on_action(cx.listener(Self::show_character_palette))
+                    .on_mouse_down(MouseButton::Left, cx.listener(Self::on_mouse_down))
+                    .on_mouse_up(MouseButton::Left, cx.listener(Self::on_mouse_up))
+                    .on_mouse_up_out(MouseButton::Left, cx.listener(Self::on_mouse_up))
+                    .on_mouse_move(cx.listener(Self::on_mouse_move))
+            })
+            .w_full()
+            .gap_2()
+            .overflow_hidden()
+            .rounded(radius)
+            .border_1()
+            .border_color(if self.invalid {
+                colors.danger
+            } else if focused && !self.read_only {
+                colors.primary
+            } else {
+                colors.border
+            })
+            .bg(colors.bg)
+            .text_color(colors.text)
+            .map(|this| match self.size {
+                Size::XSmall => this.h_7().px_2p5().text_xs(),
+                Size::Small => this.h_8().px_3p5().text_sm(),
+                Size::Medium => this.h_9().px_4(),
+                Size::Large => this.h_10().px_5(),
+                Size::Custom(size) => this.h(size * 0.1 + rems(1.5)),
+            })
+            .when(self.disabled, |this| this.cursor_not_allowed().opacity(0.5))
+            .when_some(self.prefix.clone(), |this, icon| {
+                this.child(
+                    div()
+                        .flex_none()
+                        .text_color(colors.text_muted)
+                        .child(icon.size(self.size.into())),
+                )
+            })
+            .child(
+                div()
+                    .flex_1()
+                    .min_w_0()
+                    .child(TextElement::new(cx.entity())),
+            )
+            .when_some(self.suffix.clone(), |this, icon| {
+                this.child(
+                    div()
+                        .flex_none()
+                        .text_color(colors.text_muted)
+                        .child(icon.size(self.size.into())),
+                )
+            })
+    }
+}
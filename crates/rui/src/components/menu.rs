@@ -0,0 +1,7 @@
+mod context_menu;
+mod dropdown_menu;
+mod right_click_menu;
+
+pub use context_menu::*;
+pub use dropdown_menu::*;
+pub use right_click_menu::*;
@@ -0,0 +1,665 @@
+use std::rc::Rc;
+
+use gpui::{
+    actions, anchored, canvas, deferred, Action, Context, Corner, DismissEvent, Entity,
+    EventEmitter, FocusHandle, Focusable, KeyBinding, KeyDownEvent, MouseDownEvent, Pixels, Point,
+    Render, Subscription,
+};
+
+use crate::{prelude::*, Divider, Icon, IconName, IconSize, Text, ToggleState, Typeahead};
+
+actions!(
+    context_menu,
+    [
+        MenuPrevious,
+        MenuNext,
+        MenuFirst,
+        MenuLast,
+        MenuConfirm,
+        MenuCancel,
+        OpenSubmenu,
+        CloseSubmenu
+    ]
+);
+
+const CONTEXT: &str = "ContextMenu";
+/// The space kept between a submenu and the edges of the window.
+const WINDOW_MARGIN: Pixels = px(8.);
+/// The space between a submenu and its parent menu.
+const SUBMENU_GAP: Pixels = px(5.);
+
+type BuildMenu = Rc<dyn Fn(ContextMenu, &mut Window, &mut Context<ContextMenu>) -> ContextMenu>;
+
+/// An item of a [`ContextMenu`] that runs a handler, dispatches an action or opens a submenu.
+pub struct ContextMenuEntry {
+    label: SharedString,
+    icon: Option<IconName>,
+    toggle: Option<ToggleState>,
+    key_hint: Option<SharedString>,
+    disabled: bool,
+    action: Option<Box<dyn Action>>,
+    handler: Option<Rc<dyn Fn(&mut Window, &mut App) + 'static>>,
+    submenu: Option<BuildMenu>,
+}
+
+impl ContextMenuEntry {
+    pub fn new(label: impl Into<SharedString>) -> Self {
+        Self {
+            label: label.into(),
+            icon: None,
+            toggle: None,
+            key_hint: None,
+            disabled: false,
+            action: None,
+            handler: None,
+            submenu: None,
+        }
+    }
+
+    /// Shows an icon before the label.
+    pub fn icon(mut self, icon: IconName) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Makes the entry checkable, with a check when selected and a dash when indeterminate.
+    pub fn toggle(mut self, toggle: impl Into<ToggleState>) -> Self {
+        self.toggle = Some(toggle.into());
+        self
+    }
+
+    /// Shows this keybinding after the label, instead of the one bound to the action.
+    pub fn key_hint(mut self, key_hint: impl Into<SharedString>) -> Self {
+        self.key_hint = Some(key_hint.into());
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Dispatches the action when the entry is picked, see [`ContextMenu::action_context`].
+    ///
+    /// The keybinding of the action is shown after the label.
+    pub fn action(mut self, action: impl Action) -> Self {
+        self.action = Some(Box::new(action));
+        self
+    }
+
+    /// Called when the entry is picked.
+    pub fn handler(mut self, handler: impl Fn(&mut Window, &mut App) + 'static) -> Self {
+        self.handler = Some(Rc::new(handler));
+        self
+    }
+
+    /// Opens a submenu, built by `build` each time it opens, instead of closing the menu.
+    pub fn submenu(
+        mut self,
+        build: impl Fn(ContextMenu, &mut Window, &mut Context<ContextMenu>) -> ContextMenu + 'static,
+    ) -> Self {
+        self.submenu = Some(Rc::new(build));
+        self
+    }
+}
+
+enum ContextMenuItem {
+    Entry(ContextMenuEntry),
+    Header(SharedString),
+    Separator,
+}
+
+struct OpenSubmenuState {
+    /// The index of the entry the submenu opened from.
+    ix: usize,
+    menu: Entity<ContextMenu>,
+    _subscriptions: [Subscription; 2],
+}
+
+/// A list of entries, separators and headers, shown in a [`DropdownMenu`](crate::DropdownMenu)
+/// or a [`RightClickMenu`](crate::RightClickMenu).
+///
+/// The arrow keys move through the entries, enter picks one, right opens a submenu and left
+/// closes it, and escape closes the menu. Typing jumps to the first entry starting with the
+/// typed text. The menu emits a [`DismissEvent`] once an entry is picked, or on escape or a
+/// click outside of it and its submenus.
+///
+/// Call [`ContextMenu::bind_keys`] once at startup.
+pub struct ContextMenu {
+    focus_handle: FocusHandle,
+    items: Vec<ContextMenuItem>,
+    highlighted: Option<usize>,
+    action_context: Option<FocusHandle>,
+    submenu: Option<OpenSubmenuState>,
+    is_submenu: bool,
+    typeahead: Typeahead,
+    bounds: Bounds<Pixels>,
+}
+
+impl EventEmitter<DismissEvent> for ContextMenu {}
+
+impl Focusable for ContextMenu {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl ContextMenu {
+    pub fn bind_keys(cx: &mut App) {
+        cx.bind_keys([
+            KeyBinding::new("up", MenuPrevious, Some(CONTEXT)),
+            KeyBinding::new("down", MenuNext, Some(CONTEXT)),
+            KeyBinding::new("home", MenuFirst, Some(CONTEXT)),
+            KeyBinding::new("end", MenuLast, Some(CONTEXT)),
+            KeyBinding::new("enter", MenuConfirm, Some(CONTEXT)),
+            KeyBinding::new("escape", MenuCancel, Some(CONTEXT)),
+            KeyBinding::new("right", OpenSubmenu, Some(CONTEXT)),
+            KeyBinding::new("left", CloseSubmenu, Some(CONTEXT)),
+        ]);
+    }
+
+    /// Creates an empty menu, its actions go to the element focused now.
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            items: Vec::new(),
+            highlighted: None,
+            action_context: window.focused(cx),
+            submenu: None,
+            is_submenu: false,
+            typeahead: Typeahead::default(),
+            bounds: Bounds::default(),
+        }
+    }
+
+    /// Creates a menu and adds its items with `build`.
+    pub fn build(
+        window: &mut Window,
+        cx: &mut App,
+        build: impl FnOnce(Self, &mut Window, &mut Context<Self>) -> Self,
+    ) -> Entity<Self> {
+        cx.new(|cx| build(Self::new(window, cx), window, cx))
+    }
+
+    pub fn entry(mut self, entry: ContextMenuEntry) -> Self {
+        self.items.push(ContextMenuItem::Entry(entry));
+        self
+    }
+
+    /// Adds an entry that dispatches `action`.
+    pub fn action(self, label: impl Into<SharedString>, action: impl Action) -> Self {
+        self.entry(ContextMenuEntry::new(label).action(action))
+    }
+
+    /// Adds a muted label above the next entries.
+    pub fn header(mut self, label: impl Into<SharedString>) -> Self {
+        self.items.push(ContextMenuItem::Header(label.into()));
+        self
+    }
+
+    pub fn separator(mut self) -> Self {
+        self.items.push(ContextMenuItem::Separator);
+        self
+    }
+
+    /// Sets the element the actions of the entries are dispatched to. Default is the element
+    /// focused when the menu was created.
+    pub fn action_context(mut self, focus_handle: FocusHandle) -> Self {
+        self.action_context = Some(focus_handle);
+        self
+    }
+
+    fn entry_at(&self, ix: usize) -> Option<&ContextMenuEntry> {
+        match self.items.get(ix) {
+            Some(ContextMenuItem::Entry(entry)) if !entry.disabled => Some(entry),
+            _ => None,
+        }
+    }
+
+    fn highlight(&mut self, ix: Option<usize>, window: &mut Window, cx: &mut Context<Self>) {
+        if self
+            .submenu
+            .as_ref()
+            .is_some_and(|submenu| Some(submenu.ix) != ix)
+        {
+            self.close_submenu(window, cx);
+        }
+        self.highlighted = ix;
+        cx.notify();
+    }
+
+    /// Picks the entry at `ix`, opening and focusing its submenu if it has one.
+    fn activate(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entry_at(ix) else {
+            return;
+        };
+        if entry.submenu.is_some() {
+            self.open_submenu(ix, true, window, cx);
+            return;
+        }
+        let handler = entry.handler.clone();
+        let action = entry.action.as_ref().map(|action| action.boxed_clone());
+        if let Some(handler) = handler {
+            handler(window, cx);
+        }
+        if let Some(action) = action {
+            if let Some(action_context) = self.action_context.as_ref() {
+                window.focus(action_context);
+            }
+            window.dispatch_action(action, cx);
+        }
+        cx.emit(DismissEvent);
+    }
+
+    fn open_submenu(
+        &mut self,
+        ix: usize,
+        focus: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(build) = self.entry_at(ix).and_then(|entry| entry.submenu.clone()) else {
+            return;
+        };
+        if self.submenu.as_ref().is_none_or(|submenu| submenu.ix != ix) {
+            self.close_submenu(window, cx);
+            let action_context = self.action_context.clone();
+            let menu = cx.new(|cx| {
+                let mut menu = ContextMenu::new(window, cx);
+                menu.is_submenu = true;
+                menu.action_context = action_context;
+                build(menu, window, cx)
+            });
+            // An entry picked in the submenu closes the whole menu.
+            let subscriptions = [
+                cx.subscribe_in(&menu, window, |this, _, _: &DismissEvent, _, cx| {
+                    this.submenu = None;
+                    cx.emit(DismissEvent);
+                }),
+                // The submenu is placed once its size is known.
+                cx.observe(&menu, |_, _, cx| cx.notify()),
+            ];
+            self.submenu = Some(OpenSubmenuState {
+                ix,
+                menu,
+                _subscriptions: subscriptions,
+            });
+        }
+        self.highlighted = Some(ix);
+        if focus {
+            if let Some(submenu) = self.submenu.as_ref() {
+                submenu.menu.update(cx, |menu, cx| {
+                    let first = next_enabled(&menu.enabled(), None, true);
+                    menu.highlight(first, window, cx);
+                });
+                window.focus(&submenu.menu.focus_handle(cx));
+            }
+        }
+        cx.notify();
+    }
+
+    /// Closes the open submenu, returning whether there was one.
+    fn close_submenu(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        let Some(submenu) = self.submenu.take() else {
+            return false;
+        };
+        if submenu.menu.focus_handle(cx).contains_focused(window, cx) {
+            window.focus(&self.focus_handle);
+        }
+        cx.notify();
+        true
+    }
+
+    /// Whether `position` is in the open submenu, or in one of its submenus.
+    fn submenu_contains(&self, position: Point<Pixels>, cx: &App) -> bool {
+        self.submenu.as_ref().is_some_and(|submenu| {
+            let menu = submenu.menu.read(cx);
+            menu.bounds.contains(&position) || menu.submenu_contains(position, cx)
+        })
+    }
+
+    fn enabled(&self) -> Vec<bool> {
+        (0..self.items.len())
+            .map(|ix| self.entry_at(ix).is_some())
+            .collect()
+    }
+
+    fn select_previous(&mut self, _: &MenuPrevious, window: &mut Window, cx: &mut Context<Self>) {
+        let ix = next_enabled(&self.enabled(), self.highlighted, false);
+        self.highlight(ix, window, cx);
+    }
+
+    fn select_next(&mut self, _: &MenuNext, window: &mut Window, cx: &mut Context<Self>) {
+        let ix = next_enabled(&self.enabled(), self.highlighted, true);
+        self.highlight(ix, window, cx);
+    }
+
+    fn select_first(&mut self, _: &MenuFirst, window: &mut Window, cx: &mut Context<Self>) {
+        let ix = next_enabled(&self.enabled(), None, true);
+        self.highlight(ix, window, cx);
+    }
+
+    fn select_last(&mut self, _: &MenuLast, window: &mut Window, cx: &mut Context<Self>) {
+        let ix = next_enabled(&self.enabled(), None, false);
+        self.highlight(ix, window, cx);
+    }
+
+    fn confirm(&mut self, _: &MenuConfirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.highlighted {
+            self.activate(ix, window, cx);
+        }
+    }
+
+    fn cancel(&mut self, _: &MenuCancel, window: &mut Window, cx: &mut Context<Self>) {
+        if self.close_submenu(window, cx) {
+            return;
+        }
+        if self.is_submenu {
+            // The parent menu closes this one.
+            cx.propagate();
+        } else {
+            cx.emit(DismissEvent);
+        }
+    }
+
+    fn open_highlighted_submenu(
+        &mut self,
+        _: &OpenSubmenu,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match self.highlighted {
+            Some(ix) => self.open_submenu(ix, true, window, cx),
+            None => cx.propagate(),
+        }
+    }
+
+    fn close_open_submenu(
+        &mut self,
+        _: &CloseSubmenu,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.close_submenu(window, cx) {
+            cx.propagate();
+        }
+    }
+
+    /// Highlights the first enabled entry whose label starts with the typed text.
+    fn on_key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        let modifiers = &event.keystroke.modifiers;
+        if modifiers.control || modifiers.alt || modifiers.platform {
+            return;
+        }
+        let Some(key_char) = event.keystroke.key_char.as_ref() else {
+            return;
+        };
+        let labels = self.items.iter().map(|item| match item {
+            ContextMenuItem::Entry(entry) if !entry.disabled => Some(entry.label.as_ref()),
+            _ => None,
+        });
+        let Some(ix) = self.typeahead.type_char(key_char, labels, self.highlighted) else {
+            return;
+        };
+        cx.stop_propagation();
+        self.highlight(Some(ix), window, cx);
+    }
+
+    fn render_entry(
+        &self,
+        ix: usize,
+        entry: &ContextMenuEntry,
+        slots: (bool, bool),
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let colors = cx.theme().colors;
+        let highlighted = self.highlighted == Some(ix);
+        let (toggle_slot, icon_slot) = slots;
+        let key_hint = entry.key_hint.clone().or_else(|| {
+            let action = entry.action.as_ref()?;
+            key_hint_for(action.as_ref(), self.action_context.as_ref(), window)
+        });
+        let submenu = self
+            .submenu
+            .as_ref()
+            .filter(|submenu| submenu.ix == ix)
+            .map(|submenu| submenu.menu.clone());
+        let toggle_icon = entry.toggle.and_then(|toggle| match toggle {
+            ToggleState::Selected => Some(IconName::Check),
+            ToggleState::Indeterminate => Some(IconName::Dash),
+            ToggleState::Unselected => None,
+        });
+        let slot = |icon: Option<IconName>| {
+            div()
+                .flex_none()
+                .size_4()
+                .flex()
+                .items_center()
+                .justify_center()
+                .children(icon.map(|icon| Icon::new(icon).size(IconSize::Small)))
+        };
+
+        h_flex()
+            .id(ix)
+            .relative()
+            .w_full()
+            .gap_2()
+            .px_2()
+            .py_1()
+            .rounded(cx.theme().styles.radius)
+            .when(highlighted, |this| this.bg(colors.element_hover))
+            .map(|this| {
+                if entry.disabled {
+                    this.opacity(0.5).cursor_not_allowed()
+                } else {
+                    let has_submenu = entry.submenu.is_some();
+                    this.cursor_pointer()
+                        .on_mouse_move(cx.listener(move |this, _, window, cx| {
+                            if this.highlighted == Some(ix) {
+                                return;
+                            }
+                            if has_submenu {
+                                this.open_submenu(ix, false, window, cx);
+                            } else {
+                                this.highlight(Some(ix), window, cx);
+                            }
+                        }))
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            this.activate(ix, window, cx);
+                        }))
+                }
+            })
+            .when(toggle_slot, |this| this.child(slot(toggle_icon)))
+            .when(icon_slot, |this| this.child(slot(entry.icon)))
+            .child(
+                div()
+                    .flex_1()
+                    .child(Text::new(entry.label.clone()).truncate()),
+            )
+            .children(
+                key_hint.map(|key_hint| Text::new(key_hint).text_xs().color(colors.text_muted)),
+            )
+            .when(entry.submenu.is_some(), |this| {
+                this.child(
+                    Icon::new(IconName::ChevronRight)
+                        .size(IconSize::Small)
+                        .color(colors.text_muted),
+                )
+            })
+            .children(submenu.map(|menu| {
+                // Next to the entry, aligned with the top of the menu. It flips to the left
+                // when there is no room on the right and more on the left.
+                let width = menu.read(cx).bounds.size.width + SUBMENU_GAP;
+                let room_right = window.viewport_size().width - self.bounds.right() - WINDOW_MARGIN;
+                let room_left = self.bounds.left() - WINDOW_MARGIN;
+                let flip = room_right < width && room_left > room_right;
+                div()
+                    .absolute()
+                    .top(px(-5.))
+                    .map(|this| {
+                        if flip {
+                            this.right_full()
+                        } else {
+                            this.left_full()
+                        }
+                    })
+                    .child(
+                        deferred(
+                            anchored()
+                                .when(flip, |this| this.anchor(Corner::TopRight))
+                                .snap_to_window_with_margin(WINDOW_MARGIN)
+                                .child(
+                                    div()
+                                        .map(|this| {
+                                            if flip {
+                                                this.mr(SUBMENU_GAP)
+                                            } else {
+                                                this.ml(SUBMENU_GAP)
+                                            }
+                                        })
+                                        .elevation_2(cx)
+                                        .child(menu),
+                                ),
+                        )
+                        .with_priority(1),
+                    )
+            }))
+    }
+}
+
+/// The keystrokes bound to `action`, like `ctrl-s` or `g g`.
+fn key_hint_for(
+    action: &dyn Action,
+    context: Option<&FocusHandle>,
+    window: &Window,
+) -> Option<SharedString> {
+    let binding = match context {
+        Some(context) => window.highest_precedence_binding_for_action_in(action, context),
+        None => window.highest_precedence_binding_for_action(action),
+    }?;
+    let keystrokes: Vec<String> = binding
+        .keystrokes()
+        .iter()
+        .map(|keystroke| keystroke.to_string())
+        .collect();
+    Some(keystrokes.join(" ").into())
+}
+
+/// Returns the enabled item after `from` in `direction`, wrapping around, or the first one in
+/// `direction` if `from` is `None`.
+fn next_enabled(enabled: &[bool], from: Option<usize>, forward: bool) -> Option<usize> {
+    let len = enabled.len();
+    if len == 0 {
+        return None;
+    }
+    let start = match (from, forward) {
+        (Some(from), true) => from + 1,
+        (Some(from), false) => from + len - 1,
+        (None, true) => 0,
+        (None, false) => len - 1,
+    };
+    (0..len)
+        .map(|step| {
+            if forward {
+                (start + step) % len
+            } else {
+                (start + len - step) % len
+            }
+        })
+        .find(|ix| enabled[*ix])
+}
+
+impl Render for ContextMenu {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let entries = || {
+            self.items.iter().filter_map(|item| match item {
+                ContextMenuItem::Entry(entry) => Some(entry),
+                _ => None,
+            })
+        };
+        // Entries are aligned by keeping room for a check or an icon if any entry has one.
+        let slots = (
+            entries().any(|entry| entry.toggle.is_some()),
+            entries().any(|entry| entry.icon.is_some()),
+        );
+        let children: Vec<AnyElement> = self
+            .items
+            .iter()
+            .enumerate()
+            .map(|(ix, item)| match item {
+                ContextMenuItem::Entry(entry) => self
+                    .render_entry(ix, entry, slots, window, cx)
+                    .into_any_element(),
+                ContextMenuItem::Header(label) => div()
+                    .px_2()
+                    .py_1()
+                    .text_xs()
+                    .text_color(cx.theme().colors.text_muted)
+                    .child(label.clone())
+                    .into_any_element(),
+                ContextMenuItem::Separator => Divider::new().my_1().into_any_element(),
+            })
+            .collect();
+        let entity = cx.entity();
+
+        v_flex()
+            .id("context-menu")
+            .key_context(CONTEXT)
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::open_highlighted_submenu))
+            .on_action(cx.listener(Self::close_open_submenu))
+            .on_key_down(cx.listener(Self::on_key_down))
+            .when(!self.is_submenu, |this| {
+                this.on_mouse_down_out(cx.listener(|this, event: &MouseDownEvent, _, cx| {
+                    if !this.submenu_contains(event.position, cx) {
+                        cx.emit(DismissEvent);
+                    }
+                }))
+            })
+            .min_w(px(180.))
+            .p_1()
+            .text_color(cx.theme().colors.text)
+            .children(children)
+            .child(
+                canvas(
+                    move |bounds, _, cx| {
+                        entity.update(cx, |this, cx| {
+                            // The parent of a submenu places it from its size.
+                            if this.is_submenu && this.bounds.size != bounds.size {
+                                cx.notify();
+                            }
+                            this.bounds = bounds;
+                        })
+                    },
+                    |_, _, _, _| {},
+                )
+                .absolute()
+                .size_full(),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_enabled() {
+        let enabled = [false, true, false, true, true, false];
+        assert_eq!(next_enabled(&enabled, None, true), Some(1));
+        assert_eq!(next_enabled(&enabled, None, false), Some(4));
+        assert_eq!(next_enabled(&enabled, Some(1), true), Some(3));
+        assert_eq!(next_enabled(&enabled, Some(4), true), Some(1));
+        assert_eq!(next_enabled(&enabled, Some(1), false), Some(4));
+        assert_eq!(next_enabled(&enabled, Some(3), false), Some(1));
+        assert_eq!(next_enabled(&[false, false], None, true), None);
+        assert_eq!(next_enabled(&[], Some(0), true), None);
+    }
+}
@@ -0,0 +1,52 @@
+use std::rc::Rc;
+
+use gpui::Context;
+
+use crate::{prelude::*, ContextMenu, EdgeCorner, Popover};
+
+/// A [`ContextMenu`] that opens below a trigger, usually a [`Button`](crate::Button), when
+/// it is clicked.
+///
+/// The menu is built by `menu` each time it opens. Call [`ContextMenu::bind_keys`] once at
+/// startup.
+#[derive(IntoElement)]
+pub struct DropdownMenu {
+    id: ElementId,
+    trigger: AnyElement,
+    menu: Rc<dyn Fn(ContextMenu, &mut Window, &mut Context<ContextMenu>) -> ContextMenu>,
+    anchor: EdgeCorner,
+}
+
+impl DropdownMenu {
+    pub fn new(
+        id: impl Into<ElementId>,
+        trigger: impl IntoElement,
+        menu: impl Fn(ContextMenu, &mut Window, &mut Context<ContextMenu>) -> ContextMenu + 'static,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            trigger: trigger.into_any_element(),
+            menu: Rc::new(menu),
+            anchor: EdgeCorner::BottomLeft,
+        }
+    }
+
+    /// Sets where the menu is attached to the trigger, see [`Popover::anchor`].
+    pub fn anchor(mut self, anchor: EdgeCorner) -> Self {
+        self.anchor = anchor;
+        self
+    }
+}
+
+impl RenderOnce for DropdownMenu {
+    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+        let menu = self.menu;
+        Popover::new(self.id)
+            .trigger(self.trigger)
+            .anchor(self.anchor)
+            .dismiss_on_outside_click(false)
+            .content(move |window, cx| {
+                ContextMenu::build(window, cx, |this, window, cx| menu(this, window, cx))
+            })
+    }
+}
@@ -0,0 +1,44 @@
+use std::rc::Rc;
+
+use gpui::Context;
+
+use crate::{prelude::*, ContextMenu, Popover};
+
+/// A [`ContextMenu`] that opens at the mouse on a right click in an area.
+///
+/// The menu is built by `menu` each time it opens. Call [`ContextMenu::bind_keys`] once at
+/// startup.
+#[derive(IntoElement)]
+pub struct RightClickMenu {
+    id: ElementId,
+    area: AnyElement,
+    menu: Rc<dyn Fn(ContextMenu, &mut Window, &mut Context<ContextMenu>) -> ContextMenu>,
+}
+
+impl RightClickMenu {
+    pub fn new(
+        id: impl Into<ElementId>,
+        area: impl IntoElement,
+        menu: impl Fn(ContextMenu, &mut Window, &mut Context<ContextMenu>) -> ContextMenu + 'static,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            area: area.into_any_element(),
+            menu: Rc::new(menu),
+        }
+    }
+}
+
+impl RenderOnce for RightClickMenu {
+    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+        let menu = self.menu;
+        Popover::new(self.id)
+            .trigger(self.area)
+            .right_click(true)
+            .offset(px(0.))
+            .dismiss_on_outside_click(false)
+            .content(move |window, cx| {
+                ContextMenu::build(window, cx, |this, window, cx| menu(this, window, cx))
+            })
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{prelude::*, Button, Headline, HeadlineSize, IconName, ModalView, Text};
+use crate::{
+    prelude::*, Button, Headline, HeadlineSize, IconName, ModalPresentation, ModalView, ModalWidth,
+    Text,
+};
 use gpui::{
     actions, App, Context, DismissEvent, EventEmitter, FocusHandle, Focusable, KeyBinding, Render,
     Window,
@@ -15,6 +18,9 @@ pub struct Modal {
     primary_button: Button,
     dismiss_button: Button,
     show_close: bool,
+    presentation: ModalPresentation,
+    width: ModalWidth,
+    dismiss_on_backdrop_click: bool,
     content_builder: Option<Box<dyn Fn(&mut Window, &mut Context<Self>) -> AnyElement>>,
 }
 
@@ -25,7 +31,19 @@ impl Focusable for Modal {
 }
 impl EventEmitter<DismissEvent> for Modal {}
 
-impl ModalView for Modal {}
+impl ModalView for Modal {
+    fn presentation(&self) -> ModalPresentation {
+        self.presentation
+    }
+
+    fn width(&self) -> ModalWidth {
+        self.width
+    }
+
+    fn dismiss_on_backdrop_click(&self) -> bool {
+        self.dismiss_on_backdrop_click
+    }
+}
 
 impl Modal {
     pub fn new(cx: &mut App, title: impl Into<SharedString>) -> Self {
@@ -35,6 +53,9 @@ impl Modal {
             primary_button: Button::new("Ok").text("Ok"),
             dismiss_button: Button::new("Cancel").text("Cancel").soft(),
             show_close: false,
+            presentation: ModalPresentation::default(),
+            width: ModalWidth::default(),
+            dismiss_on_backdrop_click: true,
             content_builder: None,
         }
     }
@@ -59,6 +80,28 @@ impl Modal {
         self
     }
 
+    pub fn with_width(mut self, width: ModalWidth) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Shows the modal as a drawer sliding in from a side of the window.
+    pub fn drawer(mut self, direction: AnimationDirection) -> Self {
+        self.presentation = ModalPresentation::Drawer(direction);
+        self
+    }
+
+    pub fn fullscreen(mut self) -> Self {
+        self.presentation = ModalPresentation::Fullscreen;
+        self
+    }
+
+    /// Whether a click on the backdrop dismisses the modal, `true` by default.
+    pub fn dismiss_on_backdrop_click(mut self, dismiss: bool) -> Self {
+        self.dismiss_on_backdrop_click = dismiss;
+        self
+    }
+
     fn cancel(&mut self, _: &Escape, _: &mut Window, cx: &mut Context<Self>) {
         cx.emit(DismissEvent)
     }
@@ -71,8 +114,13 @@ impl Render for Modal {
             .track_focus(&self.focus_handle)
             .on_action(cx.listener(Self::cancel))
             .elevation_3(cx)
-            .w_96()
-            .h_auto()
+            .w_full()
+            .map(|this| match self.presentation {
+                ModalPresentation::Dialog
+                | ModalPresentation::Drawer(AnimationDirection::FromTop)
+                | ModalPresentation::Drawer(AnimationDirection::FromBottom) => this.h_auto(),
+                ModalPresentation::Drawer(_) | ModalPresentation::Fullscreen => this.h_full(),
+            })
             .p_4()
             .gap_2()
             // .when(self.show_close, |this| {
@@ -1,7 +1,7 @@
 use crate::prelude::*;
 use gpui::{
-    AnyView, DismissEvent, Entity, FocusHandle, Focusable as _, ManagedView, MouseButton,
-    Subscription,
+    AnyView, DefiniteLength, DismissEvent, Entity, EntityId, FocusHandle, Focusable as _,
+    ManagedView, MouseButton, Subscription,
 };
 
 #[derive(Debug)]
@@ -22,12 +22,63 @@ pub trait ModalView: ManagedView {
     fn fade_out_background(&self) -> bool {
         true
     }
+
+    /// Where the modal is shown in the window.
+    fn presentation(&self) -> ModalPresentation {
+        ModalPresentation::default()
+    }
+
+    /// The width of a dialog or of a side drawer, the view should fill it.
+    fn width(&self) -> ModalWidth {
+        ModalWidth::default()
+    }
+
+    /// Whether a click on the backdrop dismisses the modal. Escape still does.
+    fn dismiss_on_backdrop_click(&self) -> bool {
+        true
+    }
+}
+
+/// How a modal is presented, see [`ModalView::presentation`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ModalPresentation {
+    /// Centered near the top of the window.
+    #[default]
+    Dialog,
+    /// Attached to an edge of the window, sliding in from it. Top and bottom sheets take the
+    /// full width of the window.
+    Drawer(AnimationDirection),
+    /// Covers the whole window.
+    Fullscreen,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ModalWidth {
+    Small,
+    #[default]
+    Medium,
+    Large,
+    Full,
+}
+
+impl ModalWidth {
+    fn length(self) -> DefiniteLength {
+        match self {
+            ModalWidth::Small => rems(20.).into(),
+            ModalWidth::Medium => rems(24.).into(),
+            ModalWidth::Large => rems(40.).into(),
+            ModalWidth::Full => relative(1.),
+        }
+    }
 }
 
 trait ModalViewHandle {
     fn on_before_dismiss(&mut self, window: &mut Window, cx: &mut App) -> DismissDecision;
     fn view(&self) -> AnyView;
     fn fade_out_background(&self, cx: &mut App) -> bool;
+    fn presentation(&self, cx: &mut App) -> ModalPresentation;
+    fn width(&self, cx: &mut App) -> ModalWidth;
+    fn dismiss_on_backdrop_click(&self, cx: &mut App) -> bool;
 }
 
 impl<V: ModalView> ModalViewHandle for Entity<V> {
@@ -42,6 +93,18 @@ impl<V: ModalView> ModalViewHandle for Entity<V> {
     fn fade_out_background(&self, cx: &mut App) -> bool {
         self.read(cx).fade_out_background()
     }
+
+    fn presentation(&self, cx: &mut App) -> ModalPresentation {
+        self.read(cx).presentation()
+    }
+
+    fn width(&self, cx: &mut App) -> ModalWidth {
+        self.read(cx).width()
+    }
+
+    fn dismiss_on_backdrop_click(&self, cx: &mut App) -> bool {
+        self.read(cx).dismiss_on_backdrop_click()
+    }
 }
 
 pub struct ActiveModal {
@@ -51,8 +114,20 @@ pub struct ActiveModal {
     focus_handle: FocusHandle,
 }
 
+impl ActiveModal {
+    fn entity_id(&self) -> EntityId {
+        self.modal.view().entity_id()
+    }
+}
+
+/// Shows modals above the content of the window, stacked on top of each other.
+///
+/// Each modal has its own backdrop, and only the topmost one can be interacted with. When a
+/// modal is dismissed, the focus goes back to where it was before it opened, usually in the
+/// modal below.
 pub struct ModalLayer {
-    active_modal: Option<ActiveModal>,
+    /// The open modals, the topmost last.
+    modals: Vec<ActiveModal>,
     dismiss_on_focus_lost: bool,
 }
 
@@ -65,23 +140,30 @@ impl Default for ModalLayer {
 impl ModalLayer {
     pub fn new() -> Self {
         Self {
-            active_modal: None,
+            modals: Vec::new(),
             dismiss_on_focus_lost: false,
         }
     }
 
+    /// Hides the topmost modal if it is a `V`, or opens a `V` on top of the open modals.
     pub fn toggle_modal<V, B>(&mut self, window: &mut Window, cx: &mut Context<Self>, build_view: B)
     where
         V: ModalView,
         B: FnOnce(&mut Window, &mut Context<V>) -> V,
     {
-        if let Some(active_modal) = &self.active_modal {
-            let is_close = active_modal.modal.view().downcast::<V>().is_ok();
-            let did_close = self.hide_modal(window, cx);
-            if is_close || !did_close {
-                return;
-            }
+        if self.active_modal::<V>().is_some() {
+            self.hide_modal(window, cx);
+            return;
         }
+        self.push_modal(window, cx, build_view);
+    }
+
+    /// Opens a modal on top of the open modals, even if the topmost one is also a `V`.
+    pub fn push_modal<V, B>(&mut self, window: &mut Window, cx: &mut Context<Self>, build_view: B)
+    where
+        V: ModalView,
+        B: FnOnce(&mut Window, &mut Context<V>) -> V,
+    {
         let new_modal = cx.new(|cx| build_view(window, cx));
         self.show_modal(new_modal, window, cx);
     }
@@ -91,18 +173,24 @@ impl ModalLayer {
         V: ModalView,
     {
         let focus_handle = cx.focus_handle();
-        self.active_modal = Some(ActiveModal {
+        let modal_id = new_modal.entity_id();
+        self.modals.push(ActiveModal {
             modal: Box::new(new_modal.clone()),
             _subscriptions: [
                 cx.subscribe_in(
                     &new_modal,
                     window,
-                    |this, _, _: &DismissEvent, window, cx| {
-                        this.hide_modal(window, cx);
+                    move |this, _, _: &DismissEvent, window, cx| {
+                        if let Some(ix) = this.position(modal_id) {
+                            this.hide_modal_at(ix, window, cx);
+                        }
                     },
                 ),
-                cx.on_focus_out(&focus_handle, window, |this, _event, window, cx| {
-                    if this.dismiss_on_focus_lost {
+                cx.on_focus_out(&focus_handle, window, move |this, _event, window, cx| {
+                    // Opening a modal on top takes the focus, that doesn't dismiss this one.
+                    let is_topmost =
+                        this.modals.last().map(ActiveModal::entity_id) == Some(modal_id);
+                    if this.dismiss_on_focus_lost && is_topmost {
                         this.hide_modal(window, cx);
                     }
                 }),
@@ -116,13 +204,19 @@ impl ModalLayer {
         cx.notify();
     }
 
+    /// Hides the topmost modal, returning whether it was hidden.
     pub fn hide_modal(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
-        let Some(active_modal) = self.active_modal.as_mut() else {
-            self.dismiss_on_focus_lost = false;
-            return false;
-        };
+        match self.modals.len().checked_sub(1) {
+            Some(ix) => self.hide_modal_at(ix, window, cx),
+            None => {
+                self.dismiss_on_focus_lost = false;
+                false
+            }
+        }
+    }
 
-        match active_modal.modal.on_before_dismiss(window, cx) {
+    fn hide_modal_at(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        match self.modals[ix].modal.on_before_dismiss(window, cx) {
             DismissDecision::Dismiss(dismiss) => {
                 self.dismiss_on_focus_lost = !dismiss;
                 if !dismiss {
@@ -135,34 +229,98 @@ impl ModalLayer {
             }
         }
 
-        if let Some(active_modal) = self.active_modal.take() {
-            if let Some(previous_focus) = active_modal.previous_focus_handle {
-                if active_modal.focus_handle.contains_focused(window, cx) {
-                    previous_focus.focus(window);
-                }
+        let modal = self.modals.remove(ix);
+        if let Some(above) = self.modals.get_mut(ix) {
+            // The focus was in the hidden modal before the one above opened, it can't go back
+            // there, so it goes back to where the hidden modal got it from.
+            above.previous_focus_handle = modal.previous_focus_handle;
+        } else if let Some(previous_focus) = modal.previous_focus_handle {
+            if modal.focus_handle.contains_focused(window, cx) {
+                previous_focus.focus(window);
             }
-            cx.notify();
         }
+        cx.notify();
         true
     }
 
+    fn position(&self, modal_id: EntityId) -> Option<usize> {
+        self.modals
+            .iter()
+            .position(|modal| modal.entity_id() == modal_id)
+    }
+
+    /// Returns the topmost modal, if it is a `V`.
     pub fn active_modal<V>(&self) -> Option<Entity<V>>
     where
         V: 'static,
     {
-        let active_modal = self.active_modal.as_ref()?;
+        let active_modal = self.modals.last()?;
         active_modal.modal.view().downcast::<V>().ok()
     }
 
+    /// Returns the topmost `V` of the open modals, even if other modals are above it.
+    pub fn find_modal<V>(&self) -> Option<Entity<V>>
+    where
+        V: 'static,
+    {
+        self.modals
+            .iter()
+            .rev()
+            .find_map(|modal| modal.modal.view().downcast::<V>().ok())
+    }
+
     pub fn has_active_modal(&self) -> bool {
-        self.active_modal.is_some()
+        !self.modals.is_empty()
     }
-}
 
-impl Render for ModalLayer {
-    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let Some(active_modal) = &self.active_modal else {
-            return div();
+    /// The number of open modals.
+    pub fn modal_count(&self) -> usize {
+        self.modals.len()
+    }
+
+    fn render_modal(&self, active_modal: &ActiveModal, cx: &mut Context<Self>) -> impl IntoElement {
+        let modal = &active_modal.modal;
+        let width = modal.width(cx).length();
+        let content = h_flex()
+            .occlude()
+            .justify_center()
+            .track_focus(&active_modal.focus_handle)
+            .child(modal.view())
+            .on_mouse_down(MouseButton::Left, |_, _, cx| {
+                cx.stop_propagation();
+            });
+
+        let content = match modal.presentation(cx) {
+            ModalPresentation::Dialog => v_flex()
+                .h(px(0.0))
+                .top_20()
+                .px_4()
+                .items_center()
+                .child(content.w(width))
+                .into_any_element(),
+            ModalPresentation::Drawer(direction) => div()
+                .absolute()
+                .size_full()
+                .top_0()
+                .left_0()
+                .child(content.absolute().map(|this| match direction {
+                    AnimationDirection::FromLeft => this.top_0().left_0().h_full().w(width),
+                    AnimationDirection::FromRight => this.top_0().right_0().h_full().w(width),
+                    AnimationDirection::FromTop => {
+                        this.top_0().left_0().w_full().max_h(relative(0.8))
+                    }
+                    AnimationDirection::FromBottom => {
+                        this.bottom_0().left_0().w_full().max_h(relative(0.8))
+                    }
+                }))
+                .slide_in(direction)
+                .into_any_element(),
+            ModalPresentation::Fullscreen => content
+                .absolute()
+                .size_full()
+                .top_0()
+                .left_0()
+                .into_any_element(),
         };
 
         div()
@@ -171,33 +329,40 @@ impl Render for ModalLayer {
             .size_full()
             .top_0()
             .left_0()
-            .when(active_modal.modal.fade_out_background(cx), |el| {
+            .when(modal.fade_out_background(cx), |el| {
                 let mut background = cx.theme().colors.bg_elevated_surface;
                 background.fade_out(0.2);
                 el.bg(background)
             })
-            .on_mouse_down(
-                MouseButton::Left,
-                cx.listener(|this, _, window, cx| {
-                    this.hide_modal(window, cx);
-                }),
-            )
-            .child(
-                v_flex()
-                    .h(px(0.0))
-                    .top_20()
-                    .flex()
-                    .flex_col()
-                    .items_center()
-                    .track_focus(&active_modal.focus_handle)
-                    .child(
-                        h_flex()
-                            .occlude()
-                            .child(active_modal.modal.view())
-                            .on_mouse_down(MouseButton::Left, |_, _, cx| {
-                                cx.stop_propagation();
-                            }),
-                    ),
-            )
+            .when(modal.dismiss_on_backdrop_click(cx), |el| {
+                el.on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(|this, _, window, cx| {
+                        this.hide_modal(window, cx);
+                    }),
+                )
+            })
+            .child(content)
+    }
+}
+
+impl Render for ModalLayer {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if self.modals.is_empty() {
+            return div();
+        }
+
+        // Each modal covers the ones below with its own backdrop.
+        let modals: Vec<AnyElement> = self
+            .modals
+            .iter()
+            .map(|active_modal| self.render_modal(active_modal, cx).into_any_element())
+            .collect();
+        div()
+            .absolute()
+            .size_full()
+            .top_0()
+            .left_0()
+            .children(modals)
     }
 }
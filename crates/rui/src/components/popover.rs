@@ -0,0 +1,654 @@
+use std::rc::Rc;
+
+use gpui::{
+    actions, anchored, canvas, deferred, percentage, point, AnyView, DismissEvent, Entity,
+    FocusHandle, Focusable as _, KeyBinding, ManagedView, MouseButton, Pixels, Point, Stateful,
+    Subscription, Transformation,
+};
+
+use crate::{prelude::*, EdgeCorner, ElevationIndex, Icon, IconName, Position};
+
+actions!(popover, [ClosePopover]);
+
+const CONTEXT: &str = "Popover";
+
+/// The space kept between the popover and the edges of the window.
+const WINDOW_MARGIN: Pixels = px(8.);
+/// The size of the box the arrow is drawn in, the arrow icon is drawn at 16px.
+const ARROW_SIZE: Pixels = px(16.);
+/// How far the arrow sticks out of the popover.
+const ARROW_HEIGHT: Pixels = px(8.);
+/// The closest the arrow gets to a corner of the popover, so it stays clear of the rounding.
+const ARROW_INSET: Pixels = px(14.);
+
+/// Where a popover goes, once flipped and shifted to fit in the window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Placement {
+    /// The top left corner of the popover, in window coordinates.
+    pub origin: Point<Pixels>,
+    /// The side of the anchor the popover is on.
+    pub side: Position,
+    /// The distance from the start of the edge facing the anchor to the arrow tip.
+    pub arrow: Pixels,
+}
+
+/// The side of the anchor a popover attached at `corner` opens on.
+fn side_of(corner: EdgeCorner) -> Position {
+    match corner {
+        EdgeCorner::TopLeft | EdgeCorner::Top | EdgeCorner::TopRight => Position::Top,
+        EdgeCorner::BottomLeft | EdgeCorner::Bottom | EdgeCorner::BottomRight => Position::Bottom,
+        EdgeCorner::Left => Position::Left,
+        EdgeCorner::Right => Position::Right,
+    }
+}
+
+fn opposite(side: Position) -> Position {
+    match side {
+        Position::Top => Position::Bottom,
+        Position::Bottom => Position::Top,
+        Position::Left => Position::Right,
+        Position::Right => Position::Left,
+    }
+}
+
+/// Clamps `value` to `min..=max`, preferring `min` when the range is empty.
+fn clamp(value: Pixels, min: Pixels, max: Pixels) -> Pixels {
+    if value > max {
+        if max > min {
+            max
+        } else {
+            min
+        }
+    } else if value < min {
+        min
+    } else {
+        value
+    }
+}
+
+/// Places a popover of `size` against the `anchor` at `corner`, `gap` away from it.
+///
+/// The popover flips to the opposite side when it doesn't fit and there is more room there,
+/// then shifts to stay inside the `viewport`. A corner aligns the popover with that edge of
+/// the anchor, `TopLeft` opens above it with the left edges aligned, while an edge centers it.
+pub(crate) fn place(
+    anchor: Bounds<Pixels>,
+    size: gpui::Size<Pixels>,
+    viewport: gpui::Size<Pixels>,
+    corner: EdgeCorner,
+    gap: Pixels,
+) -> Placement {
+    let room = |side: Position| match side {
+        Position::Top => anchor.top() - gap - WINDOW_MARGIN,
+        Position::Bottom => viewport.height - anchor.bottom() - gap - WINDOW_MARGIN,
+        Position::Left => anchor.left() - gap - WINDOW_MARGIN,
+        Position::Right => viewport.width - anchor.right() - gap - WINDOW_MARGIN,
+    };
+    let needed = |side: Position| match side {
+        Position::Top | Position::Bottom => size.height,
+        Position::Left | Position::Right => size.width,
+    };
+
+    let mut side = side_of(corner);
+    if room(side) < needed(side) && room(opposite(side)) > room(side) {
+        side = opposite(side);
+    }
+
+    let center = anchor.center();
+    let origin = match side {
+        Position::Top | Position::Bottom => {
+            let x = match corner {
+                EdgeCorner::TopLeft | EdgeCorner::BottomLeft => anchor.left(),
+                EdgeCorner::TopRight | EdgeCorner::BottomRight => anchor.right() - size.width,
+                _ => center.x - size.width / 2.,
+            };
+            let y = match side {
+                Position::Top => anchor.top() - gap - size.height,
+                _ => anchor.bottom() + gap,
+            };
+            point(x, y)
+        }
+        Position::Left | Position::Right => {
+            let x = match side {
+                Position::Left => anchor.left() - gap - size.width,
+                _ => anchor.right() + gap,
+            };
+            point(x, center.y - size.height / 2.)
+        }
+    };
+    let origin = point(
+        clamp(
+            origin.x,
+            WINDOW_MARGIN,
+            viewport.width - size.width - WINDOW_MARGIN,
+        ),
+        clamp(
+            origin.y,
+            WINDOW_MARGIN,
+            viewport.height - size.height - WINDOW_MARGIN,
+        ),
+    );
+
+    let (arrow, length) = match side {
+        Position::Top | Position::Bottom => (center.x - origin.x, size.width),
+        Position::Left | Position::Right => (center.y - origin.y, size.height),
+    };
+    let arrow = if length < ARROW_INSET * 2. {
+        length / 2.
+    } else {
+        clamp(arrow, ARROW_INSET, length - ARROW_INSET)
+    };
+
+    Placement {
+        origin,
+        side,
+        arrow,
+    }
+}
+
+#[derive(Default)]
+struct PopoverState {
+    content: Option<AnyView>,
+    content_focus: Option<FocusHandle>,
+    previous_focus: Option<FocusHandle>,
+    trigger_bounds: Bounds<Pixels>,
+    /// The mouse position the popover was opened at with a right click.
+    mouse_position: Option<Point<Pixels>>,
+    /// Whether the popover was open when the trigger was pressed, the click then only closes
+    /// it, even if the content already closed itself on the press.
+    open_on_press: bool,
+    /// The size of the content, measured after it was first laid out.
+    content_size: Option<gpui::Size<Pixels>>,
+    /// Closes the popover when the content is dismissed, or for a [`ControlledPopover`], when
+    /// the focus leaves its view.
+    _subscription: Option<Subscription>,
+}
+
+impl PopoverState {
+    fn open<M: ManagedView>(
+        &mut self,
+        content: Entity<M>,
+        mouse_position: Option<Point<Pixels>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let focus = content.focus_handle(cx);
+        self._subscription = Some(cx.subscribe_in(
+            &content,
+            window,
+            |this, _, _: &DismissEvent, window, cx| this.close(window, cx),
+        ));
+        self.previous_focus = window.focused(cx);
+        self.content = Some(content.into());
+        self.content_size = None;
+        self.mouse_position = mouse_position;
+        window.focus(&focus);
+        self.content_focus = Some(focus);
+        cx.notify();
+    }
+
+    fn close(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.content.take().is_none() {
+            return;
+        }
+        self.content_size = None;
+        self._subscription = None;
+        let previous_focus = self.previous_focus.take();
+        // Give the focus back only if the content still has it, not after a click elsewhere.
+        if let Some(focus) = self.content_focus.take() {
+            if focus.contains_focused(window, cx) {
+                if let Some(previous_focus) = previous_focus {
+                    window.focus(&previous_focus);
+                }
+            }
+        }
+        cx.notify();
+    }
+}
+
+/// Floating content anchored to a trigger element, like the menu of a dropdown.
+///
+/// Clicking the trigger builds the content and shows it next to the trigger, at the
+/// [`EdgeCorner`] set with [`Popover::anchor`]. The popover flips to the other side of the
+/// trigger and shifts to stay inside the window. It closes when the trigger is clicked again,
+/// on a click outside, on escape, or when the content emits a [`DismissEvent`].
+///
+/// The content is a [`ManagedView`] built each time the popover opens, and focused while it
+/// is shown. The trigger must not handle clicks itself. With [`Popover::right_click`] the
+/// popover opens at the mouse instead, on a right click anywhere in the trigger.
+///
+/// Call [`bind_popover_keys`] once at startup.
+#[derive(IntoElement)]
+pub struct Popover<M: 'static> {
+    id: ElementId,
+    trigger: Option<AnyElement>,
+    content: Option<Rc<dyn Fn(&mut Window, &mut App) -> Entity<M> + 'static>>,
+    anchor: EdgeCorner,
+    offset: Pixels,
+    arrow: bool,
+    right_click: bool,
+    dismiss_on_outside_click: bool,
+}
+
+/// Binds the keys of all the [`Popover`]s, whatever their content.
+pub fn bind_popover_keys(cx: &mut App) {
+    cx.bind_keys([KeyBinding::new("escape", ClosePopover, Some(CONTEXT))]);
+}
+
+impl<M: ManagedView> Popover<M> {
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            trigger: None,
+            content: None,
+            anchor: EdgeCorner::BottomLeft,
+            offset: px(4.),
+            arrow: false,
+            right_click: false,
+            dismiss_on_outside_click: true,
+        }
+    }
+
+    /// The element that opens the popover when clicked, and that it is anchored to.
+    pub fn trigger(mut self, trigger: impl IntoElement) -> Self {
+        self.trigger = Some(trigger.into_any_element());
+        self
+    }
+
+    /// Builds the content each time the popover opens.
+    pub fn content(
+        mut self,
+        content: impl Fn(&mut Window, &mut App) -> Entity<M> + 'static,
+    ) -> Self {
+        self.content = Some(Rc::new(content));
+        self
+    }
+
+    /// Sets where the popover is attached to the trigger. Default is `EdgeCorner::BottomLeft`,
+    /// below the trigger with the left edges aligned.
+    pub fn anchor(mut self, anchor: EdgeCorner) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Opens the popover on this side of the trigger, centered.
+    pub fn position(mut self, position: Position) -> Self {
+        self.anchor = match position {
+            Position::Top => EdgeCorner::Top,
+            Position::Right => EdgeCorner::Right,
+            Position::Bottom => EdgeCorner::Bottom,
+            Position::Left => EdgeCorner::Left,
+        };
+        self
+    }
+
+    /// Sets the space between the trigger and the popover, not counting the arrow.
+    /// Default is 4px.
+    pub fn offset(mut self, offset: impl Into<Pixels>) -> Self {
+        self.offset = offset.into();
+        self
+    }
+
+    /// Shows an arrow pointing at the trigger.
+    pub fn arrow(mut self, arrow: bool) -> Self {
+        self.arrow = arrow;
+        self
+    }
+
+    /// Opens the popover at the mouse on a right click in the trigger, like a context menu.
+    pub fn right_click(mut self, right_click: bool) -> Self {
+        self.right_click = right_click;
+        self
+    }
+
+    /// Whether a click outside closes the popover. Default is `true`, turn it off for
+    /// content that handles the clicks outside itself, like a menu with submenus.
+    pub fn dismiss_on_outside_click(mut self, dismiss: bool) -> Self {
+        self.dismiss_on_outside_click = dismiss;
+        self
+    }
+}
+
+fn render_arrow(placement: Placement, cx: &App) -> impl IntoElement {
+    // The triangle points up, toward a trigger above the popover.
+    let turn = match placement.side {
+        Position::Bottom => 0.,
+        Position::Left => 0.25,
+        Position::Top => 0.5,
+        Position::Right => 0.75,
+    };
+    // The base of the triangle is a quarter of the box from its bottom, this overlaps it
+    // with the border of the popover.
+    let outset = -(ARROW_SIZE * 0.75 - px(1.));
+    let along = placement.arrow - ARROW_SIZE / 2.;
+
+    div()
+        .absolute()
+        .size(ARROW_SIZE)
+        .map(|this| match placement.side {
+            Position::Bottom => this.top(outset).left(along),
+            Position::Top => this.bottom(outset).left(along),
+            Position::Right => this.left(outset).top(along),
+            Position::Left => this.right(outset).top(along),
+        })
+        .child(
+            Icon::new(IconName::Triangle)
+                .custom_size(rems_from_px(16.))
+                .color(ElevationIndex::ElevatedSurface.bg(cx))
+                .transform(Transformation::rotate(percentage(turn))),
+        )
+}
+
+impl<M: ManagedView> RenderOnce for Popover<M> {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let state = window.use_keyed_state(self.id.clone(), cx, |_, _| PopoverState::default());
+        let (content, trigger_bounds, mouse_position, content_size) = {
+            let state = state.read(cx);
+            (
+                state.content.clone(),
+                state.trigger_bounds,
+                state.mouse_position,
+                state.content_size,
+            )
+        };
+        let anchor = match mouse_position {
+            Some(position) => Bounds::new(position, gpui::Size::default()),
+            None => trigger_bounds,
+        };
+        let gap = self.offset + if self.arrow { ARROW_HEIGHT } else { px(0.) };
+        let placement =
+            content_size.map(|size| place(anchor, size, window.viewport_size(), self.anchor, gap));
+        let open = {
+            let state = state.clone();
+            let build = self.content;
+            move |mouse_position, window: &mut Window, cx: &mut App| {
+                if let Some(build) = build.as_ref() {
+                    let content = build(window, cx);
+                    state.update(cx, |state, cx| {
+                        state.open(content, mouse_position, window, cx)
+                    });
+                }
+            }
+        };
+
+        div()
+            .id(self.id)
+            .relative()
+            .map(|this| {
+                if self.right_click {
+                    this.on_mouse_down(MouseButton::Right, move |event, window, cx| {
+                        cx.stop_propagation();
+                        open(Some(event.position), window, cx);
+                    })
+                } else {
+                    let state = state.clone();
+                    this.capture_any_mouse_down({
+                        let state = state.clone();
+                        move |_, _, cx| {
+                            state.update(cx, |state, _| {
+                                state.open_on_press = state.content.is_some()
+                            })
+                        }
+                    })
+                    .on_click(move |_, window, cx| {
+                        if state.read(cx).open_on_press {
+                            state.update(cx, |state, cx| state.close(window, cx));
+                        } else {
+                            open(None, window, cx);
+                        }
+                    })
+                }
+            })
+            .children(self.trigger)
+            .child(
+                canvas(
+                    {
+                        let state = state.clone();
+                        move |bounds, _, cx| {
+                            state.update(cx, |state, _| state.trigger_bounds = bounds)
+                        }
+                    },
+                    |_, _, _, _| {},
+                )
+                .absolute()
+                .size_full(),
+            )
+            .when_some(content, |this, content| {
+                let close = {
+                    let state = state.clone();
+                    move |window: &mut Window, cx: &mut App| {
+                        state.update(cx, |state, cx| state.close(window, cx))
+                    }
+                };
+                let close = Rc::new(close);
+
+                this.child(render_overlay(
+                    state,
+                    anchor,
+                    placement,
+                    div()
+                        .id("popover-content")
+                        .key_context(CONTEXT)
+                        .on_action({
+                            let close = close.clone();
+                            move |_: &ClosePopover, window, cx| close(window, cx)
+                        })
+                        .when(self.dismiss_on_outside_click, |this| {
+                            this.on_mouse_down_out(move |_, window, cx| close(window, cx))
+                        })
+                        .child(div().elevation_2(cx).child(content))
+                        .when(self.arrow, |this| {
+                            this.children(placement.map(|placement| render_arrow(placement, cx)))
+                        }),
+                ))
+            })
+    }
+}
+
+/// Shows the `content` of an open popover at its `placement`, measuring its size into `state`.
+///
+/// Until the content is measured it is laid out hidden below the `anchor`.
+fn render_overlay(
+    state: Entity<PopoverState>,
+    anchor: Bounds<Pixels>,
+    placement: Option<Placement>,
+    content: Stateful<Div>,
+) -> impl IntoElement {
+    let origin = placement.map_or(anchor.bottom_left(), |p| p.origin);
+
+    deferred(
+        anchored().position(origin).child(
+            content
+                .occlude()
+                .relative()
+                .when(placement.is_none(), |this| this.invisible())
+                .child(
+                    canvas(
+                        move |bounds, _, cx| {
+                            state.update(cx, |state, cx| {
+                                if state.content_size != Some(bounds.size) {
+                                    state.content_size = Some(bounds.size);
+                                    cx.notify();
+                                }
+                            })
+                        },
+                        |_, _, _, _| {},
+                    )
+                    .absolute()
+                    .size_full(),
+                ),
+        ),
+    )
+    .with_priority(1)
+}
+
+/// A popover opened and closed by the view rendering it, for a dropdown whose trigger keeps
+/// the focus while it is open, like the menu of a [`Select`](crate::Select).
+///
+/// The content is shown while it is set, placed like the content of a [`Popover`]. The
+/// `on_dismiss` handler is called on a click outside both the trigger and the content, and
+/// when the focus leaves the [`ControlledPopover::focus_handle`]. The view then closes the
+/// popover by no longer setting the content.
+#[derive(IntoElement)]
+pub(crate) struct ControlledPopover {
+    id: ElementId,
+    trigger: AnyElement,
+    content: Option<AnyElement>,
+    focus_handle: Option<FocusHandle>,
+    anchor: EdgeCorner,
+    offset: Pixels,
+    on_dismiss: Rc<dyn Fn(&DismissEvent, &mut Window, &mut App) + 'static>,
+}
+
+impl ControlledPopover {
+    pub fn new(
+        id: impl Into<ElementId>,
+        trigger: impl IntoElement,
+        on_dismiss: impl Fn(&DismissEvent, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            trigger: trigger.into_any_element(),
+            content: None,
+            focus_handle: None,
+            anchor: EdgeCorner::BottomLeft,
+            offset: px(4.),
+            on_dismiss: Rc::new(on_dismiss),
+        }
+    }
+
+    /// Opens the popover with this content.
+    pub fn content(mut self, content: impl IntoElement) -> Self {
+        self.content = Some(content.into_any_element());
+        self
+    }
+
+    /// Dismisses the popover when the focus leaves this handle, like on tab.
+    pub fn focus_handle(mut self, focus_handle: &FocusHandle) -> Self {
+        self.focus_handle = Some(focus_handle.clone());
+        self
+    }
+
+    /// Sets where the popover is attached to the trigger, see [`Popover::anchor`].
+    pub fn anchor(mut self, anchor: EdgeCorner) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Sets the space between the trigger and the popover. Default is 4px.
+    pub fn offset(mut self, offset: impl Into<Pixels>) -> Self {
+        self.offset = offset.into();
+        self
+    }
+}
+
+impl RenderOnce for ControlledPopover {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let state = window.use_keyed_state(self.id.clone(), cx, |_, _| PopoverState::default());
+        let open = self.content.is_some();
+        let on_dismiss = self.on_dismiss;
+        // Listens for the focus leaving only while open, and measures the content again on
+        // the next open.
+        state.update(cx, |state, cx| {
+            if !open {
+                state.content_size = None;
+                state._subscription = None;
+            } else if state._subscription.is_none() {
+                if let Some(focus_handle) = self.focus_handle.as_ref() {
+                    let on_dismiss = on_dismiss.clone();
+                    state._subscription =
+                        Some(window.on_focus_out(focus_handle, cx, move |_, window, cx| {
+                            on_dismiss(&DismissEvent, window, cx)
+                        }));
+                }
+            }
+        });
+        let (trigger_bounds, content_size) = {
+            let state = state.read(cx);
+            (state.trigger_bounds, state.content_size)
+        };
+        let placement = content_size.map(|size| {
+            place(
+                trigger_bounds,
+                size,
+                window.viewport_size(),
+                self.anchor,
+                self.offset,
+            )
+        });
+
+        div()
+            .id(self.id)
+            .relative()
+            .child(self.trigger)
+            .child(
+                canvas(
+                    {
+                        let state = state.clone();
+                        move |bounds, _, cx| {
+                            state.update(cx, |state, _| state.trigger_bounds = bounds)
+                        }
+                    },
+                    |_, _, _, _| {},
+                )
+                .absolute()
+                .size_full(),
+            )
+            .when_some(self.content, |this, content| {
+                this.child(render_overlay(
+                    state.clone(),
+                    trigger_bounds,
+                    placement,
+                    div()
+                        .id("popover-content")
+                        .on_mouse_down_out(move |event, window, cx| {
+                            // The trigger handles its own clicks, like toggling the popover.
+                            if !state.read(cx).trigger_bounds.contains(&event.position) {
+                                on_dismiss(&DismissEvent, window, cx);
+                            }
+                        })
+                        .child(content),
+                ))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::size;
+
+    use super::*;
+
+    fn bounds(x: f32, y: f32, width: f32, height: f32) -> Bounds<Pixels> {
+        Bounds::new(point(px(x), px(y)), size(px(width), px(height)))
+    }
+
+    #[test]
+    fn test_place() {
+        let viewport = size(px(800.), px(600.));
+        let anchor = bounds(100., 100., 80., 20.);
+        let content = size(px(200.), px(100.));
+
+        let placement = place(anchor, content, viewport, EdgeCorner::BottomLeft, px(4.));
+        assert_eq!(placement.side, Position::Bottom);
+        assert_eq!(placement.origin, point(px(100.), px(124.)));
+        assert_eq!(placement.arrow, px(40.));
+
+        let placement = place(anchor, content, viewport, EdgeCorner::Right, px(4.));
+        assert_eq!(placement.side, Position::Right);
+        assert_eq!(placement.origin, point(px(184.), px(60.)));
+        assert_eq!(placement.arrow, px(50.));
+
+        // No room above, flips below.
+        let placement = place(anchor, content, viewport, EdgeCorner::Top, px(4.));
+        assert_eq!(placement.side, Position::Bottom);
+        assert_eq!(placement.origin, point(px(40.), px(124.)));
+
+        // Shifts away from the left edge, the arrow still points at the anchor.
+        let anchor = bounds(10., 500., 20., 20.);
+        let placement = place(anchor, content, viewport, EdgeCorner::Bottom, px(4.));
+        assert_eq!(placement.side, Position::Top);
+        assert_eq!(placement.origin, point(px(8.), px(396.)));
+        assert_eq!(placement.arrow, px(14.));
+    }
+}
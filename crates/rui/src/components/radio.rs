@@ -2,16 +2,27 @@
 
 use std::rc::Rc;
 
+use gpui::{actions, point, BoxShadow, FocusHandle, FontWeight, KeyBinding};
+use smallvec::smallvec;
+
 use crate::prelude::*;
+use crate::Card;
+use crate::Color;
 use crate::Direction;
 use crate::Text;
 
+actions!(radio_group, [NextRadio, PreviousRadio, CheckRadio]);
+
+const CONTEXT: &str = "RadioGroup";
+
 #[derive(IntoElement)]
 pub struct Radio {
     id: ElementId,
     text: Option<Text>,
     checked: bool,
     disabled: bool,
+    invalid: bool,
+    focused: bool,
     on_click: Option<Box<dyn Fn(&bool, &mut Window, &mut App) + 'static>>,
 }
 
@@ -22,6 +33,8 @@ impl Radio {
             text: None,
             checked: false,
             disabled: false,
+            invalid: false,
+            focused: false,
             on_click: None,
         }
     }
@@ -41,6 +54,18 @@ impl Radio {
         self
     }
 
+    /// Shows the radio as invalid, like a required group without a selection.
+    pub fn invalid(mut self, invalid: bool) -> Self {
+        self.invalid = invalid;
+        self
+    }
+
+    /// Shows a focus ring around the radio, set by the [`RadioGroup`] that owns the focus.
+    pub(crate) fn focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+
     pub fn on_click(mut self, on_click: impl Fn(&bool, &mut Window, &mut App) + 'static) -> Self {
         self.on_click = Some(Box::new(on_click));
         self
@@ -52,6 +77,7 @@ impl RenderOnce for Radio {
         let inner_diameter = rems_from_px(6.);
         let outer_diameter = rems_from_px(16.);
         let border_width = rems_from_px(1.);
+        let scale = Color::Primary.scale(cx);
 
         h_flex()
             .id(self.id)
@@ -61,9 +87,7 @@ impl RenderOnce for Radio {
                 if self.disabled {
                     this.cursor_not_allowed().opacity(0.5)
                 } else {
-                    this.cursor_pointer().group_hover("", |this| {
-                        this.border_color(cx.theme().colors.element_hover)
-                    })
+                    this.cursor_pointer()
                 }
             })
             .child(
@@ -72,13 +96,28 @@ impl RenderOnce for Radio {
                     .rounded(outer_diameter / 2.)
                     .border_color(cx.theme().colors.border)
                     .border(border_width)
+                    .when(self.checked, |this| this.border_color(scale.solid()))
+                    .when(self.invalid, |this| {
+                        this.border_color(cx.theme().colors.danger)
+                    })
+                    .when(self.focused, |this| {
+                        this.shadow(smallvec![BoxShadow {
+                            color: scale.solid().opacity(0.3),
+                            offset: point(px(0.), px(0.)),
+                            blur_radius: px(0.),
+                            spread_radius: px(2.),
+                        }])
+                    })
+                    .when(!self.disabled, |this| {
+                        this.group_hover("", |this| this.border_color(scale.hover_border()))
+                    })
                     .when(self.checked, |this| {
                         this.child(
                             div()
                                 .m((outer_diameter - inner_diameter) / 2. - border_width)
                                 .size(inner_diameter)
                                 .rounded(inner_diameter / 2.)
-                                .bg(cx.theme().colors.icon_accent),
+                                .bg(scale.solid()),
                         )
                     }),
             )
@@ -112,24 +151,93 @@ impl From<String> for Radio {
     }
 }
 
+/// How the options of a [`RadioGroup`] are shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RadioGroupVariant {
+    /// A radio with its label.
+    #[default]
+    Default,
+    /// A selectable [`Card`] with a title and a description.
+    Card,
+}
+
+/// An option of a [`RadioGroup`].
+pub struct RadioOption<T> {
+    value: T,
+    label: SharedString,
+    description: Option<SharedString>,
+    disabled: bool,
+}
+
+impl<T> RadioOption<T> {
+    pub fn new(value: T, label: impl Into<SharedString>) -> Self {
+        Self {
+            value,
+            label: label.into(),
+            description: None,
+            disabled: false,
+        }
+    }
+
+    /// Sets the description, shown below the title in the card variant.
+    pub fn description(mut self, description: impl Into<SharedString>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+/// The focus handles of the options of a [`RadioGroup`], kept across renders.
+#[derive(Default)]
+struct RadioGroupState {
+    focus_handles: Vec<FocusHandle>,
+}
+
 /// A Radio group element.
+///
+/// Like a native radio group, only the selected option, or the first enabled one without a
+/// selection, is reached with tab. The arrow keys then move the focus and the selection to
+/// the next or previous enabled option, wrapping around at the ends.
+///
+/// Call [`bind_radio_keys`] once at startup to bind the keys.
 #[derive(IntoElement)]
-pub struct RadioGroup {
-    radios: Vec<Radio>,
+pub struct RadioGroup<T: 'static> {
+    id: ElementId,
+    options: Vec<RadioOption<T>>,
     direction: Direction,
-    selected_index: Option<usize>,
+    variant: RadioGroupVariant,
+    selected: Option<T>,
     disabled: bool,
-    on_change: Option<Rc<dyn Fn(&usize, &mut Window, &mut App) + 'static>>,
+    required: bool,
+    on_change: Option<Rc<dyn Fn(&T, &mut Window, &mut App) + 'static>>,
 }
 
-impl RadioGroup {
-    pub fn new() -> Self {
+/// Binds the keys of all the [`RadioGroup`]s, whatever their value type.
+pub fn bind_radio_keys(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("down", NextRadio, Some(CONTEXT)),
+        KeyBinding::new("right", NextRadio, Some(CONTEXT)),
+        KeyBinding::new("up", PreviousRadio, Some(CONTEXT)),
+        KeyBinding::new("left", PreviousRadio, Some(CONTEXT)),
+        KeyBinding::new("space", CheckRadio, Some(CONTEXT)),
+    ]);
+}
+
+impl<T: Clone + PartialEq + 'static> RadioGroup<T> {
+    pub fn new(id: impl Into<ElementId>) -> Self {
         Self {
-            on_change: None,
+            id: id.into(),
+            options: Vec::new(),
             direction: Direction::Horizontal,
-            selected_index: None,
+            variant: RadioGroupVariant::default(),
+            selected: None,
             disabled: false,
-            radios: vec![],
+            required: false,
+            on_change: None,
         }
     }
 
@@ -153,15 +261,26 @@ impl RadioGroup {
         self
     }
 
-    /// Listen to the change event.
-    pub fn on_change(mut self, handler: impl Fn(&usize, &mut Window, &mut App) + 'static) -> Self {
+    /// Sets the [`RadioGroupVariant`]. Default is `RadioGroupVariant::Default`.
+    pub fn variant(mut self, variant: RadioGroupVariant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// Shows the options as selectable cards.
+    pub fn cards(self) -> Self {
+        self.variant(RadioGroupVariant::Card)
+    }
+
+    /// Listen to the change event, with the value of the selected option.
+    pub fn on_change(mut self, handler: impl Fn(&T, &mut Window, &mut App) + 'static) -> Self {
         self.on_change = Some(Rc::new(handler));
         self
     }
 
-    /// Set the selected index.
-    pub fn selected_index(mut self, index: Option<usize>) -> Self {
-        self.selected_index = index;
+    /// Set the selected value, `None` for no selection.
+    pub fn selected(mut self, value: Option<T>) -> Self {
+        self.selected = value;
         self
     }
 
@@ -171,43 +290,178 @@ impl RadioGroup {
         self
     }
 
-    /// Add a child Radio element.
-    pub fn child(mut self, child: impl Into<Radio>) -> Self {
-        self.radios.push(child.into());
+    /// Shows the group as invalid while nothing is selected.
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
         self
     }
 
-    /// Add multiple child Radio elements.
-    pub fn children(mut self, children: impl IntoIterator<Item = impl Into<Radio>>) -> Self {
-        self.radios.extend(children.into_iter().map(Into::into));
+    /// Add an option.
+    pub fn option(mut self, option: RadioOption<T>) -> Self {
+        self.options.push(option);
+        self
+    }
+
+    /// Add multiple options.
+    pub fn options(mut self, options: impl IntoIterator<Item = RadioOption<T>>) -> Self {
+        self.options.extend(options);
         self
     }
 }
 
-impl RenderOnce for RadioGroup {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
-        let on_change = self.on_change;
-        let disabled = self.disabled;
-        let selected_index = self.selected_index;
+impl<T: Clone + PartialEq + 'static> RenderOnce for RadioGroup<T> {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let count = self.options.len();
+        let state = window.use_keyed_state(self.id.clone(), cx, |_, _| RadioGroupState::default());
+        let focus_handles = state.update(cx, |state, cx| {
+            state.focus_handles.resize_with(count, || cx.focus_handle());
+            state.focus_handles.clone()
+        });
+
+        let enabled: Rc<[bool]> = self
+            .options
+            .iter()
+            .map(|option| !self.disabled && !option.disabled)
+            .collect();
+        let values: Rc<[T]> = self
+            .options
+            .iter()
+            .map(|option| option.value.clone())
+            .collect();
+        let selected_ix = self
+            .selected
+            .as_ref()
+            .and_then(|selected| values.iter().position(|value| value == selected));
+        let tab_stop_ix = selected_ix
+            .filter(|ix| enabled[*ix])
+            .or_else(|| enabled.iter().position(|enabled| *enabled));
+        let invalid = self.required && selected_ix.is_none();
+
+        // Focuses and selects the option at `ix`.
+        let check: Rc<dyn Fn(usize, &mut Window, &mut App)> = {
+            let focus_handles = focus_handles.clone();
+            let values = values.clone();
+            let on_change = self.on_change.clone();
+            Rc::new(move |ix, window, cx| {
+                window.focus(&focus_handles[ix]);
+                if selected_ix != Some(ix) {
+                    if let Some(on_change) = on_change.as_ref() {
+                        on_change(&values[ix], window, cx);
+                    }
+                }
+            })
+        };
+        // Checks the next enabled option after the focused one, wrapping around.
+        let step = {
+            let focus_handles = focus_handles.clone();
+            let enabled = enabled.clone();
+            let check = check.clone();
+            move |forward: bool, window: &mut Window, cx: &mut App| {
+                let Some(current) = focus_handles.iter().position(|h| h.is_focused(window)) else {
+                    return;
+                };
+                let next = (1..=count)
+                    .map(|delta| {
+                        if forward {
+                            (current + delta) % count
+                        } else {
+                            (current + count - delta) % count
+                        }
+                    })
+                    .find(|ix| enabled[*ix]);
+                if let Some(next) = next {
+                    check(next, window, cx);
+                }
+            }
+        };
+        let step = Rc::new(step);
 
         let base = if self.direction == Direction::Vertical {
             v_flex()
         } else {
             h_flex().flex_wrap()
         };
+        let colors = cx.theme().colors;
 
-        base.gap_3()
-            .children(self.radios.into_iter().enumerate().map(|(index, radio)| {
-                let checked = selected_index == Some(index);
+        base.key_context(CONTEXT)
+            .on_action({
+                let step = step.clone();
+                move |_: &NextRadio, window, cx| step(true, window, cx)
+            })
+            .on_action(move |_: &PreviousRadio, window, cx| step(false, window, cx))
+            .on_action({
+                let focus_handles = focus_handles.clone();
+                let check = check.clone();
+                move |_: &CheckRadio, window, cx| {
+                    if let Some(ix) = focus_handles.iter().position(|h| h.is_focused(window)) {
+                        check(ix, window, cx);
+                    }
+                }
+            })
+            .gap_3()
+            .children(self.options.into_iter().enumerate().map(|(ix, option)| {
+                let disabled = !enabled[ix];
+                let checked = selected_ix == Some(ix);
+                let focus_handle = focus_handles[ix].clone().tab_stop(tab_stop_ix == Some(ix));
+                let focused = focus_handle.is_focused(window);
+                let radio = Radio::new(("radio", ix))
+                    .checked(checked)
+                    .disabled(disabled)
+                    .invalid(invalid)
+                    .focused(focused);
+                let check = check.clone();
 
-                radio.disabled(disabled).checked(checked).when_some(
-                    on_change.clone(),
-                    |this, on_change| {
-                        this.on_click(move |_, window, cx| {
-                            on_change(&index, window, cx);
+                match self.variant {
+                    RadioGroupVariant::Default => div()
+                        .when(!disabled, |this| this.track_focus(&focus_handle))
+                        .child(radio.text(Text::new(option.label)).when(!disabled, |this| {
+                            this.on_click(move |_, window, cx| check(ix, window, cx))
+                        }))
+                        .into_any_element(),
+                    RadioGroupVariant::Card => div()
+                        .id(("radio-card", ix))
+                        .map(|this| {
+                            if disabled {
+                                this.cursor_not_allowed().opacity(0.5)
+                            } else {
+                                this.cursor_pointer()
+                                    .track_focus(&focus_handle)
+                                    .on_click(move |_, window, cx| check(ix, window, cx))
+                            }
                         })
-                    },
-                )
+                        .child(
+                            Card::new()
+                                .direction_horizontal()
+                                .margin(0.)
+                                .padding(12.)
+                                .gap_3()
+                                .items_start()
+                                .border_color(if checked {
+                                    colors.primary
+                                } else if invalid {
+                                    colors.danger
+                                } else {
+                                    colors.border_variant
+                                })
+                                .when(checked, |this| this.bg(colors.primary.opacity(0.05)))
+                                .child(radio)
+                                .child(
+                                    v_flex()
+                                        .gap_1()
+                                        .child(
+                                            Text::new(option.label).font_weight(FontWeight::MEDIUM),
+                                        )
+                                        .when_some(option.description, |this, description| {
+                                            this.child(
+                                                Text::new(description)
+                                                    .text_sm()
+                                                    .color(colors.text_muted),
+                                            )
+                                        }),
+                                ),
+                        )
+                        .into_any_element(),
+                }
             }))
     }
 }
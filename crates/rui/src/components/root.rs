@@ -1,21 +1,48 @@
-use crate::{prelude::*, ModalLayer, ModalView, ToastLayer, ToastView};
-use gpui::{AnyView, Entity};
+use std::path::PathBuf;
 
+use crate::{
+    prelude::*, IconName, ModalLayer, ModalView, StatusToast, ThemeEvent, ThemeSetting,
+    ThemeWatcher, ThemeWatcherEvent, ToastIcon, ToastLayer, ToastPosition, ToastView,
+};
+use gpui::{AnyView, Entity, EventEmitter, Subscription, WeakEntity};
+
+/// The root view of a window, hosting the modal and toast layers.
+///
+/// While in [`ThemeMode::System`](crate::ThemeMode::System), the theme follows
+/// the appearance of the window and [`ThemeEvent::AppearanceChanged`] is emitted.
 pub struct Root {
     toast_layer: Entity<ToastLayer>,
     modal_layer: Entity<ModalLayer>,
     view: AnyView,
+    _appearance_subscription: Subscription,
+    theme_watcher: Option<(Entity<ThemeWatcher>, Subscription)>,
+    theme_error_toast: Option<WeakEntity<StatusToast>>,
 }
 
 impl Root {
-    pub fn new(cx: &mut App, view: AnyView) -> Self {
+    pub fn new(view: AnyView, window: &mut Window, cx: &mut Context<Self>) -> Self {
         let toast_layer = cx.new(|_| ToastLayer::new());
         let modal_layer = cx.new(|_| ModalLayer::new());
 
+        // Sync the theme with an appearance the window already had.
+        Self::follow_system_appearance(window, cx);
+        let appearance_subscription = cx.observe_window_appearance(window, |_, window, cx| {
+            Self::follow_system_appearance(window, cx);
+        });
+
         Self {
             toast_layer,
             modal_layer,
             view,
+            _appearance_subscription: appearance_subscription,
+            theme_watcher: None,
+            theme_error_toast: None,
+        }
+    }
+
+    fn follow_system_appearance(window: &Window, cx: &mut Context<Self>) {
+        if let Some(appearance) = ThemeSetting::follow_system_appearance(window, cx) {
+            cx.emit(ThemeEvent::AppearanceChanged(appearance));
         }
     }
 
@@ -28,14 +55,81 @@ impl Root {
         })
     }
 
+    /// Opens a modal on top of the open ones, see [`ModalLayer::push_modal`].
+    pub fn push_modal<V: ModalView, B>(&mut self, window: &mut Window, cx: &mut App, build: B)
+    where
+        B: FnOnce(&mut Window, &mut Context<V>) -> V,
+    {
+        self.modal_layer.update(cx, |modal_layer, cx| {
+            modal_layer.push_modal(window, cx, build)
+        })
+    }
+
     pub fn toggle_status_toast<V: ToastView>(&mut self, entity: Entity<V>, cx: &mut App) {
         self.toast_layer
             .update(cx, |toast_layer, cx| toast_layer.toggle_toast(cx, entity))
     }
+
+    /// Queues a toast, see [`ToastLayer::show_toast`].
+    pub fn show_toast<V: ToastView>(&mut self, entity: Entity<V>, cx: &mut App) {
+        self.toast_layer
+            .update(cx, |toast_layer, cx| toast_layer.show_toast(entity, cx))
+    }
+
+    pub fn set_toast_position(&mut self, position: ToastPosition, cx: &mut App) {
+        self.toast_layer
+            .update(cx, |toast_layer, cx| toast_layer.set_position(position, cx))
+    }
+
+    /// Sets how many toasts are shown at once, see [`ToastLayer::set_max_visible`].
+    pub fn set_max_visible_toasts(&mut self, max_visible: usize, cx: &mut App) {
+        self.toast_layer.update(cx, |toast_layer, cx| {
+            toast_layer.set_max_visible(max_visible, cx)
+        })
+    }
+
+    /// Reloads the theme file at `path` whenever it changes, see [`ThemeWatcher`].
+    ///
+    /// If the changed file can't be loaded, the previous theme is kept and the error is shown
+    /// as a toast. Replaces the file watched before, if any.
+    pub fn watch_theme_file(&mut self, path: impl Into<PathBuf>, cx: &mut Context<Self>) {
+        let watcher = cx.new(|cx| ThemeWatcher::new(path, cx));
+        let subscription = cx.subscribe(&watcher, |this, _, event, cx| match event {
+            ThemeWatcherEvent::Reloaded(_) => this.hide_theme_error_toast(cx),
+            ThemeWatcherEvent::Failed(error) => {
+                // Only the last error is relevant, repeated failed saves don't pile up toasts.
+                this.hide_theme_error_toast(cx);
+                let color = cx.theme().colors.danger;
+                let toast =
+                    StatusToast::new(format!("Failed to reload theme: {error}"), cx, |this, _| {
+                        this.icon(ToastIcon::new(IconName::Warning).color(color))
+                    });
+                this.theme_error_toast = Some(toast.downgrade());
+                this.toast_layer
+                    .update(cx, |toast_layer, cx| toast_layer.show_toast(toast, cx));
+            }
+        });
+        self.theme_watcher = Some((watcher, subscription));
+    }
+
+    fn hide_theme_error_toast(&mut self, cx: &mut App) {
+        if let Some(toast) = self.theme_error_toast.take() {
+            self.toast_layer.update(cx, |toast_layer, cx| {
+                toast_layer.hide_toast_with_id(toast.entity_id(), cx);
+            });
+        }
+    }
+
+    /// Stops watching the theme file, see [`Root::watch_theme_file`].
+    pub fn unwatch_theme_file(&mut self) {
+        self.theme_watcher = None;
+    }
 }
 
+impl EventEmitter<ThemeEvent> for Root {}
+
 impl Render for Root {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         div()
             .flex()
             .flex_col()
@@ -1,5 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 /// Scrollbar show mode.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ScrollbarShow {
     #[default]
     Scrolling,
@@ -0,0 +1,716 @@
+use std::rc::Rc;
+
+use gpui::{
+    actions, AnyElement, App, Context, DismissEvent, ElementId, EventEmitter, FocusHandle,
+    Focusable, ImageSource, KeyBinding, KeyDownEvent, Render, ScrollHandle, Window,
+};
+
+use crate::{
+    prelude::*, Avatar, Button, ButtonVariant, Color, ControlledPopover, Divider, Icon, IconName,
+    IconSize, Text, Typeahead,
+};
+
+actions!(
+    select,
+    [
+        SelectPrevious,
+        SelectNext,
+        SelectFirst,
+        SelectLast,
+        Confirm,
+        Cancel
+    ]
+);
+
+const CONTEXT: &str = "Select";
+
+type RenderFn = Rc<dyn Fn(&mut Window, &mut App) -> AnyElement>;
+
+/// An option of a [`Select`].
+pub struct SelectOption<T> {
+    value: T,
+    label: SharedString,
+    disabled: bool,
+    prefix: Option<RenderFn>,
+    render: Option<RenderFn>,
+}
+
+impl<T> SelectOption<T> {
+    pub fn new(value: T, label: impl Into<SharedString>) -> Self {
+        Self {
+            value,
+            label: label.into(),
+            disabled: false,
+            prefix: None,
+            render: None,
+        }
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Shows an icon before the label.
+    pub fn icon(mut self, icon: impl Into<Icon>) -> Self {
+        let icon = icon.into().size(IconSize::Small);
+        self.prefix = Some(Rc::new(move |_, _| icon.clone().into_any_element()));
+        self
+    }
+
+    /// Shows an avatar before the label.
+    pub fn avatar(mut self, src: impl Into<ImageSource>) -> Self {
+        let src = src.into();
+        self.prefix = Some(Rc::new(move |_, _| {
+            Avatar::new(src.clone()).size(px(20.)).into_any_element()
+        }));
+        self
+    }
+
+    /// Renders the content of the option instead of its label.
+    ///
+    /// The label is still shown in the trigger and used by the type-ahead.
+    pub fn render(
+        mut self,
+        render: impl Fn(&mut Window, &mut App) -> AnyElement + 'static,
+    ) -> Self {
+        self.render = Some(Rc::new(render));
+        self
+    }
+
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    pub fn label(&self) -> &SharedString {
+        &self.label
+    }
+}
+
+/// A group of options of a [`Select`], separated from the other groups by a [`Divider`].
+pub struct SelectGroup<T> {
+    label: Option<SharedString>,
+    options: Vec<SelectOption<T>>,
+}
+
+impl<T> SelectGroup<T> {
+    pub fn new(label: impl Into<SharedString>) -> Self {
+        Self {
+            label: Some(label.into()),
+            options: Vec::new(),
+        }
+    }
+
+    pub fn option(mut self, option: SelectOption<T>) -> Self {
+        self.options.push(option);
+        self
+    }
+
+    pub fn options(mut self, options: impl IntoIterator<Item = SelectOption<T>>) -> Self {
+        self.options.extend(options);
+        self
+    }
+}
+
+/// Events emitted by a [`Select`].
+#[derive(Debug, Clone)]
+pub enum SelectEvent<T> {
+    /// An option was selected.
+    Change(T),
+}
+
+/// A dropdown to pick one value out of a list of options.
+///
+/// The trigger is a [`Button`] and the options open in a popover below it, which closes on a
+/// click outside or when the focus leaves the select. When focused, the arrow keys move
+/// through the options, enter selects, escape closes and typing jumps to the first option
+/// starting with the typed text.
+///
+/// Call [`bind_select_keys`] once at startup to bind the navigation keys.
+pub struct Select<T> {
+    id: ElementId,
+    focus_handle: FocusHandle,
+    groups: Vec<SelectGroup<T>>,
+    selected: Option<T>,
+    /// The index of the highlighted option, in all the options.
+    highlighted: Option<usize>,
+    open: bool,
+    placeholder: SharedString,
+    variant: ButtonVariant,
+    color: Color,
+    size: Size,
+    disabled: bool,
+    on_change: Option<Box<dyn Fn(&T, &mut Window, &mut App) + 'static>>,
+    scroll_handle: ScrollHandle,
+    typeahead: Typeahead,
+}
+
+impl<T: 'static> EventEmitter<SelectEvent<T>> for Select<T> {}
+
+impl<T> Focusable for Select<T> {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+/// Binds the navigation keys of all the [`Select`]s, whatever their value type.
+pub fn bind_select_keys(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("up", SelectPrevious, Some(CONTEXT)),
+        KeyBinding::new("down", SelectNext, Some(CONTEXT)),
+        KeyBinding::new("home", SelectFirst, Some(CONTEXT)),
+        KeyBinding::new("end", SelectLast, Some(CONTEXT)),
+        KeyBinding::new("enter", Confirm, Some(CONTEXT)),
+        KeyBinding::new("space", Confirm, Some(CONTEXT)),
+        KeyBinding::new("escape", Cancel, Some(CONTEXT)),
+    ]);
+}
+
+impl<T: Clone + PartialEq + 'static> Select<T> {
+    pub fn new(id: impl Into<ElementId>, cx: &mut App) -> Self {
+        Self {
+            id: id.into(),
+            focus_handle: cx.focus_handle(),
+            groups: Vec::new(),
+            selected: None,
+            highlighted: None,
+            open: false,
+            placeholder: "Select...".into(),
+            variant: ButtonVariant::Outline,
+            color: Color::Default,
+            size: Size::default(),
+            disabled: false,
+            on_change: None,
+            scroll_handle: ScrollHandle::new(),
+            typeahead: Typeahead::default(),
+        }
+    }
+
+    /// Adds an option outside of any group.
+    pub fn option(mut self, option: SelectOption<T>) -> Self {
+        match self.groups.last_mut() {
+            Some(group) if group.label.is_none() => group.options.push(option),
+            _ => self.groups.push(SelectGroup {
+                label: None,
+                options: vec![option],
+            }),
+        }
+        self
+    }
+
+    pub fn options(self, options: impl IntoIterator<Item = SelectOption<T>>) -> Self {
+        options.into_iter().fold(self, Self::option)
+    }
+
+    pub fn group(mut self, group: SelectGroup<T>) -> Self {
+        self.groups.push(group);
+        self
+    }
+
+    /// Sets the initially selected value.
+    pub fn selected(mut self, value: T) -> Self {
+        self.selected = Some(value);
+        self
+    }
+
+    /// Sets the text shown while nothing is selected, default: "Select...".
+    pub fn placeholder(mut self, placeholder: impl Into<SharedString>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Sets the [`ButtonVariant`] of the trigger, default: [`ButtonVariant::Outline`].
+    pub fn variant(mut self, variant: ButtonVariant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// Sets the [`Color`] of the trigger.
+    pub fn color(mut self, color: impl Into<Color>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    pub fn size(mut self, size: Size) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Called with the value of the option selected by the user.
+    pub fn on_change(mut self, handler: impl Fn(&T, &mut Window, &mut App) + 'static) -> Self {
+        self.on_change = Some(Box::new(handler));
+        self
+    }
+
+    pub fn selected_value(&self) -> Option<&T> {
+        self.selected.as_ref()
+    }
+
+    /// Selects `value`, without calling `on_change`.
+    pub fn set_selected(&mut self, value: Option<T>, cx: &mut Context<Self>) {
+        self.selected = value;
+        cx.notify();
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn all_options(&self) -> impl Iterator<Item = &SelectOption<T>> {
+        self.groups.iter().flat_map(|group| group.options.iter())
+    }
+
+    fn option_at(&self, ix: usize) -> Option<&SelectOption<T>> {
+        self.all_options().nth(ix)
+    }
+
+    fn selected_ix(&self) -> Option<usize> {
+        let selected = self.selected.as_ref()?;
+        self.all_options()
+            .position(|option| &option.value == selected)
+    }
+
+    /// Returns the enabled option after `from` in `direction`, or the first one in
+    /// `direction` if `from` is `None`.
+    fn next_enabled(&self, from: Option<usize>, forward: bool) -> Option<usize> {
+        let enabled: Vec<usize> = self
+            .all_options()
+            .enumerate()
+            .filter(|(_, option)| !option.disabled)
+            .map(|(ix, _)| ix)
+            .collect();
+        match (from, forward) {
+            (None, true) => enabled.first().copied(),
+            (None, false) => enabled.last().copied(),
+            (Some(from), true) => enabled.iter().copied().find(|ix| *ix > from).or(Some(from)),
+            (Some(from), false) => enabled
+                .iter()
+                .rev()
+                .copied()
+                .find(|ix| *ix < from)
+                .or(Some(from)),
+        }
+    }
+
+    fn highlight(&mut self, ix: Option<usize>, cx: &mut Context<Self>) {
+        self.highlighted = ix;
+        if let Some(ix) = ix {
+            self.scroll_handle.scroll_to_item(self.child_ix(ix));
+        }
+        cx.notify();
+    }
+
+    /// The index of the menu child showing the option at `ix`, after the group labels and
+    /// dividers before it.
+    fn child_ix(&self, ix: usize) -> usize {
+        let mut child_ix = 0;
+        let mut option_ix = 0;
+        for (group_ix, group) in self.groups.iter().enumerate() {
+            child_ix += (group_ix > 0) as usize + group.label.is_some() as usize;
+            if ix < option_ix + group.options.len() {
+                return child_ix + ix - option_ix;
+            }
+            child_ix += group.options.len();
+            option_ix += group.options.len();
+        }
+        child_ix
+    }
+
+    fn open(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.disabled {
+            return;
+        }
+        self.open = true;
+        window.focus(&self.focus_handle);
+        let highlighted = self.selected_ix().or_else(|| self.next_enabled(None, true));
+        self.highlight(highlighted, cx);
+    }
+
+    fn close(&mut self, cx: &mut Context<Self>) {
+        self.open = false;
+        self.highlighted = None;
+        cx.notify();
+    }
+
+    fn toggle(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.open {
+            self.close(cx);
+        } else {
+            self.open(window, cx);
+        }
+    }
+
+    fn select(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(option) = self.option_at(ix).filter(|option| !option.disabled) else {
+            return;
+        };
+        let value = option.value.clone();
+        self.close(cx);
+        if self.selected.as_ref() == Some(&value) {
+            return;
+        }
+        self.selected = Some(value.clone());
+        if let Some(on_change) = self.on_change.as_ref() {
+            on_change(&value, window, cx);
+        }
+        cx.emit(SelectEvent::Change(value));
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, window: &mut Window, cx: &mut Context<Self>) {
+        if self.open {
+            let ix = self.next_enabled(self.highlighted, false);
+            self.highlight(ix, cx);
+        } else {
+            self.open(window, cx);
+        }
+    }
+
+    fn select_next(&mut self, _: &SelectNext, window: &mut Window, cx: &mut Context<Self>) {
+        if self.open {
+            let ix = self.next_enabled(self.highlighted, true);
+            self.highlight(ix, cx);
+        } else {
+            self.open(window, cx);
+        }
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        if self.open {
+            let ix = self.next_enabled(None, true);
+            self.highlight(ix, cx);
+        }
+    }
+
+    fn select_last(&mut self, _: &SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        if self.open {
+            let ix = self.next_enabled(None, false);
+            self.highlight(ix, cx);
+        }
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        match self.highlighted.filter(|_| self.open) {
+            Some(ix) => self.select(ix, window, cx),
+            None => self.toggle(window, cx),
+        }
+    }
+
+    fn cancel(&mut self, _: &Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        if self.open {
+            self.close(cx);
+        } else {
+            cx.propagate();
+        }
+    }
+
+    /// Jumps to the first enabled option whose label starts with the typed text, selecting it
+    /// when the menu is closed.
+    fn on_key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        let modifiers = &event.keystroke.modifiers;
+        if modifiers.control || modifiers.alt || modifiers.platform {
+            return;
+        }
+        let Some(key_char) = event.keystroke.key_char.as_ref() else {
+            return;
+        };
+        let current = if self.open {
+            self.highlighted
+        } else {
+            self.selected_ix()
+        };
+        let labels = self
+            .groups
+            .iter()
+            .flat_map(|group| group.options.iter())
+            .map(|option| (!option.disabled).then_some(option.label.as_ref()));
+        let next = self.typeahead.type_char(key_char, labels, current);
+        let Some(next) = next else {
+            return;
+        };
+        cx.stop_propagation();
+        if self.open {
+            self.highlight(Some(next), cx);
+        } else {
+            self.select(next, window, cx);
+        }
+    }
+
+    fn render_option(
+        &self,
+        ix: usize,
+        option: &SelectOption<T>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let colors = cx.theme().colors;
+        let selected = self.selected.as_ref() == Some(&option.value);
+        let highlighted = self.highlighted == Some(ix);
+
+        h_flex()
+            .id(ix)
+            .w_full()
+            .gap_2()
+            .px_2()
+            .py_1()
+            .rounded(cx.theme().styles.radius)
+            .when(highlighted, |this| this.bg(colors.element_hover))
+            .map(|this| {
+                if option.disabled {
+                    this.opacity(0.5).cursor_not_allowed()
+                } else {
+                    this.cursor_pointer()
+                        .on_mouse_move(cx.listener(move |this, _, _, cx| {
+                            if this.highlighted != Some(ix) {
+                                this.highlighted = Some(ix);
+                                cx.notify();
+                            }
+                        }))
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            this.select(ix, window, cx);
+                        }))
+                }
+            })
+            .when_some(option.prefix.as_ref(), |this, prefix| {
+                this.child(prefix(window, cx))
+            })
+            .child(div().flex_1().map(|this| match option.render.as_ref() {
+                Some(render) => this.child(render(window, cx)),
+                None => this.child(Text::new(option.label.clone()).truncate()),
+            }))
+            .when(selected, |this| {
+                this.child(
+                    Icon::new(IconName::Check)
+                        .size(IconSize::Small)
+                        .color(colors.primary),
+                )
+            })
+    }
+
+    fn render_menu(&self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let mut children: Vec<AnyElement> = Vec::new();
+        let mut ix = 0;
+        for (group_ix, group) in self.groups.iter().enumerate() {
+            if group_ix > 0 {
+                children.push(Divider::new().my_1().into_any_element());
+            }
+            if let Some(label) = group.label.clone() {
+                children.push(
+                    div()
+                        .px_2()
+                        .py_1()
+                        .text_xs()
+                        .text_color(cx.theme().colors.text_muted)
+                        .child(label)
+                        .into_any_element(),
+                );
+            }
+            for option in group.options.iter() {
+                children.push(
+                    self.render_option(ix, option, window, cx)
+                        .into_any_element(),
+                );
+                ix += 1;
+            }
+        }
+
+        v_flex()
+            .id("select-menu")
+            .min_w(px(160.))
+            .max_h(px(280.))
+            .overflow_y_scroll()
+            .track_scroll(&self.scroll_handle)
+            .p_1()
+            .elevation_2(cx)
+            .text_color(cx.theme().colors.text)
+            .children(children)
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> Render for Select<T> {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let label = self
+            .selected_ix()
+            .and_then(|ix| self.option_at(ix))
+            .map_or(self.placeholder.clone(), |option| option.label.clone());
+
+        v_flex()
+            .key_context(CONTEXT)
+            .when(!self.disabled, |this| {
+                this.track_focus(&self.focus_handle)
+                    .on_action(cx.listener(Self::select_previous))
+                    .on_action(cx.listener(Self::select_next))
+                    .on_action(cx.listener(Self::select_first))
+                    .on_action(cx.listener(Self::select_last))
+                    .on_action(cx.listener(Self::confirm))
+                    .on_action(cx.listener(Self::cancel))
+                    .on_key_down(cx.listener(Self::on_key_down))
+            })
+            .child(
+                ControlledPopover::new(
+                    "select-popover",
+                    Button::new(self.id.clone())
+                        .text(label)
+                        .icon(IconName::ChevronDown)
+                        .icon_right()
+                        .variant(self.variant)
+                        .color(self.color)
+                        .size(self.size)
+                        .disabled(self.disabled)
+                        .on_click(cx.listener(|this, _, window, cx| this.toggle(window, cx))),
+                    cx.listener(|this, _: &DismissEvent, _, cx| this.close(cx)),
+                )
+                .focus_handle(&self.focus_handle)
+                .when(self.open, |this| this.content(self.render_menu(window, cx))),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use gpui::{TestAppContext, VisualTestContext};
+
+    use super::*;
+    use crate::Theme;
+
+    /// Apple, Banana (disabled), Cherry | Avocado, Carrot
+    fn build_select(cx: &mut App) -> Select<&'static str> {
+        Select::new("select", cx)
+            .group(SelectGroup::new("Fruits").options([
+                SelectOption::new("apple", "Apple"),
+                SelectOption::new("banana", "Banana").disabled(true),
+                SelectOption::new("cherry", "Cherry"),
+            ]))
+            .group(SelectGroup::new("Vegetables").options([
+                SelectOption::new("avocado", "Avocado"),
+                SelectOption::new("carrot", "Carrot"),
+            ]))
+    }
+
+    fn add_select(
+        cx: &mut TestAppContext,
+    ) -> (
+        Entity<Select<&'static str>>,
+        Rc<RefCell<Vec<&'static str>>>,
+        &mut VisualTestContext,
+    ) {
+        cx.update(|cx| {
+            Theme::init(cx, None, None);
+            bind_select_keys(cx);
+        });
+        let (select, cx) = cx.add_window_view(|_, cx| build_select(cx));
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        cx.update(|window, cx| {
+            window.focus(&select.focus_handle(cx));
+            let changes = changes.clone();
+            cx.subscribe(&select, move |_, event: &SelectEvent<_>, _| {
+                let SelectEvent::Change(value) = event;
+                changes.borrow_mut().push(*value);
+            })
+            .detach();
+        });
+        (select, changes, cx)
+    }
+
+    fn highlighted(
+        select: &Entity<Select<&'static str>>,
+        cx: &mut VisualTestContext,
+    ) -> Option<usize> {
+        cx.read(|cx| select.read(cx).highlighted)
+    }
+
+    fn selected(
+        select: &Entity<Select<&'static str>>,
+        cx: &mut VisualTestContext,
+    ) -> Option<&'static str> {
+        cx.read(|cx| select.read(cx).selected_value().copied())
+    }
+
+    #[gpui::test]
+    fn test_next_enabled(cx: &mut TestAppContext) {
+        let select = cx.update(build_select);
+
+        assert_eq!(select.next_enabled(None, true), Some(0));
+        assert_eq!(select.next_enabled(None, false), Some(4));
+        // The disabled option is skipped, and groups are crossed.
+        assert_eq!(select.next_enabled(Some(0), true), Some(2));
+        assert_eq!(select.next_enabled(Some(2), true), Some(3));
+        assert_eq!(select.next_enabled(Some(2), false), Some(0));
+        // The ends are kept.
+        assert_eq!(select.next_enabled(Some(4), true), Some(4));
+        assert_eq!(select.next_enabled(Some(0), false), Some(0));
+
+        // The group labels and the divider between the groups are children of the menu.
+        assert_eq!(select.child_ix(0), 1);
+        assert_eq!(select.child_ix(2), 3);
+        assert_eq!(select.child_ix(3), 6);
+    }
+
+    #[gpui::test]
+    fn test_navigation(cx: &mut TestAppContext) {
+        let (select, changes, cx) = add_select(cx);
+
+        cx.simulate_keystrokes("down");
+        cx.read(|cx| assert!(select.read(cx).is_open()));
+        assert_eq!(highlighted(&select, cx), Some(0));
+
+        cx.simulate_keystrokes("down");
+        assert_eq!(highlighted(&select, cx), Some(2));
+        cx.simulate_keystrokes("down");
+        assert_eq!(highlighted(&select, cx), Some(3));
+        cx.simulate_keystrokes("up up");
+        assert_eq!(highlighted(&select, cx), Some(0));
+        cx.simulate_keystrokes("end");
+        assert_eq!(highlighted(&select, cx), Some(4));
+
+        cx.simulate_keystrokes("enter");
+        cx.read(|cx| assert!(!select.read(cx).is_open()));
+        assert_eq!(selected(&select, cx), Some("carrot"));
+        assert_eq!(*changes.borrow(), ["carrot"]);
+
+        // Opening again highlights the selected option.
+        cx.simulate_keystrokes("up");
+        assert_eq!(highlighted(&select, cx), Some(4));
+        cx.simulate_keystrokes("home escape");
+        cx.read(|cx| assert!(!select.read(cx).is_open()));
+        assert_eq!(selected(&select, cx), Some("carrot"));
+        assert_eq!(changes.borrow().len(), 1);
+    }
+
+    #[gpui::test]
+    fn test_typeahead_selects_when_closed(cx: &mut TestAppContext) {
+        let (select, changes, cx) = add_select(cx);
+
+        cx.simulate_keystrokes("c");
+        cx.read(|cx| assert!(!select.read(cx).is_open()));
+        assert_eq!(selected(&select, cx), Some("cherry"));
+
+        // Repeating the char cycles through the options starting with it.
+        cx.simulate_keystrokes("c");
+        assert_eq!(selected(&select, cx), Some("carrot"));
+        assert_eq!(*changes.borrow(), ["cherry", "carrot"]);
+    }
+
+    #[gpui::test]
+    fn test_typeahead_highlights_when_open(cx: &mut TestAppContext) {
+        let (select, changes, cx) = add_select(cx);
+
+        // The disabled option can't be reached.
+        cx.simulate_keystrokes("down b");
+        assert_eq!(highlighted(&select, cx), Some(0));
+
+        select.update(cx, |select, _| select.typeahead = Typeahead::default());
+        cx.simulate_keystrokes("a");
+        assert_eq!(highlighted(&select, cx), Some(3));
+
+        cx.read(|cx| assert!(select.read(cx).is_open()));
+        assert_eq!(selected(&select, cx), None);
+        assert!(changes.borrow().is_empty());
+    }
+}
@@ -0,0 +1,840 @@
+use gpui::{
+    actions, anchored, canvas, deferred, point, relative, Bounds, BoxShadow, Context, Corner,
+    DispatchPhase, Entity, EventEmitter, FocusHandle, Focusable, KeyBinding, MouseButton,
+    MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels, Point, Render, Window,
+};
+
+use smallvec::smallvec;
+
+use crate::{prelude::*, tooltip_container, Direction, ElevationIndex};
+
+actions!(
+    slider,
+    [
+        Increase,
+        Decrease,
+        IncreasePage,
+        DecreasePage,
+        SetMinimum,
+        SetMaximum
+    ]
+);
+
+const CONTEXT: &str = "Slider";
+
+/// A page key moves the value by this many steps.
+const PAGE_STEPS: f32 = 10.;
+/// Without a step, the keys move the value by this fraction of the range.
+const CONTINUOUS_STEP: f32 = 0.01;
+
+/// The value of a [`Slider`], one value or a range with two thumbs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SliderValue {
+    Single(f32),
+    Range(f32, f32),
+}
+
+impl SliderValue {
+    fn thumbs(self) -> [f32; 2] {
+        match self {
+            Self::Single(value) => [value, value],
+            Self::Range(start, end) => [start.min(end), start.max(end)],
+        }
+    }
+}
+
+/// Events emitted by a [`Slider`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SliderEvent {
+    /// The user moved a thumb.
+    Change(SliderValue),
+}
+
+struct SliderTick {
+    value: f32,
+    label: Option<SharedString>,
+}
+
+/// A slider to pick a value, or a range of values with two thumbs, between a min and a max.
+///
+/// The thumbs are moved by dragging them or clicking the track, or with the arrow, page,
+/// home and end keys once focused. The value is shown in a tooltip above the thumb while it
+/// is hovered or dragged.
+///
+/// Call [`Slider::bind_keys`] once at startup to bind the keys.
+pub struct Slider {
+    focus_handles: [FocusHandle; 2],
+    /// The value of each thumb, only the second is used for a single value.
+    values: [f32; 2],
+    range: bool,
+    min: f32,
+    max: f32,
+    step: Option<f32>,
+    direction: Direction,
+    size: Size,
+    disabled: bool,
+    ticks: Vec<SliderTick>,
+    format: Option<Box<dyn Fn(f32) -> SharedString + 'static>>,
+    on_change: Option<Box<dyn Fn(&SliderValue, &mut Window, &mut App) + 'static>>,
+    dragging: Option<usize>,
+    hovered: Option<usize>,
+    track_bounds: Bounds<Pixels>,
+}
+
+impl EventEmitter<SliderEvent> for Slider {}
+
+impl Focusable for Slider {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handles[self.thumb_ixs().start].clone()
+    }
+}
+
+impl Slider {
+    pub fn new(cx: &mut App) -> Self {
+        Self {
+            focus_handles: [cx.focus_handle(), cx.focus_handle()],
+            values: [0., 0.],
+            range: false,
+            min: 0.,
+            max: 100.,
+            step: None,
+            direction: Direction::Horizontal,
+            size: Size::default(),
+            disabled: false,
+            ticks: Vec::new(),
+            format: None,
+            on_change: None,
+            dragging: None,
+            hovered: None,
+            track_bounds: Bounds::default(),
+        }
+    }
+
+    pub fn bind_keys(cx: &mut App) {
+        cx.bind_keys([
+            KeyBinding::new("right", Increase, Some(CONTEXT)),
+            KeyBinding::new("up", Increase, Some(CONTEXT)),
+            KeyBinding::new("left", Decrease, Some(CONTEXT)),
+            KeyBinding::new("down", Decrease, Some(CONTEXT)),
+            KeyBinding::new("pageup", IncreasePage, Some(CONTEXT)),
+            KeyBinding::new("pagedown", DecreasePage, Some(CONTEXT)),
+            KeyBinding::new("home", SetMinimum, Some(CONTEXT)),
+            KeyBinding::new("end", SetMaximum, Some(CONTEXT)),
+        ]);
+    }
+
+    /// Sets a single value.
+    pub fn value(mut self, value: f32) -> Self {
+        self.range = false;
+        self.values = [value, value];
+        self.clamp_values();
+        self
+    }
+
+    /// Sets a range of values, shown with two thumbs.
+    pub fn range(mut self, start: f32, end: f32) -> Self {
+        self.range = true;
+        self.values = SliderValue::Range(start, end).thumbs();
+        self.clamp_values();
+        self
+    }
+
+    /// Sets the min value, default: 0.
+    pub fn min(mut self, min: f32) -> Self {
+        self.min = min;
+        self.clamp_values();
+        self
+    }
+
+    /// Sets the max value, default: 100.
+    pub fn max(mut self, max: f32) -> Self {
+        self.max = max;
+        self.clamp_values();
+        self
+    }
+
+    /// Snaps the values to multiples of `step` from the min, by default values are continuous.
+    pub fn step(mut self, step: f32) -> Self {
+        self.step = Some(step).filter(|step| *step > 0.);
+        self.clamp_values();
+        self
+    }
+
+    /// Sets the [`Direction`], default: [`Direction::Horizontal`].
+    ///
+    /// A vertical slider fills the height of its parent, with the min at the bottom.
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    pub fn vertical(self) -> Self {
+        self.direction(Direction::Vertical)
+    }
+
+    pub fn size(mut self, size: Size) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Adds tick marks at `values`.
+    pub fn ticks(mut self, values: impl IntoIterator<Item = f32>) -> Self {
+        self.ticks.extend(
+            values
+                .into_iter()
+                .map(|value| SliderTick { value, label: None }),
+        );
+        self
+    }
+
+    /// Adds a tick mark at `value`, with a label below it.
+    pub fn tick_label(mut self, value: f32, label: impl Into<SharedString>) -> Self {
+        self.ticks.push(SliderTick {
+            value,
+            label: Some(label.into()),
+        });
+        self
+    }
+
+    /// Formats the values in the tooltip, by default with the decimals of the step.
+    pub fn format(mut self, format: impl Fn(f32) -> SharedString + 'static) -> Self {
+        self.format = Some(Box::new(format));
+        self
+    }
+
+    /// Called with the new value when the user moves a thumb.
+    pub fn on_change(
+        mut self,
+        handler: impl Fn(&SliderValue, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_change = Some(Box::new(handler));
+        self
+    }
+
+    pub fn get_value(&self) -> SliderValue {
+        if self.range {
+            SliderValue::Range(self.values[0], self.values[1])
+        } else {
+            SliderValue::Single(self.values[1])
+        }
+    }
+
+    /// Sets the value, without calling `on_change`.
+    pub fn set_value(&mut self, value: SliderValue, cx: &mut Context<Self>) {
+        self.range = matches!(value, SliderValue::Range(..));
+        self.values = value.thumbs();
+        self.clamp_values();
+        cx.notify();
+    }
+
+    pub fn set_disabled(&mut self, disabled: bool, cx: &mut Context<Self>) {
+        self.disabled = disabled;
+        cx.notify();
+    }
+
+    fn clamp_values(&mut self) {
+        for value in &mut self.values {
+            *value = snap(*value, self.min, self.max, self.step);
+        }
+    }
+
+    /// The indices in `values` of the shown thumbs.
+    fn thumb_ixs(&self) -> std::ops::Range<usize> {
+        if self.range {
+            0..2
+        } else {
+            1..2
+        }
+    }
+
+    fn format_value(&self, value: f32) -> SharedString {
+        match self.format.as_ref() {
+            Some(format) => format(value),
+            None => {
+                let decimals = self.step.map_or(1, decimals);
+                format!("{value:.decimals$}").into()
+            }
+        }
+    }
+
+    /// The position of `value` along the track, from 0 at the min to 1 at the max.
+    fn fraction(&self, value: f32) -> f32 {
+        if self.max > self.min {
+            (value - self.min) / (self.max - self.min)
+        } else {
+            0.
+        }
+    }
+
+    fn value_for_position(&self, position: Point<Pixels>) -> f32 {
+        let bounds = self.track_bounds;
+        let fraction = match self.direction {
+            Direction::Horizontal if bounds.size.width > px(0.) => {
+                (position.x - bounds.left()) / bounds.size.width
+            }
+            Direction::Vertical if bounds.size.height > px(0.) => {
+                (bounds.bottom() - position.y) / bounds.size.height
+            }
+            _ => 0.,
+        };
+        self.min + fraction.clamp(0., 1.) * (self.max - self.min)
+    }
+
+    /// Moves the thumb at `ix` to `value`, keeping the start of a range before its end.
+    fn set_thumb(&mut self, ix: usize, value: f32, window: &mut Window, cx: &mut Context<Self>) {
+        let mut value = snap(value, self.min, self.max, self.step);
+        if self.range {
+            value = match ix {
+                0 => value.min(self.values[1]),
+                _ => value.max(self.values[0]),
+            };
+        } else {
+            self.values[0] = value;
+        }
+        if self.values[ix] == value {
+            return;
+        }
+        self.values[ix] = value;
+        cx.notify();
+
+        let value = self.get_value();
+        if let Some(on_change) = self.on_change.as_ref() {
+            on_change(&value, window, cx);
+        }
+        cx.emit(SliderEvent::Change(value));
+    }
+
+    /// The thumb the keys move, the focused one.
+    fn focused_thumb(&self, window: &Window) -> usize {
+        self.thumb_ixs()
+            .find(|ix| self.focus_handles[*ix].is_focused(window))
+            .unwrap_or(self.thumb_ixs().start)
+    }
+
+    fn step_by(&mut self, steps: f32, window: &mut Window, cx: &mut Context<Self>) {
+        let step = self.step.unwrap_or((self.max - self.min) * CONTINUOUS_STEP);
+        let ix = self.focused_thumb(window);
+        self.set_thumb(ix, self.values[ix] + step * steps, window, cx);
+    }
+
+    fn increase(&mut self, _: &Increase, window: &mut Window, cx: &mut Context<Self>) {
+        self.step_by(1., window, cx);
+    }
+
+    fn decrease(&mut self, _: &Decrease, window: &mut Window, cx: &mut Context<Self>) {
+        self.step_by(-1., window, cx);
+    }
+
+    fn increase_page(&mut self, _: &IncreasePage, window: &mut Window, cx: &mut Context<Self>) {
+        self.step_by(PAGE_STEPS, window, cx);
+    }
+
+    fn decrease_page(&mut self, _: &DecreasePage, window: &mut Window, cx: &mut Context<Self>) {
+        self.step_by(-PAGE_STEPS, window, cx);
+    }
+
+    fn set_minimum(&mut self, _: &SetMinimum, window: &mut Window, cx: &mut Context<Self>) {
+        let ix = self.focused_thumb(window);
+        self.set_thumb(ix, self.min, window, cx);
+    }
+
+    fn set_maximum(&mut self, _: &SetMaximum, window: &mut Window, cx: &mut Context<Self>) {
+        let ix = self.focused_thumb(window);
+        self.set_thumb(ix, self.max, window, cx);
+    }
+
+    /// Moves the closest thumb to the clicked value and starts dragging it.
+    fn on_mouse_down(
+        &mut self,
+        event: &MouseDownEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let value = self.value_for_position(event.position);
+        let ix = self.closest_thumb(value);
+        self.dragging = Some(ix);
+        window.focus(&self.focus_handles[ix]);
+        self.set_thumb(ix, value, window, cx);
+        cx.notify();
+    }
+
+    fn on_drag_move(
+        &mut self,
+        event: &MouseMoveEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(mut ix) = self.dragging else {
+            return;
+        };
+        let value = self.value_for_position(event.position);
+        // Thumbs on top of each other can only be dragged apart, by the one in that direction.
+        if self.range && self.values[0] == self.values[1] {
+            ix = self.closest_thumb(value);
+            self.dragging = Some(ix);
+            window.focus(&self.focus_handles[ix]);
+        }
+        self.set_thumb(ix, value, window, cx);
+    }
+
+    /// The thumb closest to `value`, or the one on the side of `value` if both are at the
+    /// same place.
+    fn closest_thumb(&self, value: f32) -> usize {
+        let [start, end] = self.values;
+        if !self.range {
+            1
+        } else if start == end {
+            (value >= start) as usize
+        } else {
+            ((value - start).abs() >= (value - end).abs()) as usize
+        }
+    }
+
+    fn on_drag_end(&mut self, cx: &mut Context<Self>) {
+        if self.dragging.take().is_some() {
+            cx.notify();
+        }
+    }
+
+    fn thumb_size(&self, window: &Window) -> Pixels {
+        match self.size {
+            Size::XSmall => px(12.),
+            Size::Small => px(14.),
+            Size::Medium => px(16.),
+            Size::Large => px(20.),
+            Size::Custom(size) => size.to_pixels(window.rem_size()),
+        }
+    }
+
+    /// Positions `element` at `fraction` along the track.
+    fn place<E: Styled>(&self, element: E, fraction: f32, size: Pixels) -> E {
+        match self.direction {
+            Direction::Horizontal => element.left(relative(fraction)).ml(-size / 2.),
+            Direction::Vertical => element.bottom(relative(fraction)).mb(-size / 2.),
+        }
+    }
+
+    fn render_thumb(
+        &self,
+        ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let colors = cx.theme().colors;
+        let thumb_size = self.thumb_size(window);
+        let value = self.values[ix];
+        let focused = self.focus_handles[ix].is_focused(window);
+        let show_tooltip =
+            !self.disabled && (self.dragging == Some(ix) || self.hovered == Some(ix));
+        let tooltip = show_tooltip.then(|| {
+            let text = self.format_value(value);
+            tooltip_container(window, cx, move |el, _, _| el.child(text))
+        });
+
+        let thumb = div()
+            .id(("thumb", ix))
+            .track_focus(&self.focus_handles[ix])
+            .absolute()
+            .size(thumb_size)
+            .rounded_full()
+            .border_2()
+            .border_color(if self.disabled {
+                cx.theme().color_scale(colors.primary).border()
+            } else {
+                colors.primary
+            })
+            .bg(colors.bg)
+            .shadow(ElevationIndex::ElevatedSurface.shadow(cx))
+            .when(focused, |this| {
+                this.shadow(smallvec![BoxShadow {
+                    color: colors.primary.opacity(0.3),
+                    offset: point(px(0.), px(0.)),
+                    blur_radius: px(0.),
+                    spread_radius: px(3.),
+                }])
+            })
+            .when(!self.disabled, |this| {
+                this.cursor_grab()
+                    .on_hover(cx.listener(move |this, hovered: &bool, _, cx| {
+                        match (*hovered, this.hovered == Some(ix)) {
+                            (true, _) => this.hovered = Some(ix),
+                            (false, true) => this.hovered = None,
+                            _ => return,
+                        }
+                        cx.notify();
+                    }))
+            })
+            .when_some(tooltip, |this, tooltip| {
+                let (anchor, offset) = match self.direction {
+                    Direction::Horizontal => (Corner::BottomLeft, point(-thumb_size / 2., px(-2.))),
+                    Direction::Vertical => (Corner::TopLeft, point(thumb_size, -thumb_size)),
+                };
+                this.child(
+                    deferred(anchored().anchor(anchor).offset(offset).child(tooltip))
+                        .with_priority(1),
+                )
+            });
+        let thumb = match self.direction {
+            Direction::Horizontal => thumb.top_0(),
+            Direction::Vertical => thumb.left_0(),
+        };
+        self.place(thumb, self.fraction(value), thumb_size)
+    }
+
+    fn render_track(&self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let colors = cx.theme().colors;
+        let thumb_size = self.thumb_size(window);
+        let track_size = thumb_size / 4.;
+        let vertical = self.direction.is_vertical();
+        let [start, end] = self.values;
+        let start = if self.range { self.fraction(start) } else { 0. };
+        let end = self.fraction(end);
+        let (rail_color, fill_color) = if self.disabled {
+            (
+                cx.theme()
+                    .color_scale(colors.switch_unchecked_bg)
+                    .active_bg(),
+                cx.theme().color_scale(colors.primary).border(),
+            )
+        } else {
+            (colors.switch_unchecked_bg, colors.primary)
+        };
+
+        let rail = div()
+            .absolute()
+            .rounded_full()
+            .bg(rail_color)
+            .when(!self.disabled, |this| {
+                this.group_hover("slider-track", |this| {
+                    this.bg(colors.switch_unchecked_hover_bg)
+                })
+            });
+        let fill = div().absolute().rounded_full().bg(fill_color);
+        let (rail, fill) = if vertical {
+            let inset = (thumb_size - track_size) / 2.;
+            (
+                rail.left(inset).w(track_size).top_0().bottom_0(),
+                fill.left(inset)
+                    .w(track_size)
+                    .bottom(relative(start))
+                    .h(relative(end - start)),
+            )
+        } else {
+            let inset = (thumb_size - track_size) / 2.;
+            (
+                rail.top(inset).h(track_size).left_0().right_0(),
+                fill.top(inset)
+                    .h(track_size)
+                    .left(relative(start))
+                    .w(relative(end - start)),
+            )
+        };
+
+        let ticks = self.ticks.iter().map(|tick| {
+            let fraction = self.fraction(tick.value);
+            let size = track_size.min(px(4.));
+            let filled = (start..=end).contains(&fraction);
+            let mark = div().absolute().size(size).rounded_full().bg(if filled {
+                colors.bg
+            } else {
+                colors.switch_unchecked_hover_bg
+            });
+            let inset = (thumb_size - size) / 2.;
+            let mark = if vertical {
+                mark.left(inset)
+            } else {
+                mark.top(inset)
+            };
+            self.place(mark, fraction, size)
+        });
+
+        let entity = cx.entity();
+        let dragging = self.dragging.is_some();
+        div()
+            .id("slider-track")
+            .group("slider-track")
+            .relative()
+            .flex_none()
+            .map(|this| {
+                if vertical {
+                    this.w(thumb_size).h_full().min_h(px(80.))
+                } else {
+                    this.h(thumb_size).w_full()
+                }
+            })
+            .when(!self.disabled, |this| {
+                this.cursor_pointer().on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(|this, event, window, cx| {
+                        cx.stop_propagation();
+                        this.on_mouse_down(event, window, cx);
+                    }),
+                )
+            })
+            .child(rail)
+            .child(fill)
+            .children(ticks)
+            .children(self.thumb_ixs().map(|ix| self.render_thumb(ix, window, cx)))
+            .child(
+                canvas(
+                    {
+                        let entity = entity.clone();
+                        move |bounds, _, cx| entity.update(cx, |this, _| this.track_bounds = bounds)
+                    },
+                    move |_, _, window, _| {
+                        if dragging {
+                            register_drag_listeners(entity, window);
+                        }
+                    },
+                )
+                .absolute()
+                .size_full(),
+            )
+    }
+
+    fn render_labels(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        if self.ticks.iter().all(|tick| tick.label.is_none()) {
+            return None;
+        }
+        let vertical = self.direction.is_vertical();
+        let labels = self.ticks.iter().filter_map(|tick| {
+            let text = tick.label.clone()?;
+            let label = div().absolute().text_xs().whitespace_nowrap().child(text);
+            let (label, size) = if vertical {
+                (label.left_0().h_4(), px(16.))
+            } else {
+                (label.top_0().w_12().flex().justify_center(), px(48.))
+            };
+            Some(self.place(label, self.fraction(tick.value), size))
+        });
+
+        Some(
+            div()
+                .relative()
+                .text_color(cx.theme().colors.text_muted)
+                .map(|this| {
+                    if vertical {
+                        this.h_full().w_12()
+                    } else {
+                        this.w_full().h_4()
+                    }
+                })
+                .children(labels),
+        )
+    }
+}
+
+/// Follows the mouse outside of the track until the button is released.
+fn register_drag_listeners(slider: Entity<Slider>, window: &mut Window) {
+    window.on_mouse_event({
+        let slider = slider.clone();
+        move |event: &MouseMoveEvent, phase, window, cx| {
+            if phase == DispatchPhase::Bubble && event.dragging() {
+                slider.update(cx, |this, cx| this.on_drag_move(event, window, cx));
+            }
+        }
+    });
+    window.on_mouse_event(move |_: &MouseUpEvent, phase, _, cx| {
+        if phase == DispatchPhase::Bubble {
+            slider.update(cx, |this, cx| this.on_drag_end(cx));
+        }
+    });
+}
+
+/// Clamps `value` between `min` and `max`, rounded to the closest step from `min`.
+fn snap(value: f32, min: f32, max: f32, step: Option<f32>) -> f32 {
+    let value = value.clamp(min, max.max(min));
+    match step {
+        Some(step) => {
+            // Rounding up past the max falls back to the last step below it, the tolerance keeps
+            // a max that is a multiple of the step despite the float error of the division.
+            let last_step = ((max - min) / step + 1e-4).floor();
+            let steps = ((value - min) / step).round().min(last_step);
+            let value = min + steps * step;
+            // Drops the float error of the multiplication, up to the decimals of the step.
+            let scale = 10f32.powi(decimals(step) as i32);
+            (value * scale).round() / scale
+        }
+        None => value,
+    }
+}
+
+fn decimals(step: f32) -> usize {
+    let step = step.to_string();
+    step.split_once('.')
+        .map_or(0, |(_, fraction)| fraction.len())
+}
+
+impl Render for Slider {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let vertical = self.direction.is_vertical();
+        let labels = self.render_labels(cx);
+
+        div()
+            .key_context(CONTEXT)
+            .when(!self.disabled, |this| {
+                this.on_action(cx.listener(Self::increase))
+                    .on_action(cx.listener(Self::decrease))
+                    .on_action(cx.listener(Self::increase_page))
+                    .on_action(cx.listener(Self::decrease_page))
+                    .on_action(cx.listener(Self::set_minimum))
+                    .on_action(cx.listener(Self::set_maximum))
+            })
+            .flex()
+            .gap_1()
+            .map(|this| {
+                if vertical {
+                    this.flex_row().h_full().px_1()
+                } else {
+                    this.flex_col().w_full().py_1()
+                }
+            })
+            .when(self.disabled, |this| this.opacity(0.5).cursor_not_allowed())
+            .child(self.render_track(window, cx))
+            .children(labels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{size, TestAppContext, VisualTestContext};
+
+    use super::*;
+    use crate::Theme;
+
+    fn build_slider(
+        cx: &mut TestAppContext,
+        build: impl FnOnce(Slider) -> Slider + 'static,
+    ) -> (Entity<Slider>, &mut VisualTestContext) {
+        cx.update(|cx| {
+            Theme::init(cx, None, None);
+            Slider::bind_keys(cx);
+        });
+        let (slider, cx) = cx.add_window_view(|_, cx| build(Slider::new(cx)));
+        slider.update(cx, |slider, _| {
+            slider.track_bounds = Bounds::new(point(px(0.), px(0.)), size(px(200.), px(16.)));
+        });
+        (slider, cx)
+    }
+
+    fn focus_thumb(slider: &Entity<Slider>, ix: usize, cx: &mut VisualTestContext) {
+        cx.update(|window, cx| window.focus(&slider.read(cx).focus_handles[ix]));
+    }
+
+    fn is_focused(slider: &Entity<Slider>, ix: usize, cx: &mut VisualTestContext) -> bool {
+        cx.update(|window, cx| slider.read(cx).focus_handles[ix].is_focused(window))
+    }
+
+    fn value(slider: &Entity<Slider>, cx: &mut VisualTestContext) -> SliderValue {
+        cx.read(|cx| slider.read(cx).get_value())
+    }
+
+    fn mouse_down(slider: &Entity<Slider>, x: f32, cx: &mut VisualTestContext) {
+        let event = MouseDownEvent {
+            position: point(px(x), px(8.)),
+            ..Default::default()
+        };
+        slider.update_in(cx, |slider, window, cx| {
+            slider.on_mouse_down(&event, window, cx)
+        });
+    }
+
+    fn drag_to(slider: &Entity<Slider>, x: f32, cx: &mut VisualTestContext) {
+        let event = MouseMoveEvent {
+            position: point(px(x), px(8.)),
+            ..Default::default()
+        };
+        slider.update_in(cx, |slider, window, cx| {
+            slider.on_drag_move(&event, window, cx)
+        });
+    }
+
+    #[gpui::test]
+    fn test_keyboard(cx: &mut TestAppContext) {
+        let (slider, cx) = build_slider(cx, |slider| slider.step(5.).value(50.));
+        focus_thumb(&slider, 1, cx);
+
+        cx.simulate_keystrokes("right right");
+        assert_eq!(value(&slider, cx), SliderValue::Single(60.));
+        cx.simulate_keystrokes("pagedown");
+        assert_eq!(value(&slider, cx), SliderValue::Single(10.));
+        cx.simulate_keystrokes("end right");
+        assert_eq!(value(&slider, cx), SliderValue::Single(100.));
+        cx.simulate_keystrokes("home");
+        assert_eq!(value(&slider, cx), SliderValue::Single(0.));
+    }
+
+    #[gpui::test]
+    fn test_keyboard_moves_focused_thumb(cx: &mut TestAppContext) {
+        let (slider, cx) = build_slider(cx, |slider| slider.step(1.).range(20., 80.));
+
+        focus_thumb(&slider, 0, cx);
+        cx.simulate_keystrokes("left");
+        assert_eq!(value(&slider, cx), SliderValue::Range(19., 80.));
+        focus_thumb(&slider, 1, cx);
+        cx.simulate_keystrokes("right");
+        assert_eq!(value(&slider, cx), SliderValue::Range(19., 81.));
+        // The start stops at the end.
+        focus_thumb(&slider, 0, cx);
+        cx.simulate_keystrokes("end");
+        assert_eq!(value(&slider, cx), SliderValue::Range(81., 81.));
+    }
+
+    #[gpui::test]
+    fn test_drag(cx: &mut TestAppContext) {
+        let (slider, cx) = build_slider(cx, |slider| slider.value(0.));
+
+        mouse_down(&slider, 50., cx);
+        assert_eq!(value(&slider, cx), SliderValue::Single(25.));
+        assert!(is_focused(&slider, 1, cx));
+        drag_to(&slider, 150., cx);
+        assert_eq!(value(&slider, cx), SliderValue::Single(75.));
+        // Past the end of the track.
+        drag_to(&slider, 300., cx);
+        assert_eq!(value(&slider, cx), SliderValue::Single(100.));
+
+        slider.update(cx, |slider, cx| slider.on_drag_end(cx));
+        drag_to(&slider, 50., cx);
+        assert_eq!(value(&slider, cx), SliderValue::Single(100.));
+    }
+
+    #[gpui::test]
+    fn test_drag_range(cx: &mut TestAppContext) {
+        let (slider, cx) = build_slider(cx, |slider| slider.step(5.).range(20., 80.));
+
+        // The click moves and focuses the closest thumb.
+        mouse_down(&slider, 180., cx);
+        assert_eq!(value(&slider, cx), SliderValue::Range(20., 90.));
+        assert!(is_focused(&slider, 1, cx));
+        assert!(!is_focused(&slider, 0, cx));
+        // The end stops at the start.
+        drag_to(&slider, 10., cx);
+        assert_eq!(value(&slider, cx), SliderValue::Range(20., 20.));
+        // Thumbs on top of each other are dragged apart by the one in that direction.
+        drag_to(&slider, 0., cx);
+        assert_eq!(value(&slider, cx), SliderValue::Range(0., 20.));
+        assert!(is_focused(&slider, 0, cx));
+    }
+
+    #[test]
+    fn test_snap() {
+        assert_eq!(snap(42.3, 0., 100., None), 42.3);
+        assert_eq!(snap(-5., 0., 100., None), 0.);
+        assert_eq!(snap(105., 0., 100., None), 100.);
+        assert_eq!(snap(42.3, 0., 100., Some(5.)), 40.);
+        assert_eq!(snap(43., 0., 100., Some(5.)), 45.);
+        assert_eq!(snap(0.3, 0., 1., Some(0.1)), 0.3);
+        assert_eq!(snap(7., 1., 10., Some(3.)), 7.);
+        // The max stays reachable only as a multiple of the step.
+        assert_eq!(snap(10., 0., 10., Some(3.)), 9.);
+        assert_eq!(snap(10., 0., 10., Some(4.)), 8.);
+        assert_eq!(snap(9.5, 0., 10., Some(4.)), 8.);
+        assert_eq!(snap(1., 0., 1., Some(0.1)), 1.);
+        assert_eq!(snap(0.72, 0., 1., Some(0.25)), 0.75);
+    }
+}
@@ -1,9 +1,9 @@
-use std::rc::Rc;
+use std::{rc::Rc, time::Duration};
 
 use crate::{prelude::*, Button, Color, Icon, IconName, ToastAction, ToastView};
 use gpui::{DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, IntoElement};
 
-use super::Text;
+use super::{toast_layer::DEFAULT_TOAST_DURATION, Text};
 
 #[derive(Clone, Copy)]
 pub struct ToastIcon {
@@ -38,6 +38,7 @@ pub struct StatusToast {
     icon: Option<ToastIcon>,
     text: SharedString,
     action: Option<ToastAction>,
+    duration: Option<Duration>,
     this_handle: Entity<Self>,
     focus_handle: FocusHandle,
 }
@@ -56,6 +57,7 @@ impl StatusToast {
                     text: text.into(),
                     icon: None,
                     action: None,
+                    duration: Some(DEFAULT_TOAST_DURATION),
                     this_handle: cx.entity(),
                     focus_handle,
                 },
@@ -69,6 +71,12 @@ impl StatusToast {
         self
     }
 
+    /// Sets how long the toast stays, `None` to keep it until it is dismissed.
+    pub fn with_duration(mut self, duration: Option<Duration>) -> Self {
+        self.duration = duration;
+        self
+    }
+
     pub fn action(
         mut self,
         label: impl Into<SharedString>,
@@ -98,7 +106,6 @@ impl Render for StatusToast {
             .px_2p5()
             .flex_none()
             .bg(cx.theme().colors.bg_elevated_surface)
-            .shadow_lg()
             .items_center()
             .when_some(self.icon.as_ref(), |this, icon| {
                 this.child(Icon::new(icon.icon).color(icon.color))
@@ -121,6 +128,10 @@ impl ToastView for StatusToast {
     fn action(&self) -> Option<ToastAction> {
         self.action.clone()
     }
+
+    fn duration(&self) -> Option<Duration> {
+        self.duration
+    }
 }
 
 impl Focusable for StatusToast {
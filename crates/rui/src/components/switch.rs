@@ -69,9 +69,15 @@ impl RenderOnce for Switch {
         let base_color = cx.theme().colors.bg;
 
         let bg_color = match (self.checked, self.disabled) {
-            (true, true) => cx.theme().colors.switch_checked_bg.opacity(0.7),
+            (true, true) => cx
+                .theme()
+                .color_scale(cx.theme().colors.switch_checked_bg)
+                .border(),
             (true, false) => cx.theme().colors.switch_checked_bg,
-            (false, true) => cx.theme().colors.switch_unchecked_bg.opacity(0.4),
+            (false, true) => cx
+                .theme()
+                .color_scale(cx.theme().colors.switch_unchecked_bg)
+                .active_bg(),
             (false, false) => cx.theme().colors.switch_unchecked_bg,
         };
         let bg_hover_color = if self.checked {
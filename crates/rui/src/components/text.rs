@@ -66,7 +66,7 @@ impl Styled for Text {
     }
 }
 
-const MASKED: &'static str = "•";
+pub(crate) const MASKED: &str = "•";
 
 impl RenderOnce for Text {
     fn render(self, window: &mut Window, _cx: &mut App) -> impl IntoElement {
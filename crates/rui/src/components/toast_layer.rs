@@ -1,15 +1,25 @@
-use crate::{animation::DefaultAnimations, prelude::*};
-use gpui::{AnyView, DismissEvent, Entity, FocusHandle, ManagedView, Subscription, Task};
+use crate::{animation::DefaultAnimations, prelude::*, Text};
+use gpui::{
+    AnyView, DismissEvent, Entity, EntityId, FocusHandle, ManagedView, MouseButton, Stateful,
+    Subscription, Task,
+};
 use std::{
     rc::Rc,
     time::{Duration, Instant},
 };
 
-const DEFAULT_TOAST_DURATION: Duration = Duration::from_secs(10);
+pub(crate) const DEFAULT_TOAST_DURATION: Duration = Duration::from_secs(10);
 const MINIMUM_RESUME_DURATION: Duration = Duration::from_millis(800);
+const DEFAULT_MAX_VISIBLE_TOASTS: usize = 3;
 
 pub trait ToastView: ManagedView {
     fn action(&self) -> Option<ToastAction>;
+
+    /// How long the toast stays before it is dismissed, `None` to keep it until it is dismissed
+    /// by the user.
+    fn duration(&self) -> Option<Duration> {
+        Some(DEFAULT_TOAST_DURATION)
+    }
 }
 
 #[derive(Clone)]
@@ -34,6 +44,27 @@ impl ToastAction {
     }
 }
 
+/// Where the toasts are shown in the window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ToastPosition {
+    TopLeft,
+    TopCenter,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomCenter,
+    BottomRight,
+}
+
+impl ToastPosition {
+    fn is_top(self) -> bool {
+        matches!(
+            self,
+            ToastPosition::TopLeft | ToastPosition::TopCenter | ToastPosition::TopRight
+        )
+    }
+}
+
 trait ToastViewHandle {
     fn view(&self) -> AnyView;
 }
@@ -48,6 +79,15 @@ pub struct ActiveToast {
     toast: Box<dyn ToastViewHandle>,
     _subscriptions: [Subscription; 1],
     focus_handle: FocusHandle,
+    /// The time left before the toast is dismissed, `None` for sticky toasts.
+    duration_remaining: Option<Duration>,
+    dismiss_timer: Option<DismissTimer>,
+}
+
+impl ActiveToast {
+    fn entity_id(&self) -> EntityId {
+        self.toast.view().entity_id()
+    }
 }
 
 struct DismissTimer {
@@ -55,10 +95,17 @@ struct DismissTimer {
     _task: Task<()>,
 }
 
+/// Shows toasts above the content of the window, stacked at one of its corners or edges.
+///
+/// Toasts are queued: at most [`ToastLayer::set_max_visible`] of them are shown, the oldest
+/// first, and the others wait for a place with a counter of how many are waiting. The timers
+/// of the shown toasts run while the pointer is not over them.
 pub struct ToastLayer {
-    active_toast: Option<ActiveToast>,
-    duration_remaining: Option<Duration>,
-    dismiss_timer: Option<DismissTimer>,
+    /// The queued toasts, the oldest first.
+    toasts: Vec<ActiveToast>,
+    position: ToastPosition,
+    max_visible: usize,
+    hovered: bool,
 }
 
 impl Default for ToastLayer {
@@ -70,153 +117,437 @@ impl Default for ToastLayer {
 impl ToastLayer {
     pub fn new() -> Self {
         Self {
-            active_toast: None,
-            duration_remaining: None,
-            dismiss_timer: None,
+            toasts: Vec::new(),
+            position: ToastPosition::default(),
+            max_visible: DEFAULT_MAX_VISIBLE_TOASTS,
+            hovered: false,
         }
     }
 
+    pub fn set_position(&mut self, position: ToastPosition, cx: &mut Context<Self>) {
+        self.position = position;
+        cx.notify();
+    }
+
+    /// Sets how many toasts are shown at once, at least one.
+    pub fn set_max_visible(&mut self, max_visible: usize, cx: &mut Context<Self>) {
+        self.max_visible = max_visible.max(1);
+        self.start_dismiss_timers(cx);
+        cx.notify();
+    }
+
+    /// Hides the newest toast if it is a `V`, or queues `new_toast`.
     pub fn toggle_toast<V>(&mut self, cx: &mut Context<Self>, new_toast: Entity<V>)
     where
         V: ToastView,
     {
-        if let Some(active_toast) = &self.active_toast {
-            let is_close = active_toast.toast.view().downcast::<V>().is_ok();
-            let did_close = self.hide_toast(cx);
-            if is_close || !did_close {
-                return;
-            }
+        if self.active_toast::<V>().is_some() {
+            self.hide_toast(cx);
+            return;
         }
         self.show_toast(new_toast, cx);
     }
 
+    /// Queues a toast, it is shown as soon as there is a place for it.
     pub fn show_toast<V>(&mut self, new_toast: Entity<V>, cx: &mut Context<Self>)
     where
         V: ToastView,
     {
         let focus_handle = cx.focus_handle();
+        let toast_id = new_toast.entity_id();
 
-        self.active_toast = Some(ActiveToast {
+        self.toasts.push(ActiveToast {
             toast: Box::new(new_toast.clone()),
-            _subscriptions: [cx.subscribe(&new_toast, |this, _, _: &DismissEvent, cx| {
-                this.hide_toast(cx);
-            })],
+            _subscriptions: [
+                cx.subscribe(&new_toast, move |this, _, _: &DismissEvent, cx| {
+                    this.hide_toast_with_id(toast_id, cx);
+                }),
+            ],
             focus_handle,
+            duration_remaining: new_toast.read(cx).duration(),
+            dismiss_timer: None,
         });
 
-        self.start_dismiss_timer(DEFAULT_TOAST_DURATION, cx);
+        self.start_dismiss_timers(cx);
 
         cx.notify();
     }
 
+    /// Hides the newest toast, returning whether there was one.
     pub fn hide_toast(&mut self, cx: &mut Context<Self>) -> bool {
-        self.active_toast.take();
+        match self.toasts.last().map(ActiveToast::entity_id) {
+            Some(toast_id) => self.hide_toast_with_id(toast_id, cx),
+            None => false,
+        }
+    }
+
+    /// Hides the toast of the entity `toast_id`, returning whether it was queued.
+    pub fn hide_toast_with_id(&mut self, toast_id: EntityId, cx: &mut Context<Self>) -> bool {
+        let Some(ix) = self
+            .toasts
+            .iter()
+            .position(|toast| toast.entity_id() == toast_id)
+        else {
+            return false;
+        };
+        self.toasts.remove(ix);
+        if self.toasts.is_empty() {
+            // The pointer can't leave toasts that are gone.
+            self.hovered = false;
+        }
+        // A waiting toast may have taken the place.
+        self.start_dismiss_timers(cx);
         cx.notify();
 
         true
     }
 
+    /// Hides all the toasts, shown or waiting.
+    pub fn clear_toasts(&mut self, cx: &mut Context<Self>) {
+        self.toasts.clear();
+        self.hovered = false;
+        cx.notify();
+    }
+
+    /// Returns the newest toast, if it is a `V`.
     pub fn active_toast<V>(&self) -> Option<Entity<V>>
     where
         V: 'static,
     {
-        let active_toast = self.active_toast.as_ref()?;
+        let active_toast = self.toasts.last()?;
         active_toast.toast.view().downcast::<V>().ok()
     }
 
-    pub fn has_active_toast(&self) -> bool {
-        self.active_toast.is_some()
+    /// Returns the newest `V` of the queued toasts.
+    pub fn find_toast<V>(&self) -> Option<Entity<V>>
+    where
+        V: 'static,
+    {
+        self.toasts
+            .iter()
+            .rev()
+            .find_map(|toast| toast.toast.view().downcast::<V>().ok())
     }
 
-    fn pause_dismiss_timer(&mut self) {
-        let Some(dismiss_timer) = self.dismiss_timer.take() else {
-            return;
-        };
-        let Some(duration_remaining) = self.duration_remaining.as_mut() else {
-            return;
-        };
-        *duration_remaining =
-            duration_remaining.saturating_sub(dismiss_timer.instant_started.elapsed());
-        if *duration_remaining < MINIMUM_RESUME_DURATION {
-            *duration_remaining = MINIMUM_RESUME_DURATION;
-        }
+    pub fn has_active_toast(&self) -> bool {
+        !self.toasts.is_empty()
     }
 
-    /// Starts a timer to automatically dismiss the toast after the specified duration
-    pub fn start_dismiss_timer(&mut self, duration: Duration, cx: &mut Context<Self>) {
-        self.clear_dismiss_timer(cx);
-
-        let instant_started = std::time::Instant::now();
-        let task = cx.spawn(async move |this, cx| {
-            cx.background_executor().timer(duration).await;
+    /// The number of queued toasts, shown or waiting.
+    pub fn toast_count(&self) -> usize {
+        self.toasts.len()
+    }
 
-            if let Some(this) = this.upgrade() {
-                this.update(cx, |this, cx| this.hide_toast(cx)).ok();
+    /// Pauses the timers of all the shown toasts, they resume with
+    /// [`ToastLayer::start_dismiss_timers`].
+    fn pause_dismiss_timers(&mut self, cx: &App) {
+        let now = cx.background_executor().now();
+        for toast in &mut self.toasts {
+            let Some(dismiss_timer) = toast.dismiss_timer.take() else {
+                continue;
+            };
+            let Some(duration_remaining) = toast.duration_remaining.as_mut() else {
+                continue;
+            };
+            *duration_remaining =
+                duration_remaining.saturating_sub(now - dismiss_timer.instant_started);
+            if *duration_remaining < MINIMUM_RESUME_DURATION {
+                *duration_remaining = MINIMUM_RESUME_DURATION;
             }
-        });
-
-        self.duration_remaining = Some(duration);
-        self.dismiss_timer = Some(DismissTimer {
-            instant_started,
-            _task: task,
-        });
-        cx.notify();
+        }
     }
 
-    /// Restarts the dismiss timer with a new duration
-    pub fn restart_dismiss_timer(&mut self, cx: &mut Context<Self>) {
-        let Some(duration) = self.duration_remaining else {
+    /// Starts a timer for each shown toast without one, to dismiss it after its remaining
+    /// duration. Does nothing while the toasts are hovered.
+    pub fn start_dismiss_timers(&mut self, cx: &mut Context<Self>) {
+        if self.hovered {
             return;
-        };
-        self.start_dismiss_timer(duration, cx);
-        cx.notify();
+        }
+
+        let now = cx.background_executor().now();
+        for toast in self.toasts.iter_mut().take(self.max_visible) {
+            if toast.dismiss_timer.is_some() {
+                continue;
+            }
+            let Some(duration) = toast.duration_remaining else {
+                continue;
+            };
+            let toast_id = toast.entity_id();
+            let task = cx.spawn(async move |this, cx| {
+                cx.background_executor().timer(duration).await;
+
+                if let Some(this) = this.upgrade() {
+                    this.update(cx, |this, cx| this.hide_toast_with_id(toast_id, cx))
+                        .ok();
+                }
+            });
+            toast.dismiss_timer = Some(DismissTimer {
+                instant_started: now,
+                _task: task,
+            });
+        }
     }
 
-    /// Clears the dismiss timer if one exists
-    pub fn clear_dismiss_timer(&mut self, cx: &mut Context<Self>) {
-        self.dismiss_timer.take();
-        cx.notify();
+    fn set_hovered(&mut self, hovered: bool, cx: &mut Context<Self>) {
+        self.hovered = hovered;
+        if hovered {
+            self.pause_dismiss_timers(cx);
+        } else {
+            self.start_dismiss_timers(cx);
+        }
     }
 }
 
 impl Render for ToastLayer {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let Some(active_toast) = &self.active_toast else {
+        if self.toasts.is_empty() {
             return div();
-        };
+        }
         let handle = cx.weak_entity();
+        let position = self.position;
+        let direction = if position.is_top() {
+            AnimationDirection::FromTop
+        } else {
+            AnimationDirection::FromBottom
+        };
+        let waiting = self.toasts.len().saturating_sub(self.max_visible);
+
+        let toasts = self
+            .toasts
+            .iter()
+            .take(self.max_visible)
+            .map(|active_toast| {
+                div().id(active_toast.entity_id()).child(
+                    h_flex()
+                        .occlude()
+                        .track_focus(&active_toast.focus_handle)
+                        .on_mouse_down(MouseButton::Left, |_, _, cx| {
+                            cx.stop_propagation();
+                        })
+                        .child(active_toast.toast.view())
+                        .animate_in(direction, true),
+                )
+            });
 
-        div().absolute().size_full().bottom_0().left_0().child(
+        let alignment = |this: Stateful<Div>| match position {
+            ToastPosition::TopLeft | ToastPosition::BottomLeft => this.items_start(),
+            ToastPosition::TopCenter | ToastPosition::BottomCenter => this.items_center(),
+            ToastPosition::TopRight | ToastPosition::BottomRight => this.items_end(),
+        };
+
+        div().absolute().size_full().top_0().left_0().child(
             v_flex()
                 .id("toast-layer-container")
                 .absolute()
                 .w_full()
-                .bottom(px(0.))
-                .flex()
-                .flex_col()
-                .items_center()
-                .track_focus(&active_toast.focus_handle)
+                .px_4()
+                .map(|this| {
+                    if position.is_top() {
+                        this.top_0()
+                    } else {
+                        this.bottom_0()
+                    }
+                })
+                .map(alignment)
                 .child(
-                    h_flex()
-                        .id("active-toast-container")
-                        .occlude()
+                    // Only as large as the toasts, so that the pointer is over them when it is
+                    // over the stack.
+                    v_flex()
+                        .id("toast-stack")
+                        .gap_2()
+                        // The oldest toast is the closest to the edge.
+                        .when(!position.is_top(), |this| this.flex_col_reverse())
+                        .map(alignment)
                         .on_hover(move |hover_start, _window, cx| {
-                            let Some(this) = handle.upgrade() else {
-                                return;
-                            };
-                            if *hover_start {
-                                this.update(cx, |this, _| this.pause_dismiss_timer());
-                            } else {
-                                this.update(cx, |this, cx| this.restart_dismiss_timer(cx));
+                            if let Some(this) = handle.upgrade() {
+                                this.update(cx, |this, cx| this.set_hovered(*hover_start, cx));
                             }
                             cx.stop_propagation();
                         })
-                        .on_click(|_, _, cx| {
-                            cx.stop_propagation();
-                        })
-                        .child(active_toast.toast.view()),
-                )
-                .animate_in(AnimationDirection::FromBottom, true),
+                        .children(toasts)
+                        .when(waiting > 0, |this| {
+                            this.child(
+                                h_flex().elevation_2(cx).px_2().py_0p5().child(
+                                    Text::new(format!("+{waiting} more"))
+                                        .color(cx.theme().colors.text_muted),
+                                ),
+                            )
+                        }),
+                ),
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use gpui::TestAppContext;
+
+    use super::*;
+    use crate::StatusToast;
+
+    fn show_toast(
+        layer: &Entity<ToastLayer>,
+        duration: Option<Duration>,
+        cx: &mut TestAppContext,
+    ) -> EntityId {
+        cx.update(|cx| {
+            let toast = StatusToast::new("Toast", cx, |this, _| this.with_duration(duration));
+            let toast_id = toast.entity_id();
+            layer.update(cx, |layer, cx| layer.show_toast(toast, cx));
+            toast_id
+        })
+    }
+
+    fn timers(layer: &Entity<ToastLayer>, cx: &mut TestAppContext) -> Vec<bool> {
+        cx.read(|cx| {
+            layer
+                .read(cx)
+                .toasts
+                .iter()
+                .map(|toast| toast.dismiss_timer.is_some())
+                .collect()
+        })
+    }
+
+    fn advance(duration: Duration, cx: &mut TestAppContext) {
+        cx.executor().advance_clock(duration);
+        cx.run_until_parked();
+    }
+
+    #[gpui::test]
+    fn test_queue(cx: &mut TestAppContext) {
+        let layer = cx.new(|_| ToastLayer::new());
+        cx.update(|cx| layer.update(cx, |layer, cx| layer.set_max_visible(2, cx)));
+
+        let first = show_toast(&layer, Some(Duration::from_secs(1)), cx);
+        show_toast(&layer, Some(Duration::from_secs(2)), cx);
+        show_toast(&layer, Some(Duration::from_secs(1)), cx);
+        // The waiting toast has no timer until it is shown.
+        assert_eq!(timers(&layer, cx), [true, true, false]);
+
+        advance(Duration::from_secs(1), cx);
+        cx.read(|cx| {
+            let layer = layer.read(cx);
+            assert_eq!(layer.toast_count(), 2);
+            assert!(layer.toasts.iter().all(|toast| toast.entity_id() != first));
+        });
+        assert_eq!(timers(&layer, cx), [true, true]);
+
+        advance(Duration::from_secs(1), cx);
+        cx.read(|cx| assert!(!layer.read(cx).has_active_toast()));
+    }
+
+    #[gpui::test]
+    fn test_sticky_toast(cx: &mut TestAppContext) {
+        let layer = cx.new(|_| ToastLayer::new());
+        let sticky = show_toast(&layer, None, cx);
+        assert_eq!(timers(&layer, cx), [false]);
+
+        advance(DEFAULT_TOAST_DURATION * 2, cx);
+        cx.read(|cx| assert_eq!(layer.read(cx).toast_count(), 1));
+
+        cx.update(|cx| {
+            layer.update(
+                cx,
+                |layer, cx| assert!(layer.hide_toast_with_id(sticky, cx)),
+            )
+        });
+        cx.read(|cx| assert!(!layer.read(cx).has_active_toast()));
+    }
+
+    #[gpui::test]
+    fn test_max_visible(cx: &mut TestAppContext) {
+        let layer = cx.new(|_| ToastLayer::new());
+        for _ in 0..3 {
+            show_toast(&layer, Some(Duration::from_secs(1)), cx);
+        }
+
+        cx.update(|cx| layer.update(cx, |layer, cx| layer.set_max_visible(0, cx)));
+        cx.read(|cx| assert_eq!(layer.read(cx).max_visible, 1));
+
+        show_toast(&layer, Some(Duration::from_secs(1)), cx);
+        assert_eq!(timers(&layer, cx), [true, true, true, false]);
+
+        // Showing more toasts starts the timers of the toasts taking a place.
+        cx.update(|cx| layer.update(cx, |layer, cx| layer.set_max_visible(4, cx)));
+        assert_eq!(timers(&layer, cx), [true, true, true, true]);
+    }
+
+    #[gpui::test]
+    fn test_pause_and_resume(cx: &mut TestAppContext) {
+        let layer = cx.new(|_| ToastLayer::new());
+        show_toast(&layer, Some(Duration::from_secs(4)), cx);
+
+        advance(Duration::from_secs(1), cx);
+        cx.update(|cx| layer.update(cx, |layer, cx| layer.set_hovered(true, cx)));
+        assert_eq!(timers(&layer, cx), [false]);
+        cx.read(|cx| {
+            assert_eq!(
+                layer.read(cx).toasts[0].duration_remaining,
+                Some(Duration::from_secs(3))
+            )
+        });
+
+        // Hovered toasts stay.
+        advance(DEFAULT_TOAST_DURATION, cx);
+        cx.read(|cx| assert_eq!(layer.read(cx).toast_count(), 1));
+
+        // Queued toasts wait for the pointer to leave.
+        show_toast(&layer, Some(Duration::from_secs(1)), cx);
+        assert_eq!(timers(&layer, cx), [false, false]);
+
+        cx.update(|cx| layer.update(cx, |layer, cx| layer.set_hovered(false, cx)));
+        assert_eq!(timers(&layer, cx), [true, true]);
+
+        advance(Duration::from_secs(1), cx);
+        cx.read(|cx| assert_eq!(layer.read(cx).toast_count(), 1));
+        advance(Duration::from_secs(2), cx);
+        cx.read(|cx| assert!(!layer.read(cx).has_active_toast()));
+    }
+
+    #[gpui::test]
+    fn test_resume_keeps_minimum_duration(cx: &mut TestAppContext) {
+        let layer = cx.new(|_| ToastLayer::new());
+        show_toast(&layer, Some(Duration::from_secs(1)), cx);
+
+        advance(Duration::from_millis(900), cx);
+        cx.update(|cx| {
+            layer.update(cx, |layer, cx| {
+                layer.set_hovered(true, cx);
+                layer.set_hovered(false, cx);
+            })
+        });
+        cx.read(|cx| {
+            assert_eq!(
+                layer.read(cx).toasts[0].duration_remaining,
+                Some(MINIMUM_RESUME_DURATION)
+            )
+        });
+
+        advance(Duration::from_millis(700), cx);
+        cx.read(|cx| assert_eq!(layer.read(cx).toast_count(), 1));
+        advance(Duration::from_millis(100), cx);
+        cx.read(|cx| assert!(!layer.read(cx).has_active_toast()));
+    }
+
+    #[gpui::test]
+    fn test_hidden_toasts_reset_hover(cx: &mut TestAppContext) {
+        let layer = cx.new(|_| ToastLayer::new());
+        let toast = show_toast(&layer, Some(Duration::from_secs(1)), cx);
+        cx.update(|cx| {
+            layer.update(cx, |layer, cx| {
+                layer.set_hovered(true, cx);
+                layer.hide_toast_with_id(toast, cx);
+                assert!(!layer.hovered);
+
+                layer.set_hovered(true, cx);
+                layer.clear_toasts(cx);
+                assert!(!layer.hovered);
+            })
+        });
+
+        // The next toast is not paused by the pointer over the old ones.
+        show_toast(&layer, Some(Duration::from_secs(1)), cx);
+        assert_eq!(timers(&layer, cx), [true]);
+    }
+}
@@ -1,4 +1,4 @@
-use gpui::{hsla, point, AnyView, AppContext as _, BoxShadow, IntoElement, Render};
+use gpui::{AnyView, AppContext as _, IntoElement, Render};
 
 use crate::{prelude::*, Text};
 
@@ -64,33 +64,7 @@ pub fn tooltip_container<V>(
     // padding to avoid tooltip appearing right below the mouse cursor
     div().pl_2().pt_2p5().child(
         v_flex()
-            .bg(cx.theme().colors.bg_elevated_surface)
-            .rounded_lg()
-            .border_1()
-            .border_color(cx.theme().colors.border_variant)
-            .shadow(smallvec::smallvec![
-                BoxShadow {
-                    color: hsla(0., 0., 0., 0.12),
-                    offset: point(px(0.), px(2.)),
-                    blur_radius: px(3.),
-                    spread_radius: px(0.),
-                },
-                BoxShadow {
-                    color: hsla(
-                        0.,
-                        0.,
-                        0.,
-                        if cx.theme().appearance.is_light() {
-                            0.03
-                        } else {
-                            0.06
-                        }
-                    ),
-                    offset: point(px(1.), px(1.)),
-                    blur_radius: px(0.),
-                    spread_radius: px(0.),
-                }
-            ])
+            .elevation_2(cx)
             .text_size(rems_from_px(14.))
             .text_color(cx.theme().colors.text)
             .py_1()
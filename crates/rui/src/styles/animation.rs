@@ -72,6 +72,28 @@ pub trait DefaultAnimations: Styled + Sized {
         )
     }
 
+    /// Slides in from outside of its parent, for elements as large as their parent, like the
+    /// container of a drawer.
+    fn slide_in(self, direction: AnimationDirection) -> AnimationElement<Self> {
+        let animation_name = match direction {
+            AnimationDirection::FromBottom => "slide_from_bottom",
+            AnimationDirection::FromLeft => "slide_from_left",
+            AnimationDirection::FromRight => "slide_from_right",
+            AnimationDirection::FromTop => "slide_from_top",
+        };
+
+        self.with_animation(
+            animation_name,
+            gpui::Animation::new(AnimationDuration::Slow.into()).with_easing(ease_out_quint()),
+            move |this, delta| match direction {
+                AnimationDirection::FromBottom => this.top(relative(1.0 - delta)),
+                AnimationDirection::FromLeft => this.left(relative(delta - 1.0)),
+                AnimationDirection::FromRight => this.left(relative(1.0 - delta)),
+                AnimationDirection::FromTop => this.top(relative(delta - 1.0)),
+            },
+        )
+    }
+
     fn animate_in_from_bottom(self, fade: bool) -> AnimationElement<Self> {
         self.animate_in(AnimationDirection::FromBottom, fade)
     }
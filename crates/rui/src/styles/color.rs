@@ -1,4 +1,4 @@
-use crate::ActiveTheme;
+use crate::{contrast_ratio, ActiveTheme, ColorScale};
 use gpui::{App, Hsla};
 
 /// Sets a color that has a consistent meaning across all themes.
@@ -28,6 +28,22 @@ impl Color {
         }
     }
 
+    /// Returns the Color's [`ColorScale`] for the active theme appearance.
+    pub fn scale(&self, cx: &App) -> ColorScale {
+        cx.theme().color_scale(self.hsla(cx))
+    }
+
+    /// Returns a readable text color on the given background color,
+    /// black or white, whichever has the highest contrast.
+    pub fn foreground_on(bg: Hsla) -> Hsla {
+        let (black, white) = (Self::black(), Self::white());
+        if contrast_ratio(black, bg) > contrast_ratio(white, bg) {
+            black
+        } else {
+            white
+        }
+    }
+
     const fn hsl(h: f32, s: f32, l: f32) -> Hsla {
         Hsla {
             h: h / 360.0,
@@ -1,8 +1,8 @@
 use std::fmt::{self, Display, Formatter};
 
 use crate::ActiveTheme;
-use gpui::{hsla, point, px, App, BoxShadow};
-use smallvec::{smallvec, SmallVec};
+use gpui::{App, BoxShadow, Hsla};
+use smallvec::SmallVec;
 
 /// Today, elevation is primarily used to add shadows to elements, and set the correct background for elements like buttons.
 ///
@@ -40,91 +40,51 @@ impl Display for ElevationIndex {
 
 impl ElevationIndex {
     /// Returns an appropriate shadow for the given elevation index.
+    ///
+    /// Returns no shadow when [`ThemeStyles::shadow`](crate::ThemeStyles::shadow) is disabled.
     pub fn shadow(self, cx: &App) -> SmallVec<[BoxShadow; 2]> {
-        let is_light = cx.theme().appearance.is_light();
+        let theme = cx.theme();
+        if !theme.styles.shadow {
+            return SmallVec::new();
+        }
+        SmallVec::from(theme.shadows.get(self))
+    }
 
+    /// Returns the background color for the given elevation index.
+    pub fn bg(self, cx: &App) -> Hsla {
+        let colors = &cx.theme().colors;
         match self {
-            ElevationIndex::Surface => smallvec![],
-            ElevationIndex::EditorSurface => smallvec![],
-
-            ElevationIndex::ElevatedSurface => smallvec![
-                BoxShadow {
-                    color: hsla(0., 0., 0., 0.12),
-                    offset: point(px(0.), px(2.)),
-                    blur_radius: px(3.),
-                    spread_radius: px(0.),
-                },
-                BoxShadow {
-                    color: hsla(0., 0., 0., if is_light { 0.03 } else { 0.06 }),
-                    offset: point(px(1.), px(1.)),
-                    blur_radius: px(0.),
-                    spread_radius: px(0.),
-                }
-            ],
-
-            ElevationIndex::ModalSurface => smallvec![
-                BoxShadow {
-                    color: hsla(0., 0., 0., if is_light { 0.06 } else { 0.12 }),
-                    offset: point(px(0.), px(2.)),
-                    blur_radius: px(3.),
-                    spread_radius: px(0.),
-                },
-                BoxShadow {
-                    color: hsla(0., 0., 0., if is_light { 0.06 } else { 0.08 }),
-                    offset: point(px(0.), px(3.)),
-                    blur_radius: px(6.),
-                    spread_radius: px(0.),
-                },
-                BoxShadow {
-                    color: hsla(0., 0., 0., 0.04),
-                    offset: point(px(0.), px(6.)),
-                    blur_radius: px(12.),
-                    spread_radius: px(0.),
-                },
-                BoxShadow {
-                    color: hsla(0., 0., 0., if is_light { 0.04 } else { 0.12 }),
-                    offset: point(px(1.), px(1.)),
-                    blur_radius: px(0.),
-                    spread_radius: px(0.),
-                },
-            ],
-
-            _ => smallvec![],
+            ElevationIndex::Background => colors.bg,
+            ElevationIndex::Surface => colors.bg_surface,
+            ElevationIndex::EditorSurface => colors.bg_editor_surface,
+            ElevationIndex::ElevatedSurface => colors.bg_elevated_surface,
+            ElevationIndex::ModalSurface => colors.bg_modal_surface,
         }
     }
 
-    // /// Returns the background color for the given elevation index.
-    // pub fn bg(&self, cx: &mut App) -> Hsla {
-    //     match self {
-    //         ElevationIndex::Background => cx.theme().colors().background,
-    //         ElevationIndex::Surface => cx.theme().colors().surface_background,
-    //         ElevationIndex::EditorSurface => cx.theme().colors().editor_background,
-    //         ElevationIndex::ElevatedSurface => cx.theme().colors().elevated_surface_background,
-    //         ElevationIndex::ModalSurface => cx.theme().colors().elevated_surface_background,
-    //     }
-    // }
-
-    // /// Returns a color that is appropriate a filled element on this elevation
-    // pub fn on_elevation_bg(&self, cx: &App) -> Hsla {
-    //     match self {
-    //         ElevationIndex::Background => cx.theme().colors().surface_background,
-    //         ElevationIndex::Surface => cx.theme().colors().background,
-    //         ElevationIndex::EditorSurface => cx.theme().colors().surface_background,
-    //         ElevationIndex::ElevatedSurface => cx.theme().colors().background,
-    //         ElevationIndex::ModalSurface => cx.theme().colors().background,
-    //     }
-    // }
+    /// Returns a color that is appropriate a filled element on this elevation
+    pub fn on_elevation_bg(self, cx: &App) -> Hsla {
+        let colors = &cx.theme().colors;
+        match self {
+            ElevationIndex::Background => colors.bg_surface,
+            ElevationIndex::Surface => colors.bg,
+            ElevationIndex::EditorSurface => colors.bg_surface,
+            ElevationIndex::ElevatedSurface => colors.bg,
+            ElevationIndex::ModalSurface => colors.bg,
+        }
+    }
 
-    // /// Attempts to return a darker background color than the current elevation index's background.
-    // ///
-    // /// If the current background color is already dark, it will return a lighter color instead.
-    // pub fn darker_bg(&self, cx: &App) -> Hsla {
-    //     match self {
-    //         ElevationIndex::Background => cx.theme().colors().surface_background,
-    //         ElevationIndex::Surface => cx.theme().colors().editor_background,
-    //         ElevationIndex::EditorSurface => cx.theme().colors().surface_background,
-    //         ElevationIndex::ElevatedSurface => cx.theme().colors().editor_background,
-    //         ElevationIndex::ModalSurface => cx.theme().colors().editor_background,
-    //     }
-    // }
+    /// Attempts to return a darker background color than the current elevation index's background.
+    ///
+    /// If the current background color is already dark, it will return a lighter color instead.
+    pub fn darker_bg(self, cx: &App) -> Hsla {
+        let colors = &cx.theme().colors;
+        match self {
+            ElevationIndex::Background => colors.bg_surface,
+            ElevationIndex::Surface => colors.bg_editor_surface,
+            ElevationIndex::EditorSurface => colors.bg_surface,
+            ElevationIndex::ElevatedSurface => colors.bg_editor_surface,
+            ElevationIndex::ModalSurface => colors.bg_editor_surface,
+        }
+    }
 }
@@ -1,11 +1,24 @@
 mod appearance;
 mod color;
+mod refinement;
+mod registry;
+mod scale;
+mod schema;
 mod setting;
+mod shadow;
 mod style;
 mod theme;
+mod tokens;
+mod watcher;
 
 pub use appearance::*;
 pub use color::*;
+pub use refinement::*;
+pub use registry::*;
+pub use scale::*;
+pub use schema::*;
 pub use setting::*;
+pub use shadow::*;
 pub use style::*;
 pub use theme::*;
+pub use watcher::*;
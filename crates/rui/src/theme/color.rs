@@ -1,6 +1,8 @@
+use std::fmt;
+
 use gpui::Hsla;
 
-use crate::hsl;
+use crate::{contrast_ratio, hsl, WCAG_AA_CONTRAST};
 
 use super::Appearance;
 
@@ -35,8 +37,14 @@ pub struct ThemeColors {
     /// Text Color. Color of muted or deemphasized text. It is a subdued version of the standard text color.
     pub text_muted: Hsla,
     pub bg: Hsla,
+    /// Background color. Used for the primary surface, like panels, title bars and tab bars.
+    pub bg_surface: Hsla,
+    /// Background color. Used for editable areas on the primary surface, like editors and text areas.
+    pub bg_editor_surface: Hsla,
     /// Background color. Used for elevated surfaces, like a context menu, popup, or dialog.
     pub bg_elevated_surface: Hsla,
+    /// Background color. Used for modal surfaces, like dialogs, alerts and settings modals.
+    pub bg_modal_surface: Hsla,
     pub fg: Hsla,
     pub switch_checked_bg: Hsla,
     pub switch_checked_hover_bg: Hsla,
@@ -62,7 +70,10 @@ impl ThemeColors {
             text: hsl(214., 11., 12.),
             text_muted: hsl(240., 1., 35.),
             bg: hsl(0., 0., 100.),
+            bg_surface: hsl(240., 3., 92.),
+            bg_editor_surface: hsl(0., 0., 100.),
             bg_elevated_surface: hsl(240., 3., 92.),
+            bg_modal_surface: hsl(240., 3., 92.),
             fg: hsl(0., 0., 0.),
             switch_checked_bg: hsl(126., 50., 47.),
             switch_checked_hover_bg: hsl(127., 51., 38.),
@@ -87,7 +98,10 @@ impl ThemeColors {
             text: hsl(0., 0., 98.),
             text_muted: hsl(221., 12., 70.),
             bg: hsl(0., 0., 0.),
+            bg_surface: hsl(220., 14., 21.),
+            bg_editor_surface: hsl(0., 0., 0.),
             bg_elevated_surface: hsl(220., 14., 21.),
+            bg_modal_surface: hsl(220., 14., 21.),
             fg: hsl(0., 0., 100.),
             switch_checked_bg: hsl(126., 50., 47.),
             switch_checked_hover_bg: hsl(127., 51., 38.),
@@ -97,6 +111,120 @@ impl ThemeColors {
     }
 }
 
+impl ThemeColors {
+    /// Returns the name and value of every color, the names are the ones used in theme files.
+    pub fn entries(&self) -> [(&'static str, Hsla); 23] {
+        [
+            ("default", self.default),
+            ("primary", self.primary),
+            ("secondary", self.secondary),
+            ("success", self.success),
+            ("warning", self.warning),
+            ("danger", self.danger),
+            ("border", self.border),
+            ("border_variant", self.border_variant),
+            ("icon_accent", self.icon_accent),
+            ("element_bg", self.element_bg),
+            ("element_hover", self.element_hover),
+            ("text", self.text),
+            ("text_muted", self.text_muted),
+            ("bg", self.bg),
+            ("bg_surface", self.bg_surface),
+            ("bg_editor_surface", self.bg_editor_surface),
+            ("bg_elevated_surface", self.bg_elevated_surface),
+            ("bg_modal_surface", self.bg_modal_surface),
+            ("fg", self.fg),
+            ("switch_checked_bg", self.switch_checked_bg),
+            ("switch_checked_hover_bg", self.switch_checked_hover_bg),
+            ("switch_unchecked_bg", self.switch_unchecked_bg),
+            ("switch_unchecked_hover_bg", self.switch_unchecked_hover_bg),
+        ]
+    }
+
+    /// Returns the name and a mutable reference of every color, see [`ThemeColors::entries`].
+    pub fn entries_mut(&mut self) -> [(&'static str, &mut Hsla); 23] {
+        [
+            ("default", &mut self.default),
+            ("primary", &mut self.primary),
+            ("secondary", &mut self.secondary),
+            ("success", &mut self.success),
+            ("warning", &mut self.warning),
+            ("danger", &mut self.danger),
+            ("border", &mut self.border),
+            ("border_variant", &mut self.border_variant),
+            ("icon_accent", &mut self.icon_accent),
+            ("element_bg", &mut self.element_bg),
+            ("element_hover", &mut self.element_hover),
+            ("text", &mut self.text),
+            ("text_muted", &mut self.text_muted),
+            ("bg", &mut self.bg),
+            ("bg_surface", &mut self.bg_surface),
+            ("bg_editor_surface", &mut self.bg_editor_surface),
+            ("bg_elevated_surface", &mut self.bg_elevated_surface),
+            ("bg_modal_surface", &mut self.bg_modal_surface),
+            ("fg", &mut self.fg),
+            ("switch_checked_bg", &mut self.switch_checked_bg),
+            ("switch_checked_hover_bg", &mut self.switch_checked_hover_bg),
+            ("switch_unchecked_bg", &mut self.switch_unchecked_bg),
+            (
+                "switch_unchecked_hover_bg",
+                &mut self.switch_unchecked_hover_bg,
+            ),
+        ]
+    }
+
+    /// Returns the text and background color pairs whose contrast ratio is below the WCAG AA level.
+    pub fn contrast_issues(&self) -> Vec<ContrastIssue> {
+        [
+            ("text", self.text, "bg", self.bg),
+            ("text_muted", self.text_muted, "bg", self.bg),
+            (
+                "text",
+                self.text,
+                "bg_elevated_surface",
+                self.bg_elevated_surface,
+            ),
+            (
+                "text_muted",
+                self.text_muted,
+                "bg_elevated_surface",
+                self.bg_elevated_surface,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(foreground, fg, background, bg)| {
+            let ratio = contrast_ratio(fg, bg);
+            (ratio < WCAG_AA_CONTRAST).then_some(ContrastIssue {
+                foreground,
+                background,
+                ratio,
+            })
+        })
+        .collect()
+    }
+}
+
+/// A pair of [`ThemeColors`] whose contrast ratio is below the WCAG AA level.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContrastIssue {
+    /// The name of the text color, e.g. `text_muted`.
+    pub foreground: &'static str,
+    /// The name of the background color, e.g. `bg`.
+    pub background: &'static str,
+    /// The contrast ratio between the two colors.
+    pub ratio: f32,
+}
+
+impl fmt::Display for ContrastIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "`{}` on `{}` has a contrast ratio of {:.2}:1, below {WCAG_AA_CONTRAST}:1",
+            self.foreground, self.background, self.ratio
+        )
+    }
+}
+
 impl From<Appearance> for ThemeColors {
     fn from(appearance: Appearance) -> Self {
         match appearance {
@@ -105,3 +233,28 @@ impl From<Appearance> for ThemeColors {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Theme;
+
+    #[test]
+    fn test_contrast_issues() {
+        assert!(ThemeColors::light().contrast_issues().is_empty());
+        assert!(ThemeColors::dark().contrast_issues().is_empty());
+
+        let theme = Theme::from_asset("themes/solarized_dark.json").unwrap();
+        let issues = theme.colors.contrast_issues();
+        assert_eq!(
+            issues
+                .iter()
+                .map(|issue| (issue.foreground, issue.background))
+                .collect::<Vec<_>>(),
+            [("text_muted", "bg"), ("text_muted", "bg_elevated_surface")]
+        );
+        assert!(issues[0]
+            .to_string()
+            .starts_with("`text_muted` on `bg` has a contrast ratio of 3.37:1"));
+    }
+}
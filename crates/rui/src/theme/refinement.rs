@@ -0,0 +1,300 @@
+use gpui::{BoxShadow, Hsla, Pixels, SharedString};
+use serde::{de, Deserialize, Deserializer};
+use smallvec::SmallVec;
+
+use crate::{
+    box_shadow, parse_color, ScrollbarShow, Theme, ThemeColors, ThemeShadows, ThemeStyles,
+};
+
+/// A partial [`Theme`], layered on top of a base theme with [`Theme::refine`].
+///
+/// Every field left to `None` is taken from the base theme.
+///
+/// ```ignore
+/// let theme = Theme::from(Appearance::Dark).refine(&ThemeRefinement {
+///     name: Some("Brand".into()),
+///     colors: ThemeColorsRefinement {
+///         primary: Some(hsl(340., 82., 52.)),
+///         ..Default::default()
+///     },
+///     styles: ThemeStylesRefinement {
+///         radius: Some(px(8.)),
+///         font_family: Some("Inter".into()),
+///         ..Default::default()
+///     },
+///     ..Default::default()
+/// });
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct ThemeRefinement {
+    pub name: Option<SharedString>,
+    pub colors: ThemeColorsRefinement,
+    pub styles: ThemeStylesRefinement,
+    pub shadows: ThemeShadowsRefinement,
+}
+
+impl ThemeRefinement {
+    /// Overrides the fields of `theme` with the ones set in this refinement.
+    pub fn apply_to(&self, theme: &mut Theme) {
+        if let Some(name) = &self.name {
+            theme.name = name.clone();
+        }
+        self.colors.apply_to(&mut theme.colors);
+        self.styles.apply_to(&mut theme.styles);
+        self.shadows.apply_to(&mut theme.shadows);
+    }
+}
+
+/// A partial [`ThemeColors`].
+///
+/// In theme files, colors are written as hex, `rgb()` or `hsl()` strings,
+/// see [`parse_color`] for the supported formats.
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeColorsRefinement {
+    #[serde(deserialize_with = "deserialize_color")]
+    pub default: Option<Hsla>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub primary: Option<Hsla>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub secondary: Option<Hsla>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub success: Option<Hsla>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub warning: Option<Hsla>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub danger: Option<Hsla>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub border: Option<Hsla>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub border_variant: Option<Hsla>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub icon_accent: Option<Hsla>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub element_bg: Option<Hsla>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub element_hover: Option<Hsla>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub text: Option<Hsla>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub text_muted: Option<Hsla>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub bg: Option<Hsla>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub bg_surface: Option<Hsla>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub bg_editor_surface: Option<Hsla>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub bg_elevated_surface: Option<Hsla>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub bg_modal_surface: Option<Hsla>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub fg: Option<Hsla>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub switch_checked_bg: Option<Hsla>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub switch_checked_hover_bg: Option<Hsla>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub switch_unchecked_bg: Option<Hsla>,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub switch_unchecked_hover_bg: Option<Hsla>,
+}
+
+impl ThemeColorsRefinement {
+    /// Overrides the colors of `colors` with the ones set in this refinement.
+    pub fn apply_to(&self, colors: &mut ThemeColors) {
+        let apply = |color: &mut Hsla, refinement: Option<Hsla>| {
+            if let Some(value) = refinement {
+                *color = value;
+            }
+        };
+
+        apply(&mut colors.default, self.default);
+        apply(&mut colors.primary, self.primary);
+        apply(&mut colors.secondary, self.secondary);
+        apply(&mut colors.success, self.success);
+        apply(&mut colors.warning, self.warning);
+        apply(&mut colors.danger, self.danger);
+        apply(&mut colors.border, self.border);
+        apply(&mut colors.border_variant, self.border_variant);
+        apply(&mut colors.icon_accent, self.icon_accent);
+        apply(&mut colors.element_bg, self.element_bg);
+        apply(&mut colors.element_hover, self.element_hover);
+        apply(&mut colors.text, self.text);
+        apply(&mut colors.text_muted, self.text_muted);
+        apply(&mut colors.bg, self.bg);
+        apply(&mut colors.bg_surface, self.bg_surface);
+        apply(&mut colors.bg_editor_surface, self.bg_editor_surface);
+        apply(&mut colors.bg_elevated_surface, self.bg_elevated_surface);
+        apply(&mut colors.bg_modal_surface, self.bg_modal_surface);
+        apply(&mut colors.fg, self.fg);
+        apply(&mut colors.switch_checked_bg, self.switch_checked_bg);
+        apply(
+            &mut colors.switch_checked_hover_bg,
+            self.switch_checked_hover_bg,
+        );
+        apply(&mut colors.switch_unchecked_bg, self.switch_unchecked_bg);
+        apply(
+            &mut colors.switch_unchecked_hover_bg,
+            self.switch_unchecked_hover_bg,
+        );
+    }
+}
+
+/// A partial [`ThemeStyles`], sizes are in pixels in theme files.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeStylesRefinement {
+    pub font_family: Option<SharedString>,
+    pub font_size: Option<Pixels>,
+    pub radius: Option<Pixels>,
+    pub shadow: Option<bool>,
+    pub scrollbar_show: Option<ScrollbarShow>,
+    pub tile_grid_size: Option<Pixels>,
+    pub tile_shadow: Option<bool>,
+}
+
+impl ThemeStylesRefinement {
+    /// Overrides the styles of `styles` with the ones set in this refinement.
+    pub fn apply_to(&self, styles: &mut ThemeStyles) {
+        if let Some(font_family) = &self.font_family {
+            styles.font_family = font_family.clone();
+        }
+        if let Some(font_size) = self.font_size {
+            styles.font_size = font_size;
+        }
+        if let Some(radius) = self.radius {
+            styles.radius = radius;
+        }
+        if let Some(shadow) = self.shadow {
+            styles.shadow = shadow;
+        }
+        if let Some(scrollbar_show) = self.scrollbar_show {
+            styles.scrollbar_show = scrollbar_show;
+        }
+        if let Some(tile_grid_size) = self.tile_grid_size {
+            styles.tile_grid_size = tile_grid_size;
+        }
+        if let Some(tile_shadow) = self.tile_shadow {
+            styles.tile_shadow = tile_shadow;
+        }
+    }
+}
+
+/// A partial [`ThemeShadows`], each elevation level replaces all the shadows of the base theme.
+///
+/// In theme files, a shadow is written like the arguments of [`box_shadow`], sizes are in pixels
+/// and default to 0: `{ "y": 2, "blur": 3, "color": "rgba(0, 0, 0, 0.12)" }`.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeShadowsRefinement {
+    #[serde(deserialize_with = "deserialize_shadows")]
+    pub surface: Option<SmallVec<[BoxShadow; 2]>>,
+    #[serde(deserialize_with = "deserialize_shadows")]
+    pub elevated_surface: Option<SmallVec<[BoxShadow; 2]>>,
+    #[serde(deserialize_with = "deserialize_shadows")]
+    pub modal_surface: Option<SmallVec<[BoxShadow; 2]>>,
+}
+
+impl ThemeShadowsRefinement {
+    /// Overrides the shadows of `shadows` with the ones set in this refinement.
+    pub fn apply_to(&self, shadows: &mut ThemeShadows) {
+        if let Some(surface) = &self.surface {
+            shadows.surface = surface.clone();
+        }
+        if let Some(elevated_surface) = &self.elevated_surface {
+            shadows.elevated_surface = elevated_surface.clone();
+        }
+        if let Some(modal_surface) = &self.modal_surface {
+            shadows.modal_surface = modal_surface.clone();
+        }
+    }
+}
+
+fn deserialize_color<'de, D>(deserializer: D) -> Result<Option<Hsla>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    parse_color(&value)
+        .map(Some)
+        .map_err(|err| de::Error::custom(format!("invalid color: {err}")))
+}
+
+fn deserialize_shadows<'de, D>(
+    deserializer: D,
+) -> Result<Option<SmallVec<[BoxShadow; 2]>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct ShadowContent {
+        #[serde(default)]
+        x: f32,
+        #[serde(default)]
+        y: f32,
+        #[serde(default)]
+        blur: f32,
+        #[serde(default)]
+        spread: f32,
+        color: String,
+    }
+
+    Vec::<ShadowContent>::deserialize(deserializer)?
+        .into_iter()
+        .map(|shadow| {
+            let color = parse_color(&shadow.color)
+                .map_err(|err| de::Error::custom(format!("invalid color: {err}")))?;
+            Ok(box_shadow(
+                shadow.x,
+                shadow.y,
+                shadow.blur,
+                shadow.spread,
+                color,
+            ))
+        })
+        .collect::<Result<_, _>>()
+        .map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hsl, Appearance};
+    use gpui::px;
+
+    #[test]
+    fn test_refine_theme() {
+        let base = Theme::from(Appearance::Dark);
+        let theme = base.refine(&ThemeRefinement {
+            name: Some("Brand".into()),
+            colors: ThemeColorsRefinement {
+                primary: Some(hsl(340., 82., 52.)),
+                ..Default::default()
+            },
+            styles: ThemeStylesRefinement {
+                radius: Some(px(8.)),
+                font_family: Some("Inter".into()),
+                ..Default::default()
+            },
+            shadows: ThemeShadowsRefinement {
+                modal_surface: Some(SmallVec::new()),
+                ..Default::default()
+            },
+        });
+
+        assert_eq!(theme.name, "Brand");
+        assert_eq!(theme.appearance, Appearance::Dark);
+        assert_eq!(theme.colors.primary, hsl(340., 82., 52.));
+        assert_eq!(theme.colors.bg, base.colors.bg);
+        assert_eq!(theme.styles.radius, px(8.));
+        assert_eq!(theme.styles.font_family, "Inter");
+        assert_eq!(theme.styles.font_size, base.styles.font_size);
+        assert!(theme.shadows.modal_surface.is_empty());
+        assert_eq!(
+            theme.shadows.elevated_surface.len(),
+            base.shadows.elevated_surface.len()
+        );
+    }
+}
@@ -0,0 +1,409 @@
+use std::{collections::HashSet, path::Path, sync::Arc};
+
+use anyhow::{anyhow, Error, Result};
+use gpui::{App, Global, SharedString};
+
+use super::schema::builtin_theme;
+use crate::{Appearance, ScrollbarShow, Theme, ThemeContent, ThemeRefinement};
+
+/// The name of the family holding the built-in light and dark themes.
+pub const BUILTIN_THEME_FAMILY: &str = "Default";
+
+/// A family of themes, made of a light and a dark variant.
+///
+/// A family may only have one of the two variants.
+#[derive(Debug, Clone)]
+pub struct ThemeFamily {
+    /// The name of the family.
+    pub name: SharedString,
+    /// The light variant of the family.
+    pub light: Option<Arc<Theme>>,
+    /// The dark variant of the family.
+    pub dark: Option<Arc<Theme>>,
+}
+
+impl ThemeFamily {
+    /// Creates an empty [`ThemeFamily`].
+    pub fn new(name: impl Into<SharedString>) -> Self {
+        Self {
+            name: name.into(),
+            light: None,
+            dark: None,
+        }
+    }
+
+    /// Creates the family of the built-in light and dark themes.
+    pub fn builtin(scrollbar_show: ScrollbarShow) -> Self {
+        let mut family = Self::new(BUILTIN_THEME_FAMILY);
+        for appearance in [Appearance::Light, Appearance::Dark] {
+            let mut theme = Theme::from(appearance);
+            theme.styles.scrollbar_show = scrollbar_show;
+            family.set_variant(Arc::new(theme));
+        }
+        family
+    }
+
+    /// Returns whether this is the family of the built-in themes.
+    pub fn is_builtin(&self) -> bool {
+        self.name == BUILTIN_THEME_FAMILY
+    }
+
+    /// Returns the variant of the given appearance, if any.
+    pub fn variant(&self, appearance: Appearance) -> Option<&Arc<Theme>> {
+        match appearance {
+            Appearance::Light => self.light.as_ref(),
+            Appearance::Dark => self.dark.as_ref(),
+        }
+    }
+
+    /// Returns the variant of the given appearance,
+    /// or the other variant if this family doesn't have it.
+    pub fn variant_or_any(&self, appearance: Appearance) -> Option<&Arc<Theme>> {
+        self.variant(appearance)
+            .or_else(|| self.light.as_ref())
+            .or_else(|| self.dark.as_ref())
+    }
+
+    /// Sets the variant matching the appearance of the theme, replacing any previous one.
+    pub fn set_variant(&mut self, theme: Arc<Theme>) {
+        match theme.appearance {
+            Appearance::Light => self.light = Some(theme),
+            Appearance::Dark => self.dark = Some(theme),
+        }
+    }
+
+    /// Returns the themes of this family, light first.
+    pub fn themes(&self) -> impl Iterator<Item = &Arc<Theme>> {
+        self.light.iter().chain(self.dark.iter())
+    }
+
+    /// Returns the theme with the given name, if it belongs to this family.
+    pub fn get(&self, name: &str) -> Option<&Arc<Theme>> {
+        self.themes().find(|theme| theme.name == name)
+    }
+}
+
+/// The registry of all the named themes available to the application.
+///
+/// Themes are grouped by [`ThemeFamily`], in registration order.
+/// Use [`ThemeSetting::set_theme`](crate::ThemeSetting::set_theme) to switch to a registered theme.
+#[derive(Debug, Clone, Default)]
+pub struct ThemeRegistry {
+    families: Vec<ThemeFamily>,
+}
+
+impl Global for ThemeRegistry {}
+
+impl ThemeRegistry {
+    /// Returns the global theme registry reference
+    pub fn global(cx: &App) -> &ThemeRegistry {
+        cx.global::<ThemeRegistry>()
+    }
+
+    /// Returns the global theme registry mutable reference
+    pub fn global_mut(cx: &mut App) -> &mut ThemeRegistry {
+        cx.global_mut::<ThemeRegistry>()
+    }
+
+    /// Initializes the global [`ThemeRegistry`] with the built-in and bundled themes,
+    /// unless it has already been initialized.
+    pub fn init(cx: &mut App) {
+        if cx.has_global::<ThemeRegistry>() {
+            return;
+        }
+
+        let scrollbar_show = if cx.should_auto_hide_scrollbars() {
+            ScrollbarShow::Scrolling
+        } else {
+            ScrollbarShow::Always
+        };
+        let mut registry = ThemeRegistry::default();
+        registry.register_family(ThemeFamily::builtin(scrollbar_show));
+        // The bundled themes that can't be loaded are skipped, the others are still registered.
+        if let Err(err) = registry.load_bundled() {
+            log::error!("{err:#}");
+        }
+
+        cx.set_global(registry);
+    }
+
+    /// Returns all the registered families, in registration order.
+    pub fn families(&self) -> &[ThemeFamily] {
+        &self.families
+    }
+
+    /// Returns the family with the given name, if any.
+    pub fn family(&self, name: &str) -> Option<&ThemeFamily> {
+        self.families.iter().find(|family| family.name == name)
+    }
+
+    /// Returns the family the theme with the given name belongs to, if any.
+    pub fn family_of(&self, theme_name: &str) -> Option<&ThemeFamily> {
+        self.families
+            .iter()
+            .find(|family| family.get(theme_name).is_some())
+    }
+
+    /// Returns the names of all the registered themes.
+    pub fn theme_names(&self) -> impl Iterator<Item = &SharedString> {
+        self.themes().map(|theme| &theme.name)
+    }
+
+    /// Returns all the registered themes.
+    pub fn themes(&self) -> impl Iterator<Item = &Arc<Theme>> {
+        self.families.iter().flat_map(ThemeFamily::themes)
+    }
+
+    /// Returns the theme with the given name, if any.
+    pub fn get(&self, name: &str) -> Option<&Arc<Theme>> {
+        self.themes().find(|theme| theme.name == name)
+    }
+
+    /// Registers a theme as the variant of the given family.
+    ///
+    /// The family is created if needed, and an existing variant
+    /// of the same appearance is replaced.
+    pub fn register(&mut self, family: impl Into<SharedString>, theme: Theme) -> Arc<Theme> {
+        let family = family.into();
+        let theme = Arc::new(theme);
+        match self.families.iter_mut().find(|f| f.name == family) {
+            Some(family) => family.set_variant(theme.clone()),
+            None => {
+                let mut family = ThemeFamily::new(family);
+                family.set_variant(theme.clone());
+                self.families.push(family);
+            }
+        }
+        theme
+    }
+
+    /// Registers a whole family, replacing any family of the same name.
+    pub fn register_family(&mut self, family: ThemeFamily) {
+        match self.families.iter_mut().find(|f| f.name == family.name) {
+            Some(existing) => *existing = family,
+            None => self.families.push(family),
+        }
+    }
+
+    /// Resolves and registers the theme of a theme file content, see [`ThemeContent::family`].
+    ///
+    /// The theme it `extends`, if any, must already be registered.
+    pub fn register_content(&mut self, content: ThemeContent) -> Result<Arc<Theme>> {
+        let family = SharedString::from(content.family_name().to_string());
+        let theme = content.resolve(self)?;
+        Ok(self.register(family, theme))
+    }
+
+    /// Returns a copy of the registered theme `base` with the fields set in `refinement` overridden.
+    pub fn refine(&self, base: &str, refinement: &ThemeRefinement) -> Result<Theme> {
+        let base = self
+            .get(base)
+            .ok_or_else(|| anyhow!("theme \"{base}\" is not registered"))?;
+        Ok(base.refine(refinement))
+    }
+
+    /// Loads and registers a `.json` or `.toml` theme file.
+    pub fn load_file(&mut self, path: impl AsRef<Path>) -> Result<Arc<Theme>> {
+        self.register_content(ThemeContent::from_file(path)?)
+    }
+
+    /// Loads and registers all the themes bundled in [`Assets`](crate::Assets) under `themes/`.
+    ///
+    /// Themes extending other bundled themes are registered after them. A theme that can't be
+    /// loaded is skipped without preventing the others from being registered, and the errors
+    /// are returned together.
+    pub fn load_bundled(&mut self) -> Result<()> {
+        let mut errors = Vec::new();
+        let mut contents = Vec::new();
+        for content in ThemeContent::bundled() {
+            match content {
+                Ok(content) => contents.push(content),
+                Err(err) => errors.push(err),
+            }
+        }
+
+        let (ordered, unresolved) = order_by_extends(contents, |name| {
+            self.get(name).is_some() || builtin_theme(name).is_some()
+        });
+        errors.extend(unresolved);
+        for content in ordered {
+            if let Err(err) = self.register_content(content) {
+                errors.push(err);
+            }
+        }
+
+        if errors.is_empty() {
+            return Ok(());
+        }
+        let errors: Vec<String> = errors.iter().map(|err| format!("{err:#}")).collect();
+        Err(anyhow!(
+            "could not load {} bundled theme(s): {}",
+            errors.len(),
+            errors.join("; ")
+        ))
+    }
+}
+
+/// Orders theme contents so that each one comes after the theme it extends.
+///
+/// `is_known` tells whether a theme outside of `contents` can be extended. The contents
+/// extending a missing theme, or part of a cycle, are returned as errors.
+fn order_by_extends(
+    mut pending: Vec<ThemeContent>,
+    is_known: impl Fn(&str) -> bool,
+) -> (Vec<ThemeContent>, Vec<Error>) {
+    let mut ordered = Vec::with_capacity(pending.len());
+    let mut resolved = HashSet::new();
+    loop {
+        let (ready, rest): (Vec<_>, Vec<_>) = pending.into_iter().partition(|content| {
+            content
+                .extends
+                .as_deref()
+                .is_none_or(|extends| resolved.contains(extends) || is_known(extends))
+        });
+        pending = rest;
+        if ready.is_empty() {
+            break;
+        }
+        resolved.extend(ready.iter().map(|content| content.name.clone()));
+        ordered.extend(ready);
+    }
+
+    // The bases of the remaining contents are either missing or never resolved because of a
+    // cycle, follow the chain of `extends` to tell which.
+    let errors = pending
+        .iter()
+        .map(|content| {
+            let mut chain = vec![content.name.as_str()];
+            let mut current = content;
+            loop {
+                let extends = current.extends.as_deref().unwrap_or_default();
+                if chain.contains(&extends) {
+                    chain.push(extends);
+                    return anyhow!(
+                        "theme \"{}\" has a cycle of `extends`: {}",
+                        content.name,
+                        chain.join(" -> ")
+                    );
+                }
+                match pending.iter().find(|theme| theme.name == extends) {
+                    Some(base) => {
+                        chain.push(extends);
+                        current = base;
+                    }
+                    None => {
+                        return anyhow!(
+                            "theme \"{}\" extends unknown theme \"{extends}\"",
+                            current.name
+                        )
+                    }
+                }
+            }
+        })
+        .collect();
+
+    (ordered, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_theme_family() {
+        let mut registry = ThemeRegistry::default();
+        registry.register_family(ThemeFamily::builtin(ScrollbarShow::Always));
+
+        let mut light = Theme::from(Appearance::Light);
+        light.name = "Paper".into();
+        registry.register("Paper", light);
+        let mut dark = Theme::from(Appearance::Dark);
+        dark.name = "Ink".into();
+        registry.register("Paper", dark);
+
+        assert_eq!(registry.families().len(), 2);
+        assert_eq!(
+            registry.theme_names().collect::<Vec<_>>(),
+            ["Light", "Dark", "Paper", "Ink"]
+        );
+
+        let family = registry.family_of("Ink").unwrap();
+        assert_eq!(family.name, "Paper");
+        assert_eq!(family.variant(Appearance::Light).unwrap().name, "Paper");
+        assert!(registry.family(BUILTIN_THEME_FAMILY).unwrap().is_builtin());
+        assert!(registry.get("Nope").is_none());
+    }
+
+    #[test]
+    fn test_register_replaces_variant() {
+        let mut registry = ThemeRegistry::default();
+        let mut theme = Theme::from(Appearance::Dark);
+        theme.name = "Night".into();
+        registry.register("Night", theme.clone());
+        theme.name = "Midnight".into();
+        registry.register("Night", theme);
+
+        let family = registry.family("Night").unwrap();
+        assert!(family.light.is_none());
+        assert_eq!(family.dark.as_ref().unwrap().name, "Midnight");
+        assert_eq!(
+            family.variant_or_any(Appearance::Light).unwrap().name,
+            "Midnight"
+        );
+    }
+
+    #[test]
+    fn test_order_by_extends() {
+        let content = |name: &str, extends: &str| {
+            let json = format!(r#"{{ "name": "{name}", "extends": "{extends}" }}"#);
+            ThemeContent::parse(&json, crate::ThemeFormat::Json).unwrap()
+        };
+        let names = |contents: &[ThemeContent]| {
+            contents
+                .iter()
+                .map(|content| content.name.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let (ordered, errors) = order_by_extends(
+            vec![
+                content("Round", "Brand"),
+                content("Brand", "Dark"),
+                content("Rounder", "Round"),
+            ],
+            |name| name == "Dark",
+        );
+        assert_eq!(names(&ordered), ["Brand", "Round", "Rounder"]);
+        assert!(errors.is_empty());
+
+        let (ordered, errors) = order_by_extends(
+            vec![
+                content("A", "B"),
+                content("B", "A"),
+                content("C", "Nope"),
+                content("D", "Dark"),
+            ],
+            |name| name == "Dark",
+        );
+        assert_eq!(names(&ordered), ["D"]);
+        let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            errors,
+            [
+                "theme \"A\" has a cycle of `extends`: A -> B -> A",
+                "theme \"B\" has a cycle of `extends`: B -> A -> B",
+                "theme \"C\" extends unknown theme \"Nope\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load_bundled_themes() {
+        let mut registry = ThemeRegistry::default();
+        registry.load_bundled().unwrap();
+
+        let family = registry.family("Solarized").unwrap();
+        assert_eq!(family.light.as_ref().unwrap().name, "Solarized Light");
+        assert_eq!(family.dark.as_ref().unwrap().name, "Solarized Dark");
+    }
+}
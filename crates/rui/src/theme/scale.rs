@@ -0,0 +1,183 @@
+use gpui::Hsla;
+
+use crate::Appearance;
+
+/// The saturation factor and lightness of the background and border steps (1 to 8) of a light scale.
+const LIGHT_STEPS: [(f32, f32); 8] = [
+    (0.6, 0.99),
+    (0.7, 0.975),
+    (0.8, 0.95),
+    (0.85, 0.92),
+    (0.85, 0.885),
+    (0.8, 0.84),
+    (0.75, 0.78),
+    (0.75, 0.7),
+];
+
+/// The saturation factor and lightness of the background and border steps (1 to 8) of a dark scale.
+const DARK_STEPS: [(f32, f32); 8] = [
+    (0.3, 0.07),
+    (0.35, 0.09),
+    (0.45, 0.13),
+    (0.5, 0.16),
+    (0.55, 0.195),
+    (0.55, 0.235),
+    (0.55, 0.29),
+    (0.6, 0.37),
+];
+
+/// A 12-step color scale generated from a base color.
+///
+/// Each step has a use case, like the [Radix color scales](https://www.radix-ui.com/colors/docs/palette-composition/understanding-the-scale):
+///
+/// - 1-2: backgrounds
+/// - 3-5: component backgrounds, at rest, hovered and pressed or selected
+/// - 6-8: borders, subtle, at rest and hovered or focused
+/// - 9-10: solid backgrounds, at rest and hovered, step 9 is the base color
+/// - 11-12: text, low and high contrast
+///
+/// The steps are lighter than the base color in a light appearance and darker in a dark one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorScale {
+    steps: [Hsla; 12],
+}
+
+impl ColorScale {
+    /// Generates the scale of `base` for the given appearance.
+    pub fn new(base: Hsla, appearance: Appearance) -> Self {
+        let tint = |saturation: f32, lightness: f32| Hsla {
+            h: base.h,
+            s: (base.s * saturation).clamp(0., 1.),
+            l: lightness,
+            a: base.a,
+        };
+        let (backgrounds, solid_hover, text, high_contrast_text) = match appearance {
+            Appearance::Light => (
+                LIGHT_STEPS,
+                (base.l - 0.05).max(0.),
+                tint(0.9, 0.42),
+                tint(0.7, 0.18),
+            ),
+            Appearance::Dark => (
+                DARK_STEPS,
+                (base.l + 0.05).min(1.),
+                tint(0.9, 0.72),
+                tint(0.6, 0.92),
+            ),
+        };
+
+        let mut steps = [base; 12];
+        for (step, (saturation, lightness)) in steps.iter_mut().zip(backgrounds) {
+            *step = tint(saturation, lightness);
+        }
+        steps[9] = Hsla {
+            l: solid_hover,
+            ..base
+        };
+        steps[10] = text;
+        steps[11] = high_contrast_text;
+
+        Self { steps }
+    }
+
+    /// Returns the given step of the scale, from 1 to 12.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the step is not in `1..=12`.
+    pub fn step(&self, step: usize) -> Hsla {
+        assert!(
+            (1..=12).contains(&step),
+            "color scale step {step} is not in 1..=12"
+        );
+        self.steps[step - 1]
+    }
+
+    /// Step 1, the background of an app.
+    pub fn bg(&self) -> Hsla {
+        self.step(1)
+    }
+
+    /// Step 2, a subtle background, e.g. striped tables or cards.
+    pub fn subtle_bg(&self) -> Hsla {
+        self.step(2)
+    }
+
+    /// Step 3, the background of a component at rest.
+    pub fn element_bg(&self) -> Hsla {
+        self.step(3)
+    }
+
+    /// Step 4, the background of a hovered component.
+    pub fn hover_bg(&self) -> Hsla {
+        self.step(4)
+    }
+
+    /// Step 5, the background of a pressed or selected component.
+    pub fn active_bg(&self) -> Hsla {
+        self.step(5)
+    }
+
+    /// Step 6, a subtle border for non-interactive components.
+    pub fn subtle_border(&self) -> Hsla {
+        self.step(6)
+    }
+
+    /// Step 7, the border of an interactive component.
+    pub fn border(&self) -> Hsla {
+        self.step(7)
+    }
+
+    /// Step 8, the border of a hovered or focused interactive component.
+    pub fn hover_border(&self) -> Hsla {
+        self.step(8)
+    }
+
+    /// Step 9, the solid background, this is the base color.
+    pub fn solid(&self) -> Hsla {
+        self.step(9)
+    }
+
+    /// Step 10, the solid background of a hovered component.
+    pub fn solid_hover(&self) -> Hsla {
+        self.step(10)
+    }
+
+    /// Step 11, low contrast text.
+    pub fn text(&self) -> Hsla {
+        self.step(11)
+    }
+
+    /// Step 12, high contrast text.
+    pub fn high_contrast_text(&self) -> Hsla {
+        self.step(12)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hsl;
+
+    #[test]
+    fn test_color_scale() {
+        let base = hsl(212., 100., 47.);
+
+        let light = ColorScale::new(base, Appearance::Light);
+        assert_eq!(light.solid(), base);
+        assert!(light.solid_hover().l < base.l);
+        for step in 1..8 {
+            assert!(light.step(step).l > light.step(step + 1).l);
+        }
+        assert!(light.text().l > light.high_contrast_text().l);
+
+        let dark = ColorScale::new(base, Appearance::Dark);
+        assert_eq!(dark.solid(), base);
+        assert!(dark.solid_hover().l > base.l);
+        for step in 1..8 {
+            assert!(dark.step(step).l < dark.step(step + 1).l);
+        }
+        assert!(dark.text().l < dark.high_contrast_text().l);
+        assert!(dark.steps.iter().all(|step| step.h == base.h));
+    }
+}
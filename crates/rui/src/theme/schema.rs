@@ -0,0 +1,325 @@
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context as _, Result};
+use serde::Deserialize;
+
+use crate::{
+    Appearance, Assets, Theme, ThemeColorsRefinement, ThemeRefinement, ThemeRegistry,
+    ThemeShadowsRefinement, ThemeStylesRefinement,
+};
+
+/// The file format of a theme file.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ThemeFormat {
+    Json,
+    Toml,
+}
+
+impl ThemeFormat {
+    /// Returns the format matching the extension of the given path, if any.
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?;
+        match extension.to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "toml" => Some(Self::Toml),
+            _ => None,
+        }
+    }
+}
+
+/// The content of a theme file.
+///
+/// Every color and style is optional, anything left out falls back to
+/// the built-in theme of the same [`Appearance`], or to the theme named by `extends`.
+/// The `appearance` may be omitted when extending a theme.
+///
+/// Themes sharing the same `family` are registered as the light and dark
+/// variants of one [`ThemeFamily`](crate::ThemeFamily).
+///
+/// ```json
+/// {
+///   "name": "Ocean",
+///   "family": "Ocean",
+///   "appearance": "dark",
+///   "colors": { "primary": "#0070f3", "bg": "hsl(220, 20%, 10%)" },
+///   "styles": { "radius": 6 },
+///   "shadows": { "modal_surface": [{ "y": 4, "blur": 12, "color": "rgba(0, 0, 0, 0.3)" }] }
+/// }
+/// ```
+///
+/// ```toml
+/// name = "Brand"
+/// extends = "Dark"
+///
+/// [colors]
+/// primary = "#e91e63"
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ThemeContent {
+    pub name: String,
+    pub family: Option<String>,
+    /// The name of the theme this one is layered on top of,
+    /// either a registered theme or the built-in `Light` and `Dark` themes.
+    pub extends: Option<String>,
+    pub appearance: Option<AppearanceContent>,
+    #[serde(default)]
+    pub colors: ThemeColorsRefinement,
+    #[serde(default)]
+    pub styles: ThemeStylesRefinement,
+    #[serde(default)]
+    pub shadows: ThemeShadowsRefinement,
+}
+
+impl ThemeContent {
+    /// Parses the content of a theme file in the given format.
+    pub fn parse(content: &str, format: ThemeFormat) -> Result<Self> {
+        match format {
+            ThemeFormat::Json => serde_json::from_str(content).context("invalid JSON theme"),
+            ThemeFormat::Toml => toml::from_str(content).context("invalid TOML theme"),
+        }
+    }
+
+    /// Reads the content of a `.json` or `.toml` theme file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let format = ThemeFormat::from_path(path)
+            .ok_or_else(|| anyhow!("unsupported theme file \"{}\"", path.display()))?;
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("could not read theme file \"{}\"", path.display()))?;
+
+        Self::parse(&content, format)
+            .with_context(|| format!("could not load theme file \"{}\"", path.display()))
+    }
+
+    /// Reads the content of a theme file bundled in [`Assets`], e.g. `themes/solarized_dark.json`.
+    pub fn from_asset(path: &str) -> Result<Self> {
+        let format = ThemeFormat::from_path(path)
+            .ok_or_else(|| anyhow!("unsupported theme asset \"{path}\""))?;
+        let asset =
+            Assets::get(path).ok_or_else(|| anyhow!("could not find theme asset \"{path}\""))?;
+        let content = std::str::from_utf8(&asset.data)
+            .with_context(|| format!("theme asset \"{path}\" is not valid UTF-8"))?;
+
+        Self::parse(content, format)
+            .with_context(|| format!("could not load theme asset \"{path}\""))
+    }
+
+    /// Reads all the theme files bundled in [`Assets`] under `themes/`.
+    ///
+    /// Each file is read on its own, so a bad file doesn't prevent reading the others.
+    pub fn bundled() -> Vec<Result<Self>> {
+        Assets::iter()
+            .filter(|path| path.starts_with("themes/") && ThemeFormat::from_path(&**path).is_some())
+            .map(|path| Self::from_asset(&path))
+            .collect()
+    }
+
+    /// Returns the name of the family this theme belongs to.
+    ///
+    /// Defaults to the theme name when no family is set.
+    pub fn family_name(&self) -> &str {
+        self.family.as_deref().unwrap_or(&self.name)
+    }
+
+    /// Resolves the [`Theme`] of this content.
+    ///
+    /// `extends` is looked up in the registry first,
+    /// then in the built-in `Light` and `Dark` themes.
+    pub fn resolve(self, registry: &ThemeRegistry) -> Result<Theme> {
+        let appearance = self.appearance.map(Appearance::from);
+        let base = match &self.extends {
+            Some(extends) => {
+                let base = registry
+                    .get(extends)
+                    .map(|theme| (**theme).clone())
+                    .or_else(|| builtin_theme(extends))
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "theme \"{}\" extends unknown theme \"{extends}\"",
+                            self.name
+                        )
+                    })?;
+                if appearance.is_some_and(|appearance| appearance != base.appearance) {
+                    bail!(
+                        "theme \"{}\" can't change the appearance of the theme \"{extends}\" it extends",
+                        self.name
+                    );
+                }
+                base
+            }
+            None => match appearance {
+                Some(appearance) => Theme::from(appearance),
+                None => bail!("theme \"{}\" must set `appearance` or `extends`", self.name),
+            },
+        };
+
+        Ok(base.refine(&ThemeRefinement {
+            name: Some(self.name.into()),
+            colors: self.colors,
+            styles: self.styles,
+            shadows: self.shadows,
+        }))
+    }
+}
+
+pub(crate) fn builtin_theme(name: &str) -> Option<Theme> {
+    [Appearance::Light, Appearance::Dark]
+        .into_iter()
+        .find(|appearance| appearance.to_string() == name)
+        .map(Theme::from)
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AppearanceContent {
+    Light,
+    Dark,
+}
+
+impl From<AppearanceContent> for Appearance {
+    fn from(appearance: AppearanceContent) -> Self {
+        match appearance {
+            AppearanceContent::Light => Appearance::Light,
+            AppearanceContent::Dark => Appearance::Dark,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hsl, ScrollbarShow, ThemeColors};
+    use gpui::px;
+
+    #[test]
+    fn test_parse_json_theme() {
+        let theme: Theme = ThemeContent::parse(
+            r##"{
+                "name": "Ocean",
+                "appearance": "dark",
+                "colors": { "primary": "#0070f3", "bg": "hsl(220, 20%, 10%)" },
+                "styles": { "radius": 6, "scrollbar_show": "always" },
+                "shadows": { "elevated_surface": [{ "y": 4, "blur": 12, "color": "#0000004d" }] }
+            }"##,
+            ThemeFormat::Json,
+        )
+        .unwrap()
+        .resolve(&ThemeRegistry::default())
+        .unwrap();
+
+        assert_eq!(theme.name, "Ocean");
+        assert_eq!(theme.appearance, Appearance::Dark);
+        assert_eq!(theme.colors.primary, crate::rgb(0, 112, 243).into());
+        assert_eq!(theme.colors.bg, hsl(220., 20., 10.));
+        assert_eq!(theme.colors.text, ThemeColors::dark().text);
+        assert_eq!(theme.styles.radius, px(6.));
+        assert_eq!(theme.styles.scrollbar_show, ScrollbarShow::Always);
+        assert_eq!(theme.shadows.elevated_surface.len(), 1);
+        assert_eq!(theme.shadows.elevated_surface[0].offset.y, px(4.));
+        assert_eq!(theme.shadows.elevated_surface[0].blur_radius, px(12.));
+        assert_eq!(theme.shadows.modal_surface.len(), 4);
+    }
+
+    #[test]
+    fn test_parse_toml_theme() {
+        let theme: Theme = ThemeContent::parse(
+            r##"
+            name = "Paper"
+            appearance = "light"
+
+            [colors]
+            primary = "rgb(0, 112, 243)"
+
+            [styles]
+            font_family = "Inter"
+            "##,
+            ThemeFormat::Toml,
+        )
+        .unwrap()
+        .resolve(&ThemeRegistry::default())
+        .unwrap();
+
+        assert_eq!(theme.appearance, Appearance::Light);
+        assert_eq!(theme.colors.primary, crate::rgb(0, 112, 243).into());
+        assert_eq!(theme.styles.font_family, "Inter");
+    }
+
+    #[test]
+    fn test_parse_theme_errors() {
+        let err = ThemeContent::parse(
+            r##"{ "name": "Ocean", "appearance": "dark", "colors": { "primry": "#0070f3" } }"##,
+            ThemeFormat::Json,
+        )
+        .unwrap_err();
+        assert!(format!("{err:#}").contains("unknown field `primry`"));
+
+        let err = ThemeContent::parse(
+            "name = \"Ocean\"\nappearance = \"dark\"\n[colors]\nprimary = \"#0070zz\"",
+            ThemeFormat::Toml,
+        )
+        .unwrap_err();
+        assert!(format!("{err:#}").contains("invalid color"));
+        assert!(format!("{err:#}").contains("line 4"));
+    }
+
+    #[test]
+    fn test_extends_theme() {
+        let theme = ThemeContent::parse(
+            "name = \"Brand\"\nextends = \"Dark\"\n[colors]\nprimary = \"#e91e63\"",
+            ThemeFormat::Toml,
+        )
+        .unwrap()
+        .resolve(&ThemeRegistry::default())
+        .unwrap();
+        assert_eq!(theme.name, "Brand");
+        assert_eq!(theme.appearance, Appearance::Dark);
+        assert_eq!(theme.colors.primary, crate::rgb(233, 30, 99).into());
+        assert_eq!(theme.colors.bg, ThemeColors::dark().bg);
+
+        let mut registry = ThemeRegistry::default();
+        registry.load_bundled().unwrap();
+        let theme = ThemeContent::parse(
+            r##"{ "name": "Solarized Round", "extends": "Solarized Dark", "styles": { "radius": 12 } }"##,
+            ThemeFormat::Json,
+        )
+        .unwrap()
+        .resolve(&registry)
+        .unwrap();
+        assert_eq!(theme.colors.bg, crate::rgb(0, 43, 54).into());
+        assert_eq!(theme.styles.radius, px(12.));
+
+        let err = ThemeContent::parse(
+            r##"{ "name": "Orphan", "extends": "Nope" }"##,
+            ThemeFormat::Json,
+        )
+        .unwrap()
+        .resolve(&registry)
+        .unwrap_err();
+        assert!(err.to_string().contains("unknown theme \"Nope\""));
+
+        let err = ThemeContent::parse(
+            r##"{ "name": "Flipped", "extends": "Dark", "appearance": "light" }"##,
+            ThemeFormat::Json,
+        )
+        .unwrap()
+        .resolve(&registry)
+        .unwrap_err();
+        assert!(err.to_string().contains("can't change the appearance"));
+
+        let err = ThemeContent::parse(r##"{ "name": "Nothing" }"##, ThemeFormat::Json)
+            .unwrap()
+            .resolve(&registry)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("must set `appearance` or `extends`"));
+    }
+
+    #[test]
+    fn test_bundled_themes() {
+        let themes = Theme::bundled().unwrap();
+        assert!(themes.iter().any(|theme| theme.name == "Solarized Dark"));
+        assert!(themes.iter().any(|theme| theme.name == "Solarized Light"));
+    }
+}
@@ -1,14 +1,18 @@
 use std::sync::Arc;
 
-use crate::{Appearance, ScrollbarShow, Theme};
+use anyhow::{anyhow, Result};
 use gpui::{App, Global, Window};
 
+use crate::{Appearance, ScrollbarShow, Theme, ThemeFamily, ThemeRegistry, BUILTIN_THEME_FAMILY};
+
 /// Customizable settings for the UI and theme system.
 #[derive(Clone)]
 pub struct ThemeSetting {
     pub active_theme: Arc<Theme>,
     pub theme_mode: ThemeMode,
     pub theme_type: ThemeType,
+    /// The [`ThemeFamily`] of the active theme, used to switch between its light and dark variants.
+    pub theme_family: Option<ThemeFamily>,
 }
 
 impl Global for ThemeSetting {}
@@ -30,68 +34,177 @@ impl ThemeSetting {
             active_theme: theme,
             theme_mode,
             theme_type: ThemeType::Custom,
+            theme_family: None,
         });
     }
 
-    /// Toggles the builtin theme current appearance between light and dark.
-    /// Updates the color settings based on the new appearance
+    /// Switches to the theme registered under `name` in the [`ThemeRegistry`]
+    /// and refreshes all the windows.
+    ///
+    /// The theme mode follows the appearance of the theme.
+    pub fn set_theme(cx: &mut App, name: &str) -> Result<()> {
+        let (family, theme) = ThemeRegistry::global(cx)
+            .families()
+            .iter()
+            .find_map(|family| Some((family.clone(), family.get(name)?.clone())))
+            .ok_or_else(|| anyhow!("theme \"{name}\" is not registered"))?;
+        let theme_mode = ThemeMode::from(theme.appearance);
+
+        Self::set_registered_theme(cx, theme, family, theme_mode);
+        Ok(())
+    }
+
+    /// Switches to the theme family registered under `name` in the [`ThemeRegistry`]
+    /// and refreshes all the windows.
+    ///
+    /// The variant matching the current theme mode is picked,
+    /// or the other one if the family doesn't have it.
+    pub fn set_theme_family(cx: &mut App, name: &str) -> Result<()> {
+        let theme_mode = cx
+            .try_global::<ThemeSetting>()
+            .map(|setting| setting.theme_mode)
+            .unwrap_or_default();
+        let appearance = theme_mode.appearance(cx);
+        let family = ThemeRegistry::global(cx)
+            .family(name)
+            .cloned()
+            .ok_or_else(|| anyhow!("theme family \"{name}\" is not registered"))?;
+        let theme = family
+            .variant_or_any(appearance)
+            .cloned()
+            .ok_or_else(|| anyhow!("theme family \"{name}\" is empty"))?;
+
+        Self::set_registered_theme(cx, theme, family, theme_mode);
+        Ok(())
+    }
+
+    /// Switches to a theme reloaded from its file, keeping the theme mode.
+    pub(crate) fn set_reloaded_theme(cx: &mut App, theme: Arc<Theme>, family: ThemeFamily) {
+        let theme_mode = cx
+            .try_global::<ThemeSetting>()
+            .map(|setting| setting.theme_mode)
+            .unwrap_or_else(|| ThemeMode::from(theme.appearance));
+        Self::set_registered_theme(cx, theme, family, theme_mode);
+    }
+
+    fn set_registered_theme(
+        cx: &mut App,
+        theme: Arc<Theme>,
+        family: ThemeFamily,
+        theme_mode: ThemeMode,
+    ) {
+        let theme_type = if family.is_builtin() {
+            ThemeType::BuiltIn
+        } else {
+            ThemeType::Registered
+        };
+        cx.set_global(ThemeSetting {
+            active_theme: theme,
+            theme_mode,
+            theme_type,
+            theme_family: Some(family),
+        });
+        cx.refresh_windows();
+    }
+
+    /// Toggles the current appearance between light and dark
     /// and refreshes the provided window to reflect the change.
-    pub fn toggle_builtin_appearance(&mut self, window: &mut Window) {
-        if !self.theme_type.is_builtin() {
-            return;
-        }
-        let mut base_theme = (*self.active_theme).clone();
-        base_theme.appearance.toggle();
-        base_theme.colors = base_theme.appearance.into();
-        self.active_theme = Arc::new(base_theme);
-        window.refresh();
+    ///
+    /// Built-in and registered themes switch to the other variant of their [`ThemeFamily`].
+    /// Returns `false` if the theme has no variant for the new appearance.
+    pub fn toggle_builtin_appearance(&mut self, window: &mut Window) -> bool {
+        let mut appearance = self.active_theme.appearance;
+        appearance.toggle();
+        self.set_builtin_appearance(appearance, window)
     }
 
-    /// Sets the appearance to a specified value.
-    /// Updates the theme colors based on the new appearance
+    /// Sets the appearance to a specified value
     /// and refreshes the provided window to apply the changes.
     ///
+    /// Built-in and registered themes switch to the variant of their [`ThemeFamily`].
+    /// Returns `false` if the theme has no variant for the given appearance.
+    ///
     /// # Parameters
     /// - `appearance`: The new appearance to set (light or dark).
     /// - `window`: A mutable reference to the window that needs to be refreshed.
-    pub fn set_builtin_appearance(&mut self, appearance: Appearance, window: &mut Window) {
-        if !self.theme_type.is_builtin() {
-            return;
+    pub fn set_builtin_appearance(&mut self, appearance: Appearance, window: &mut Window) -> bool {
+        if !self.apply_appearance(appearance) {
+            return false;
         }
-        let mut base_theme = (*self.active_theme).clone();
-        base_theme.appearance = appearance;
-        base_theme.colors = appearance.into();
-        self.active_theme = Arc::new(base_theme);
         window.refresh();
+        true
+    }
+
+    /// Makes the active theme follow the appearance of the window when in [`ThemeMode::System`],
+    /// and refreshes all the windows if the theme changed.
+    ///
+    /// Returns the new appearance of the active theme, or `None` if the theme didn't change:
+    /// not in system mode, already following the window, or without a variant for it.
+    pub fn follow_system_appearance(window: &Window, cx: &mut App) -> Option<Appearance> {
+        let appearance = Appearance::from(window.appearance());
+        let setting = Self::global_mut(cx);
+        if !setting.theme_mode.is_system()
+            || setting.active_theme.appearance == appearance
+            || !setting.apply_appearance(appearance)
+        {
+            return None;
+        }
+        cx.refresh_windows();
+        Some(appearance)
+    }
+
+    fn apply_appearance(&mut self, appearance: Appearance) -> bool {
+        match self.theme_type {
+            ThemeType::BuiltIn | ThemeType::Registered => {
+                let Some(theme) = self
+                    .theme_family
+                    .as_ref()
+                    .and_then(|family| family.variant(appearance))
+                else {
+                    return false;
+                };
+                self.active_theme = theme.clone();
+            }
+            ThemeType::Custom => return false,
+        }
+        true
     }
 
     /// Initializes the built-in theme based on the provided theme mode.
     pub fn init_builtin_theme(cx: &mut App, theme_mode: ThemeMode) {
-        let appearance = match theme_mode {
-            ThemeMode::Light => Appearance::Light,
-            ThemeMode::Dark => Appearance::Dark,
-            ThemeMode::System => Appearance::from(cx.window_appearance()),
-        };
-
-        let mut theme = Theme::from(appearance);
-        theme.styles.scrollbar_show = if cx.should_auto_hide_scrollbars() {
-            ScrollbarShow::Scrolling
-        } else {
-            ScrollbarShow::Always
-        };
+        ThemeRegistry::init(cx);
+        let appearance = theme_mode.appearance(cx);
+        let family = ThemeRegistry::global(cx)
+            .family(BUILTIN_THEME_FAMILY)
+            .cloned()
+            .unwrap_or_else(|| ThemeFamily::builtin(ScrollbarShow::default()));
+        let theme = family
+            .variant(appearance)
+            .cloned()
+            .unwrap_or_else(|| Arc::new(Theme::from(appearance)));
 
         cx.set_global(ThemeSetting {
-            active_theme: Arc::new(theme),
+            active_theme: theme,
             theme_mode,
             theme_type: ThemeType::BuiltIn,
+            theme_family: Some(family),
         });
     }
 }
 
+/// Events about the theme, emitted by [`Root`](crate::Root).
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ThemeEvent {
+    /// The active theme followed a change of the system appearance, see [`ThemeMode::System`].
+    AppearanceChanged(Appearance),
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
 pub enum ThemeType {
     #[default]
     BuiltIn,
+    /// A theme registered in the [`ThemeRegistry`].
+    Registered,
     Custom,
 }
 
@@ -99,6 +212,10 @@ impl ThemeType {
     pub fn is_builtin(&self) -> bool {
         matches!(self, ThemeType::BuiltIn)
     }
+
+    pub fn is_registered(&self) -> bool {
+        matches!(self, ThemeType::Registered)
+    }
 }
 
 /// The mode use to select a theme.
@@ -121,4 +238,64 @@ impl ThemeMode {
     pub fn is_system(&self) -> bool {
         matches!(self, ThemeMode::System)
     }
+
+    /// Returns the appearance selected by this mode.
+    pub fn appearance(&self, cx: &App) -> Appearance {
+        match self {
+            ThemeMode::Light => Appearance::Light,
+            ThemeMode::Dark => Appearance::Dark,
+            ThemeMode::System => Appearance::from(cx.window_appearance()),
+        }
+    }
+}
+
+impl From<Appearance> for ThemeMode {
+    fn from(appearance: Appearance) -> Self {
+        match appearance {
+            Appearance::Light => ThemeMode::Light,
+            Appearance::Dark => ThemeMode::Dark,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setting(family: ThemeFamily, theme_type: ThemeType) -> ThemeSetting {
+        ThemeSetting {
+            active_theme: family.variant(Appearance::Light).unwrap().clone(),
+            theme_mode: ThemeMode::System,
+            theme_type,
+            theme_family: Some(family),
+        }
+    }
+
+    #[test]
+    fn test_apply_appearance() {
+        let family = ThemeFamily::builtin(ScrollbarShow::Always);
+        let mut setting = setting(family.clone(), ThemeType::BuiltIn);
+
+        assert!(setting.apply_appearance(Appearance::Dark));
+        let dark = family.variant(Appearance::Dark).unwrap();
+        // The whole variant is used, not only its colors.
+        assert!(Arc::ptr_eq(&setting.active_theme, dark));
+        assert_eq!(
+            setting.active_theme.styles.scrollbar_show,
+            ScrollbarShow::Always
+        );
+    }
+
+    #[test]
+    fn test_apply_missing_appearance() {
+        let mut family = ThemeFamily::new("Light only");
+        family.set_variant(Arc::new(Theme::from(Appearance::Light)));
+        let mut setting = setting(family, ThemeType::Registered);
+
+        assert!(!setting.apply_appearance(Appearance::Dark));
+        assert_eq!(setting.active_theme.appearance, Appearance::Light);
+
+        setting.theme_type = ThemeType::Custom;
+        assert!(!setting.apply_appearance(Appearance::Light));
+    }
 }
@@ -0,0 +1,70 @@
+use gpui::{hsla, BoxShadow};
+use smallvec::{smallvec, SmallVec};
+
+use crate::{box_shadow, Appearance, ElevationIndex};
+
+/// The shadows of the [`ElevationIndex`] levels.
+#[derive(Debug, Clone)]
+pub struct ThemeShadows {
+    /// The shadows of the [`Surface`](ElevationIndex::Surface) and
+    /// [`EditorSurface`](ElevationIndex::EditorSurface) levels.
+    pub surface: SmallVec<[BoxShadow; 2]>,
+    /// The shadows of the [`ElevatedSurface`](ElevationIndex::ElevatedSurface) level.
+    pub elevated_surface: SmallVec<[BoxShadow; 2]>,
+    /// The shadows of the [`ModalSurface`](ElevationIndex::ModalSurface) level.
+    pub modal_surface: SmallVec<[BoxShadow; 2]>,
+}
+
+/// The default shadows for the theme.
+impl ThemeShadows {
+    pub fn light() -> Self {
+        Self {
+            surface: smallvec![],
+            elevated_surface: smallvec![
+                box_shadow(0., 2., 3., 0., hsla(0., 0., 0., 0.12)),
+                box_shadow(1., 1., 0., 0., hsla(0., 0., 0., 0.03)),
+            ],
+            modal_surface: smallvec![
+                box_shadow(0., 2., 3., 0., hsla(0., 0., 0., 0.06)),
+                box_shadow(0., 3., 6., 0., hsla(0., 0., 0., 0.06)),
+                box_shadow(0., 6., 12., 0., hsla(0., 0., 0., 0.04)),
+                box_shadow(1., 1., 0., 0., hsla(0., 0., 0., 0.04)),
+            ],
+        }
+    }
+
+    pub fn dark() -> Self {
+        Self {
+            surface: smallvec![],
+            elevated_surface: smallvec![
+                box_shadow(0., 2., 3., 0., hsla(0., 0., 0., 0.12)),
+                box_shadow(1., 1., 0., 0., hsla(0., 0., 0., 0.06)),
+            ],
+            modal_surface: smallvec![
+                box_shadow(0., 2., 3., 0., hsla(0., 0., 0., 0.12)),
+                box_shadow(0., 3., 6., 0., hsla(0., 0., 0., 0.08)),
+                box_shadow(0., 6., 12., 0., hsla(0., 0., 0., 0.04)),
+                box_shadow(1., 1., 0., 0., hsla(0., 0., 0., 0.12)),
+            ],
+        }
+    }
+
+    /// Returns the shadows of the given elevation.
+    pub fn get(&self, index: ElevationIndex) -> &[BoxShadow] {
+        match index {
+            ElevationIndex::Background => &[],
+            ElevationIndex::Surface | ElevationIndex::EditorSurface => &self.surface,
+            ElevationIndex::ElevatedSurface => &self.elevated_surface,
+            ElevationIndex::ModalSurface => &self.modal_surface,
+        }
+    }
+}
+
+impl From<Appearance> for ThemeShadows {
+    fn from(appearance: Appearance) -> Self {
+        match appearance {
+            Appearance::Light => ThemeShadows::light(),
+            Appearance::Dark => ThemeShadows::dark(),
+        }
+    }
+}
@@ -1,7 +1,11 @@
-use gpui::{App, SharedString};
+use anyhow::Result;
+use gpui::{App, Hsla, SharedString};
 
-use crate::{Appearance, ThemeColors, ThemeMode, ThemeSetting, ThemeStyles};
-use std::sync::Arc;
+use crate::{
+    Appearance, ColorScale, ThemeColors, ThemeContent, ThemeFormat, ThemeMode, ThemeRefinement,
+    ThemeRegistry, ThemeSetting, ThemeShadows, ThemeStyles,
+};
+use std::{path::Path, sync::Arc};
 
 pub trait ActiveTheme {
     fn theme(&self) -> &Arc<Theme>;
@@ -33,18 +37,58 @@ pub struct Theme {
     pub appearance: Appearance,
     pub colors: ThemeColors,
     pub styles: ThemeStyles,
+    /// The shadows of the elevation levels.
+    pub shadows: ThemeShadows,
 }
 
 impl Theme {
     /// Initializes the [`Theme`] for the application.
     pub fn init(cx: &mut App, theme: Option<Arc<Theme>>, theme_mode: Option<ThemeMode>) {
         let theme_mode = theme_mode.unwrap_or_else(|| ThemeMode::default());
+        ThemeRegistry::init(cx);
         if let Some(theme) = theme {
             ThemeSetting::set_active_theme(cx, theme, theme_mode);
             return;
         }
         ThemeSetting::init_builtin_theme(cx, theme_mode);
     }
+
+    /// Returns the [`ColorScale`] of a color for the appearance of this theme.
+    pub fn color_scale(&self, color: Hsla) -> ColorScale {
+        ColorScale::new(color, self.appearance)
+    }
+
+    /// Returns a copy of this theme with the fields set in `refinement` overridden.
+    pub fn refine(&self, refinement: &ThemeRefinement) -> Self {
+        let mut theme = self.clone();
+        refinement.apply_to(&mut theme);
+        theme
+    }
+
+    /// Parses a [`Theme`] from the content of a theme file.
+    ///
+    /// See [`ThemeContent`] for the file format, `extends` may only name a built-in theme,
+    /// use [`ThemeContent::resolve`] to extend a registered theme.
+    pub fn from_str(content: &str, format: ThemeFormat) -> Result<Self> {
+        ThemeContent::parse(content, format)?.resolve(&ThemeRegistry::default())
+    }
+
+    /// Loads a [`Theme`] from a `.json` or `.toml` theme file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        ThemeContent::from_file(path)?.resolve(&ThemeRegistry::default())
+    }
+
+    /// Loads a [`Theme`] bundled in [`Assets`](crate::Assets), e.g. `themes/solarized_dark.json`.
+    pub fn from_asset(path: &str) -> Result<Self> {
+        ThemeContent::from_asset(path)?.resolve(&ThemeRegistry::default())
+    }
+
+    /// Loads all the themes bundled in [`Assets`](crate::Assets) under `themes/`.
+    pub fn bundled() -> Result<Vec<Self>> {
+        let mut registry = ThemeRegistry::default();
+        registry.load_bundled()?;
+        Ok(registry.themes().map(|theme| (**theme).clone()).collect())
+    }
 }
 
 // use std::ops::{Deref, DerefMut};
@@ -84,6 +128,7 @@ impl From<Appearance> for Theme {
             appearance,
             colors: appearance.into(),
             styles: ThemeStyles::default(),
+            shadows: appearance.into(),
         }
     }
 }
@@ -0,0 +1,576 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, bail, Context as _, Result};
+use gpui::{px, BoxShadow, Hsla, Pixels};
+use serde_json::{json, Map, Value};
+use smallvec::SmallVec;
+
+use crate::{box_shadow, parse_color, relative_luminance, to_hex_string, Appearance, Theme};
+
+/// The size of a `rem` in pixels, for dimension tokens written in `rem`.
+const REM_SIZE: f32 = 16.;
+
+/// The maximum number of aliases followed to resolve a token, to catch cycles.
+const MAX_ALIAS_DEPTH: usize = 16;
+
+/// The name of the theme when the tokens don't set one.
+const DEFAULT_NAME: &str = "Design Tokens";
+
+/// Import and export of [W3C Design Tokens](https://tr.designtokens.org/format/),
+/// the JSON format of design tools like Figma.
+///
+/// A theme maps to these tokens, names are the kebab-case field names:
+///
+/// - `color.*`: the [`ThemeColors`](crate::ThemeColors), e.g. `color.text-muted`
+/// - `dimension.radius`, `dimension.font-size` and `dimension.tile-grid-size`
+/// - `font.family`: the font family
+/// - `shadow.surface`, `shadow.elevated-surface` and `shadow.modal-surface`:
+///   the [`ThemeShadows`](crate::ThemeShadows) of the elevation levels
+///
+/// The name and appearance are stored in `$extensions.rui`.
+///
+/// ```json
+/// {
+///   "$extensions": { "rui": { "name": "Ocean", "appearance": "dark" } },
+///   "color": {
+///     "$type": "color",
+///     "primary": { "$value": "#0070f3" },
+///     "element-hover": { "$value": "{color.primary}" }
+///   },
+///   "dimension": {
+///     "$type": "dimension",
+///     "radius": { "$value": "6px" }
+///   },
+///   "shadow": {
+///     "$type": "shadow",
+///     "elevated-surface": {
+///       "$value": [
+///         { "color": "#0000001f", "offsetX": "0px", "offsetY": "2px", "blur": "3px", "spread": "0px" }
+///       ]
+///     }
+///   }
+/// }
+/// ```
+impl Theme {
+    /// Exports this theme as W3C Design Tokens.
+    pub fn to_design_tokens(&self) -> Value {
+        let colors = self
+            .colors
+            .entries()
+            .map(|(name, color)| (name, json!(to_hex_string(color))));
+        let dimensions = [
+            ("radius", self.styles.radius),
+            ("font_size", self.styles.font_size),
+            ("tile_grid_size", self.styles.tile_grid_size),
+        ]
+        .map(|(name, pixels)| (name, json!(dimension_value(pixels))));
+        let shadows = [
+            ("surface", &self.shadows.surface),
+            ("elevated_surface", &self.shadows.elevated_surface),
+            ("modal_surface", &self.shadows.modal_surface),
+        ]
+        .map(|(name, shadows)| (name, shadows.iter().map(shadow_value).collect::<Value>()));
+        let font_family = json!(self.styles.font_family.to_string());
+        let appearance = if self.appearance.is_light() {
+            "light"
+        } else {
+            "dark"
+        };
+
+        json!({
+            "$extensions": {
+                "rui": { "name": self.name.to_string(), "appearance": appearance },
+            },
+            "color": token_group("color", colors),
+            "dimension": token_group("dimension", dimensions),
+            "font": token_group("fontFamily", [("family", font_family)]),
+            "shadow": token_group("shadow", shadows),
+        })
+    }
+
+    /// Imports a theme from W3C Design Tokens JSON.
+    ///
+    /// Tokens are matched by type and by the last segment of their path, in kebab, snake
+    /// or camel case, so `color.text-muted` and `brand.colors.textMuted` both set `text_muted`.
+    /// Aliases like `{color.primary}` are resolved, unknown tokens are ignored. Tokens setting the
+    /// same field, like `color.primary` and `brand.primary`, are an error.
+    ///
+    /// Anything not set falls back to the built-in theme of the same appearance,
+    /// which is taken from `$extensions.rui.appearance`, or guessed from the `bg` color.
+    pub fn from_design_tokens(content: &str) -> Result<Self> {
+        let root: Value = serde_json::from_str(content).context("invalid design tokens JSON")?;
+        let root = root
+            .as_object()
+            .ok_or_else(|| anyhow!("design tokens must be a JSON object"))?;
+        let tokens = Tokens::collect(root);
+        let extension = root
+            .get("$extensions")
+            .and_then(|extensions| extensions.get("rui"));
+
+        let appearance = match extension.and_then(|rui| rui.get("appearance")) {
+            Some(appearance) => match appearance.as_str() {
+                Some("light") => Appearance::Light,
+                Some("dark") => Appearance::Dark,
+                _ => bail!("invalid appearance {appearance}, expected \"light\" or \"dark\""),
+            },
+            None => tokens.guess_appearance()?,
+        };
+        let mut theme = Theme::from(appearance);
+        theme.name = extension
+            .and_then(|rui| rui.get("name"))
+            .and_then(Value::as_str)
+            .unwrap_or(DEFAULT_NAME)
+            .to_string()
+            .into();
+
+        // Which of several tokens setting the same field would win depends on their order.
+        let mut fields = HashMap::new();
+        for token in &tokens.tokens {
+            let field = tokens
+                .apply_to(token, &mut theme)
+                .with_context(|| format!("invalid design token \"{}\"", token.path))?;
+            if let Some(other) = field.and_then(|field| fields.insert(field, &token.path)) {
+                bail!(
+                    "design tokens \"{other}\" and \"{}\" both set the same field",
+                    token.path
+                );
+            }
+        }
+
+        Ok(theme)
+    }
+}
+
+/// A design token, a JSON object with a `$value`.
+struct Token<'a> {
+    /// The dot separated path of the token, e.g. `color.primary`.
+    path: String,
+    /// The `$type` of the token, or of the closest group that sets one.
+    ty: Option<&'a str>,
+    value: &'a Value,
+}
+
+impl Token<'_> {
+    /// Returns the last segment of the path in snake case, e.g. `text_muted` for `color.text-muted`.
+    fn name(&self) -> String {
+        let name = self.path.rsplit('.').next().unwrap_or_default();
+        let mut snake_case = String::with_capacity(name.len());
+        for c in name.chars() {
+            match c {
+                '-' | ' ' => snake_case.push('_'),
+                c if c.is_ascii_uppercase() => {
+                    if !snake_case.is_empty() && !snake_case.ends_with('_') {
+                        snake_case.push('_');
+                    }
+                    snake_case.push(c.to_ascii_lowercase());
+                }
+                c => snake_case.push(c),
+            }
+        }
+        snake_case
+    }
+}
+
+/// All the tokens of a design tokens file, indexed by path to resolve aliases.
+struct Tokens<'a> {
+    tokens: Vec<Token<'a>>,
+    paths: HashMap<String, usize>,
+}
+
+impl<'a> Tokens<'a> {
+    fn collect(root: &'a Map<String, Value>) -> Self {
+        fn walk<'a>(
+            group: &'a Map<String, Value>,
+            path: &str,
+            ty: Option<&'a str>,
+            tokens: &mut Vec<Token<'a>>,
+        ) {
+            let ty = group.get("$type").and_then(Value::as_str).or(ty);
+            for (key, value) in group {
+                let Some(object) = value.as_object().filter(|_| !key.starts_with('$')) else {
+                    continue;
+                };
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                match object.get("$value") {
+                    Some(value) => tokens.push(Token {
+                        path,
+                        ty: object.get("$type").and_then(Value::as_str).or(ty),
+                        value,
+                    }),
+                    None => walk(object, &path, ty, tokens),
+                }
+            }
+        }
+
+        let mut tokens = Vec::new();
+        walk(root, "", None, &mut tokens);
+        let paths = tokens
+            .iter()
+            .enumerate()
+            .map(|(ix, token)| (token.path.clone(), ix))
+            .collect();
+        Self { tokens, paths }
+    }
+
+    /// Follows the aliases of a value, returning the final value and the first type found.
+    fn resolve(
+        &self,
+        mut value: &'a Value,
+        mut ty: Option<&'a str>,
+    ) -> Result<(&'a Value, Option<&'a str>)> {
+        for _ in 0..MAX_ALIAS_DEPTH {
+            let Some(path) = alias(value) else {
+                return Ok((value, ty));
+            };
+            let token = self
+                .paths
+                .get(path)
+                .map(|ix| &self.tokens[*ix])
+                .ok_or_else(|| anyhow!("unknown alias \"{{{path}}}\""))?;
+            value = token.value;
+            ty = ty.or(token.ty);
+        }
+        bail!("more than {MAX_ALIAS_DEPTH} nested aliases, the aliases may be circular")
+    }
+
+    /// Guesses the appearance from the luminance of the `bg` color token, defaults to light.
+    fn guess_appearance(&self) -> Result<Appearance> {
+        for token in self.tokens.iter().filter(|token| token.name() == "bg") {
+            let (value, ty) = self.resolve(token.value, token.ty)?;
+            if ty == Some("color") {
+                return Ok(if relative_luminance(parse_color_value(value)?) < 0.5 {
+                    Appearance::Dark
+                } else {
+                    Appearance::Light
+                });
+            }
+        }
+        Ok(Appearance::Light)
+    }
+
+    /// Sets the field of the theme matching the token, if any, and returns its type and name.
+    fn apply_to(
+        &self,
+        token: &Token<'a>,
+        theme: &mut Theme,
+    ) -> Result<Option<(&'static str, &'static str)>> {
+        let (value, ty) = self.resolve(token.value, token.ty)?;
+        let name = token.name();
+        let field = match (ty, name.as_str()) {
+            (Some("color"), name) => {
+                let Some((field, color)) = theme
+                    .colors
+                    .entries_mut()
+                    .into_iter()
+                    .find(|(field, _)| *field == name)
+                else {
+                    return Ok(None);
+                };
+                *color = parse_color_value(value)?;
+                ("color", field)
+            }
+            (Some("dimension"), "radius") => {
+                theme.styles.radius = self.dimension(value)?;
+                ("dimension", "radius")
+            }
+            (Some("dimension"), "font_size") => {
+                theme.styles.font_size = self.dimension(value)?;
+                ("dimension", "font_size")
+            }
+            (Some("dimension"), "tile_grid_size") => {
+                theme.styles.tile_grid_size = self.dimension(value)?;
+                ("dimension", "tile_grid_size")
+            }
+            (Some("fontFamily"), "family" | "font_family") => {
+                let family = match value {
+                    Value::Array(families) => families.first().and_then(Value::as_str),
+                    value => value.as_str(),
+                };
+                theme.styles.font_family = family
+                    .ok_or_else(|| anyhow!("expected a font family name, got {value}"))?
+                    .to_string()
+                    .into();
+                ("fontFamily", "font_family")
+            }
+            (Some("shadow"), "surface") => {
+                theme.shadows.surface = self.shadows(value)?;
+                ("shadow", "surface")
+            }
+            (Some("shadow"), "elevated_surface") => {
+                theme.shadows.elevated_surface = self.shadows(value)?;
+                ("shadow", "elevated_surface")
+            }
+            (Some("shadow"), "modal_surface") => {
+                theme.shadows.modal_surface = self.shadows(value)?;
+                ("shadow", "modal_surface")
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(field))
+    }
+
+    /// Parses a dimension value, like `4px`, `0.25rem` or `{ "value": 4, "unit": "px" }`.
+    fn dimension(&self, value: &'a Value) -> Result<Pixels> {
+        let (value, _) = self.resolve(value, None)?;
+        let pixels = |number: f64, unit: &str| match unit {
+            "px" => Ok(px(number as f32)),
+            "rem" => Ok(px(number as f32 * REM_SIZE)),
+            _ => Err(anyhow!("unsupported dimension unit \"{unit}\"")),
+        };
+        match value {
+            Value::Number(number) => Ok(px(number.as_f64().unwrap_or_default() as f32)),
+            Value::String(dimension) => {
+                let dimension = dimension.trim();
+                let split = dimension
+                    .find(|c: char| c.is_ascii_alphabetic())
+                    .unwrap_or(dimension.len());
+                let (number, unit) = dimension.split_at(split);
+                let number = number
+                    .trim()
+                    .parse::<f64>()
+                    .with_context(|| format!("invalid dimension \"{dimension}\""))?;
+                pixels(number, if unit.is_empty() { "px" } else { unit })
+            }
+            Value::Object(dimension) => {
+                let number = dimension
+                    .get("value")
+                    .and_then(Value::as_f64)
+                    .ok_or_else(|| anyhow!("dimension {value} has no numeric `value`"))?;
+                pixels(
+                    number,
+                    dimension
+                        .get("unit")
+                        .and_then(Value::as_str)
+                        .unwrap_or("px"),
+                )
+            }
+            _ => bail!("expected a dimension, got {value}"),
+        }
+    }
+
+    /// Parses a shadow value, either one shadow object or an array of them.
+    fn shadows(&self, value: &'a Value) -> Result<SmallVec<[BoxShadow; 2]>> {
+        let shadows = match value {
+            Value::Array(shadows) => shadows.iter().collect(),
+            value => vec![value],
+        };
+        shadows
+            .into_iter()
+            .map(|shadow| {
+                let (shadow, _) = self.resolve(shadow, None)?;
+                let shadow = shadow
+                    .as_object()
+                    .ok_or_else(|| anyhow!("expected a shadow object, got {shadow}"))?;
+                if shadow.get("inset").and_then(Value::as_bool) == Some(true) {
+                    bail!("inset shadows are not supported");
+                }
+                let dimension = |field: &str| match shadow.get(field) {
+                    Some(value) => self.dimension(value),
+                    None => Ok(px(0.)),
+                };
+                let color = shadow
+                    .get("color")
+                    .ok_or_else(|| anyhow!("shadow has no `color`"))?;
+                Ok(box_shadow(
+                    dimension("offsetX")?,
+                    dimension("offsetY")?,
+                    dimension("blur")?,
+                    dimension("spread")?,
+                    parse_color_value(self.resolve(color, None)?.0)?,
+                ))
+            })
+            .collect()
+    }
+}
+
+/// Returns the path of an alias like `{color.primary}`.
+fn alias(value: &Value) -> Option<&str> {
+    value.as_str()?.strip_prefix('{')?.strip_suffix('}')
+}
+
+/// Parses a color value, either a color string or an object with a `hex` and an optional `alpha`.
+fn parse_color_value(value: &Value) -> Result<Hsla> {
+    match value {
+        Value::String(color) => parse_color(color),
+        Value::Object(color) => {
+            let hex = color
+                .get("hex")
+                .and_then(Value::as_str)
+                .ok_or_else(|| anyhow!("color {value} has no `hex`"))?;
+            let mut color_value = parse_color(hex)?;
+            if let Some(alpha) = color.get("alpha").and_then(Value::as_f64) {
+                color_value.a = alpha as f32;
+            }
+            Ok(color_value)
+        }
+        _ => bail!("expected a color, got {value}"),
+    }
+}
+
+/// Returns a group of tokens of the given type, with kebab-case names.
+fn token_group(ty: &str, tokens: impl IntoIterator<Item = (&'static str, Value)>) -> Value {
+    let mut group = Map::new();
+    group.insert("$type".into(), json!(ty));
+    for (name, value) in tokens {
+        group.insert(name.replace('_', "-"), json!({ "$value": value }));
+    }
+    Value::Object(group)
+}
+
+fn dimension_value(pixels: Pixels) -> String {
+    format!("{}px", f32::from(pixels))
+}
+
+fn shadow_value(shadow: &BoxShadow) -> Value {
+    json!({
+        "color": to_hex_string(shadow.color),
+        "offsetX": dimension_value(shadow.offset.x),
+        "offsetY": dimension_value(shadow.offset.y),
+        "blur": dimension_value(shadow.blur_radius),
+        "spread": dimension_value(shadow.spread_radius),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hsl, ThemeColors, ThemeShadows};
+    use smallvec::smallvec;
+
+    fn assert_same_shadows(a: &[BoxShadow], b: &[BoxShadow]) {
+        assert_eq!(a.len(), b.len());
+        for (a, b) in a.iter().zip(b) {
+            assert_eq!(to_hex_string(a.color), to_hex_string(b.color));
+            assert_eq!(a.offset, b.offset);
+            assert_eq!(a.blur_radius, b.blur_radius);
+            assert_eq!(a.spread_radius, b.spread_radius);
+        }
+    }
+
+    #[test]
+    fn test_design_tokens_round_trip() {
+        let mut theme = Theme::from(Appearance::Dark);
+        theme.name = "Ocean".into();
+        theme.colors.primary = hsl(212., 100., 47.);
+        theme.styles.radius = px(6.);
+        theme.styles.font_family = "Inter".into();
+        theme.shadows.modal_surface = smallvec![box_shadow(0., 8., 30., 2., hsl(220., 40., 10.))];
+
+        let tokens = theme.to_design_tokens();
+        assert_eq!(tokens["color"]["text-muted"]["$value"], "#a9afbc");
+        assert_eq!(tokens["dimension"]["radius"]["$value"], "6px");
+
+        let imported = Theme::from_design_tokens(&tokens.to_string()).unwrap();
+        assert_eq!(imported.name, "Ocean");
+        assert_eq!(imported.appearance, Appearance::Dark);
+        for ((name, a), (_, b)) in theme
+            .colors
+            .entries()
+            .into_iter()
+            .zip(imported.colors.entries())
+        {
+            assert_eq!(to_hex_string(a), to_hex_string(b), "color `{name}`");
+        }
+        assert_eq!(imported.styles.radius, px(6.));
+        assert_eq!(imported.styles.font_size, theme.styles.font_size);
+        assert_eq!(imported.styles.tile_grid_size, theme.styles.tile_grid_size);
+        assert_eq!(imported.styles.font_family, "Inter");
+        assert_same_shadows(&imported.shadows.surface, &theme.shadows.surface);
+        assert_same_shadows(
+            &imported.shadows.elevated_surface,
+            &theme.shadows.elevated_surface,
+        );
+        assert_same_shadows(
+            &imported.shadows.modal_surface,
+            &theme.shadows.modal_surface,
+        );
+    }
+
+    #[test]
+    fn test_import_design_tokens() {
+        let theme = Theme::from_design_tokens(
+            r##"{
+                "base": {
+                    "$type": "color",
+                    "blue": { "$value": "#0070f3" },
+                    "night": { "$value": "#0b0d12" }
+                },
+                "semantic": {
+                    "bg": { "$value": "{base.night}" },
+                    "primary": { "$type": "color", "$value": "{base.blue}" },
+                    "textMuted": {
+                        "$type": "color",
+                        "$value": { "colorSpace": "srgb", "hex": "#a0a0a0", "alpha": 0.5 }
+                    }
+                },
+                "radius": { "$type": "dimension", "$value": "0.5rem" },
+                "font-size": { "$type": "dimension", "$value": { "value": 14, "unit": "px" } },
+                "elevated-surface": {
+                    "$type": "shadow",
+                    "$value": {
+                        "color": "rgba(0, 0, 0, 0.2)",
+                        "offsetX": "0px",
+                        "offsetY": "4px",
+                        "blur": "8px"
+                    }
+                }
+            }"##,
+        )
+        .unwrap();
+
+        assert_eq!(theme.name, DEFAULT_NAME);
+        assert_eq!(theme.appearance, Appearance::Dark);
+        assert_eq!(to_hex_string(theme.colors.bg), "#0b0d12");
+        assert_eq!(to_hex_string(theme.colors.primary), "#0070f3");
+        assert_eq!(to_hex_string(theme.colors.text_muted), "#a0a0a080");
+        assert_eq!(theme.colors.text, ThemeColors::dark().text);
+        assert_eq!(theme.styles.radius, px(8.));
+        assert_eq!(theme.styles.font_size, px(14.));
+        assert_same_shadows(
+            &theme.shadows.elevated_surface,
+            &[box_shadow(0., 4., 8., 0., crate::rgba(0, 0, 0, 0.2))],
+        );
+        assert_same_shadows(
+            &theme.shadows.modal_surface,
+            &ThemeShadows::dark().modal_surface,
+        );
+    }
+
+    #[test]
+    fn test_design_tokens_errors() {
+        let err = Theme::from_design_tokens(
+            r##"{ "color": { "$type": "color", "primary": { "$value": "{color.brand}" } } }"##,
+        )
+        .unwrap_err();
+        assert!(format!("{err:#}").contains("invalid design token \"color.primary\""));
+        assert!(format!("{err:#}").contains("unknown alias \"{color.brand}\""));
+
+        let err = Theme::from_design_tokens(
+            r##"{ "a": { "$type": "color", "$value": "{b}" }, "b": { "$value": "{a}" } }"##,
+        )
+        .unwrap_err();
+        assert!(format!("{err:#}").contains("aliases may be circular"));
+
+        let err = Theme::from_design_tokens(
+            r##"{ "radius": { "$type": "dimension", "$value": "4em" } }"##,
+        )
+        .unwrap_err();
+        assert!(format!("{err:#}").contains("unsupported dimension unit \"em\""));
+
+        let err = Theme::from_design_tokens(
+            r##"{
+                "color": { "$type": "color", "primary": { "$value": "#0070f3" } },
+                "brand": { "$type": "color", "primary": { "$value": "#e91e63" } }
+            }"##,
+        )
+        .unwrap_err();
+        // The order of the tokens depends on the features of `serde_json`.
+        let err = format!("{err:#}");
+        assert!(err.contains("\"brand.primary\"") && err.contains("\"color.primary\""));
+        assert!(err.contains("both set the same field"));
+    }
+}
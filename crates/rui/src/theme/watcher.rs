@@ -0,0 +1,204 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{anyhow, Result};
+use gpui::{Context, EventEmitter, SharedString, Task};
+
+use crate::{Theme, ThemeContent, ThemeRegistry, ThemeSetting};
+
+/// How often the watched theme file is checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Events emitted by a [`ThemeWatcher`].
+#[derive(Debug, Clone)]
+pub enum ThemeWatcherEvent {
+    /// The theme file changed and is now the active theme.
+    Reloaded(Arc<Theme>),
+    /// The theme file changed but could not be loaded, the active theme is kept.
+    Failed(SharedString),
+}
+
+/// Reloads a `.json` or `.toml` theme file when it changes on disk, for iterating on themes
+/// without restarting the app.
+///
+/// On every change the file is registered in the [`ThemeRegistry`], replacing the previous
+/// version, and set as the active theme. The file is polled and read on the background
+/// executor, so the watcher is meant for development only.
+///
+/// See [`Root::watch_theme_file`](crate::Root::watch_theme_file) to show load errors as toasts.
+pub struct ThemeWatcher {
+    path: PathBuf,
+    _poll_task: Task<()>,
+    _reload_task: Option<Task<()>>,
+}
+
+impl EventEmitter<ThemeWatcherEvent> for ThemeWatcher {}
+
+impl ThemeWatcher {
+    /// Starts watching the theme file at `path`, the file is not loaded until it changes.
+    pub fn new(path: impl Into<PathBuf>, cx: &mut Context<Self>) -> Self {
+        let path = path.into();
+        let poll_task = cx.spawn({
+            let path = path.clone();
+            async move |this, cx| {
+                let mut modified = cx
+                    .background_executor()
+                    .spawn(modified_time(path.clone()))
+                    .await;
+                loop {
+                    cx.background_executor().timer(POLL_INTERVAL).await;
+                    let current = cx
+                        .background_executor()
+                        .spawn(modified_time(path.clone()))
+                        .await;
+                    if current == modified {
+                        continue;
+                    }
+                    modified = current;
+                    // Editors may remove the file while saving it, wait for it to be written back.
+                    if modified.is_none() {
+                        continue;
+                    }
+                    let content = cx.background_executor().spawn(read(path.clone())).await;
+                    if this.update(cx, |this, cx| this.apply(content, cx)).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        Self {
+            path,
+            _poll_task: poll_task,
+            _reload_task: None,
+        }
+    }
+
+    /// Returns the path of the watched theme file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Reads the theme file on the background executor and makes it the active theme.
+    ///
+    /// If the file can't be loaded, the active theme is kept and
+    /// [`ThemeWatcherEvent::Failed`] is emitted with the error.
+    pub fn reload(&mut self, cx: &mut Context<Self>) {
+        let read = cx.background_executor().spawn(read(self.path.clone()));
+        self._reload_task = Some(cx.spawn(async move |this, cx| {
+            let content = read.await;
+            this.update(cx, |this, cx| this.apply(content, cx)).ok();
+        }));
+    }
+
+    fn apply(&mut self, content: Result<ThemeContent>, cx: &mut Context<Self>) {
+        let event = match content.and_then(|content| self.load(content, cx)) {
+            Ok(theme) => ThemeWatcherEvent::Reloaded(theme),
+            Err(err) => ThemeWatcherEvent::Failed(format!("{err:#}").into()),
+        };
+        cx.emit(event);
+    }
+
+    fn load(&self, content: ThemeContent, cx: &mut Context<Self>) -> Result<Arc<Theme>> {
+        let family_name = content.family_name().to_string();
+        ThemeRegistry::init(cx);
+        let theme = ThemeRegistry::global_mut(cx).register_content(content)?;
+        // Another family may have a theme of the same name, use the family of the file.
+        let family = ThemeRegistry::global(cx)
+            .family(&family_name)
+            .cloned()
+            .ok_or_else(|| anyhow!("theme family \"{family_name}\" is not registered"))?;
+        ThemeSetting::set_reloaded_theme(cx, theme.clone(), family);
+        Ok(theme)
+    }
+}
+
+async fn modified_time(path: PathBuf) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+async fn read(path: PathBuf) -> Result<ThemeContent> {
+    ThemeContent::from_file(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use gpui::{AppContext as _, Entity, TestAppContext};
+
+    use super::*;
+    use crate::ActiveTheme;
+
+    fn watch(name: &str, content: &str, cx: &mut TestAppContext) -> Entity<ThemeWatcher> {
+        let path = std::env::temp_dir().join(format!("rui-theme-watcher-{name}.toml"));
+        std::fs::write(&path, content).unwrap();
+        cx.update(|cx| Theme::init(cx, None, None));
+        cx.new(|cx| ThemeWatcher::new(path, cx))
+    }
+
+    fn events(
+        watcher: &Entity<ThemeWatcher>,
+        cx: &mut TestAppContext,
+    ) -> Rc<RefCell<Vec<ThemeWatcherEvent>>> {
+        let events = Rc::new(RefCell::new(Vec::new()));
+        cx.update(|cx| {
+            let events = events.clone();
+            cx.subscribe(watcher, move |_, event: &ThemeWatcherEvent, _| {
+                events.borrow_mut().push(event.clone())
+            })
+            .detach();
+        });
+        events
+    }
+
+    #[gpui::test]
+    fn test_reload(cx: &mut TestAppContext) {
+        let watcher = watch(
+            "reload",
+            "name = \"Reloaded\"\nappearance = \"dark\"\n[colors]\nprimary = \"#e91e63\"",
+            cx,
+        );
+        let events = events(&watcher, cx);
+
+        watcher.update(cx, |watcher, cx| watcher.reload(cx));
+        cx.run_until_parked();
+
+        assert!(matches!(
+            events.borrow().as_slice(),
+            [ThemeWatcherEvent::Reloaded(theme)] if theme.name == "Reloaded"
+        ));
+        cx.read(|cx| {
+            assert_eq!(cx.theme().name, "Reloaded");
+            assert!(ThemeRegistry::global(cx).get("Reloaded").is_some());
+        });
+    }
+
+    #[gpui::test]
+    fn test_failed_reload_keeps_theme(cx: &mut TestAppContext) {
+        let watcher = watch(
+            "failed",
+            "name = \"Broken\"\nappearance = \"dark\"\n[colors]\nprimary = \"#0070zz\"",
+            cx,
+        );
+        let events = events(&watcher, cx);
+        let active_theme = cx.read(|cx| cx.theme().name.clone());
+
+        watcher.update(cx, |watcher, cx| watcher.reload(cx));
+        cx.run_until_parked();
+
+        assert!(matches!(
+            events.borrow().as_slice(),
+            [ThemeWatcherEvent::Failed(error)] if error.contains("invalid color")
+        ));
+        cx.read(|cx| {
+            assert_eq!(cx.theme().name, active_theme);
+            assert!(ThemeRegistry::global(cx).get("Broken").is_none());
+        });
+    }
+}
@@ -1,11 +1,17 @@
-use crate::{ModalView, Root, ToastView};
+use crate::{ModalView, Root, ToastPosition, ToastView};
 use gpui::{App, Context, Entity, Window};
 
 pub trait RootView {
     fn toggle_modal<V: ModalView, B>(&mut self, cx: &mut App, build: B)
+    where
+        B: FnOnce(&mut Window, &mut Context<V>) -> V;
+    fn push_modal<V: ModalView, B>(&mut self, cx: &mut App, build: B)
     where
         B: FnOnce(&mut Window, &mut Context<V>) -> V;
     fn toggle_status_toast<V: ToastView>(&mut self, cx: &mut App, entity: Entity<V>);
+    fn show_toast<V: ToastView>(&mut self, cx: &mut App, entity: Entity<V>);
+    fn set_toast_position(&mut self, cx: &mut App, position: ToastPosition);
+    fn set_max_visible_toasts(&mut self, cx: &mut App, max_visible: usize);
 }
 
 impl RootView for Window {
@@ -20,6 +26,17 @@ impl RootView for Window {
         }
     }
 
+    fn push_modal<V: ModalView, B>(&mut self, cx: &mut App, build: B)
+    where
+        B: FnOnce(&mut Window, &mut Context<V>) -> V,
+    {
+        if let Some(workspace) = self.root::<Root>().flatten() {
+            workspace.update(cx, |workspace, cx| {
+                workspace.push_modal(self, cx, build);
+            })
+        }
+    }
+
     fn toggle_status_toast<V: ToastView>(&mut self, cx: &mut App, entity: Entity<V>) {
         if let Some(workspace) = self.root::<Root>().flatten() {
             workspace.update(cx, |workspace, cx| {
@@ -27,4 +44,28 @@ impl RootView for Window {
             })
         }
     }
+
+    fn show_toast<V: ToastView>(&mut self, cx: &mut App, entity: Entity<V>) {
+        if let Some(workspace) = self.root::<Root>().flatten() {
+            workspace.update(cx, |workspace, cx| {
+                workspace.show_toast(entity, cx);
+            })
+        }
+    }
+
+    fn set_toast_position(&mut self, cx: &mut App, position: ToastPosition) {
+        if let Some(workspace) = self.root::<Root>().flatten() {
+            workspace.update(cx, |workspace, cx| {
+                workspace.set_toast_position(position, cx);
+            })
+        }
+    }
+
+    fn set_max_visible_toasts(&mut self, cx: &mut App, max_visible: usize) {
+        if let Some(workspace) = self.root::<Root>().flatten() {
+            workspace.update(cx, |workspace, cx| {
+                workspace.set_max_visible_toasts(max_visible, cx);
+            })
+        }
+    }
 }
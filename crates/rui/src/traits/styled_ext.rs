@@ -31,7 +31,7 @@ impl From<(f32, f32, f32, f32)> for Edge {
 }
 
 fn elevated<E: Styled>(this: E, cx: &App, index: ElevationIndex) -> E {
-    this.bg(cx.theme().colors.bg_elevated_surface)
+    this.bg(index.bg(cx))
         .rounded_lg()
         .border_1()
         .border_color(cx.theme().colors.border_variant)
@@ -39,9 +39,7 @@ fn elevated<E: Styled>(this: E, cx: &App, index: ElevationIndex) -> E {
 }
 
 fn elevated_borderless<E: Styled>(this: E, cx: &mut App, index: ElevationIndex) -> E {
-    this.bg(cx.theme().colors.bg_elevated_surface)
-        .rounded_lg()
-        .shadow(index.shadow(cx))
+    this.bg(index.bg(cx)).rounded_lg().shadow(index.shadow(cx))
 }
 
 /// Extends [`gpui::Styled`] with Zed-specific styling methods.
@@ -1,3 +1,12 @@
 mod color;
+mod css_colors;
+mod date;
+mod fuzzy;
+mod oklab;
+mod typeahead;
 
 pub use color::*;
+pub use date::*;
+pub use fuzzy::*;
+pub use oklab::*;
+pub use typeahead::*;
@@ -1,5 +1,8 @@
+use anyhow::{anyhow, bail, Result};
 use gpui::{hsla, point, BoxShadow, Hsla, Pixels, Rgba};
 
+use super::css_colors::css_color;
+
 /// Create an RGBA color from RGB components.
 ///
 /// This function takes three color components (red, green, blue)
@@ -60,6 +63,227 @@ pub fn hsl(h: f32, s: f32, l: f32) -> Hsla {
     hsla(h / 360., s / 100.0, l / 100.0, 1.0)
 }
 
+/// Parse a color string into a [gpui::Hsla] color.
+///
+/// Supported formats:
+///
+/// - `#rgb`, `#rgba`, `#rrggbb` and `#rrggbbaa`
+/// - `rgb(r, g, b)` and `rgba(r, g, b, a)`, with `r`, `g`, `b` in 0..255 and `a` in 0.0..1.0
+/// - `hsl(h, s%, l%)` and `hsla(h, s%, l%, a)`, with `h` in 0..360
+/// - CSS named colors, like `rebeccapurple`, and `transparent`
+///
+/// e.g:
+///
+/// - `parse_color("#0070f3")`
+/// - `parse_color("rgba(0, 112, 243, 0.5)")`
+/// - `parse_color("hsl(212, 100%, 47%)")`
+/// - `parse_color("tomato")`
+pub fn parse_color(value: &str) -> Result<Hsla> {
+    let value = value.trim();
+
+    if let Some(hex) = value.strip_prefix('#') {
+        return parse_hex(hex);
+    }
+    if value.eq_ignore_ascii_case("transparent") {
+        return Ok(gpui::transparent_black());
+    }
+    if let Some(color) = css_color(value) {
+        return Ok(gpui::rgb(color).into());
+    }
+
+    let Some((name, args)) = value
+        .strip_suffix(')')
+        .and_then(|value| value.split_once('('))
+    else {
+        bail!("unsupported color format `{value}`, expected a hex, rgb(), hsl() or named color");
+    };
+    let args = args.split(',').map(str::trim).collect::<Vec<_>>();
+
+    match (name.trim(), args.as_slice()) {
+        ("rgb", [r, g, b]) => Ok(rgb(parse_u8(r)?, parse_u8(g)?, parse_u8(b)?).into()),
+        ("rgba", [r, g, b, a]) => {
+            Ok(rgba(parse_u8(r)?, parse_u8(g)?, parse_u8(b)?, parse_alpha(a)?).into())
+        }
+        ("hsl", [h, s, l]) => Ok(hsl(parse_hue(h)?, parse_percent(s)?, parse_percent(l)?)),
+        ("hsla", [h, s, l, a]) => Ok(hsla(
+            parse_hue(h)? / 360.,
+            parse_percent(s)? / 100.,
+            parse_percent(l)? / 100.,
+            parse_alpha(a)?,
+        )),
+        ("rgb" | "rgba" | "hsl" | "hsla", _) => {
+            bail!("wrong number of arguments in `{value}`")
+        }
+        (name, _) => bail!("unknown color function `{name}` in `{value}`"),
+    }
+}
+
+fn parse_hex(hex: &str) -> Result<Hsla> {
+    if !matches!(hex.len(), 3 | 4 | 6 | 8) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        bail!("invalid hex color `#{hex}`, expected `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa`");
+    }
+
+    // Short hex colors repeat each digit, `#0af` is `#00aaff`.
+    let digits = if hex.len() <= 4 { 1 } else { 2 };
+    let channel = |i: usize| {
+        let value = u8::from_str_radix(&hex[i * digits..(i + 1) * digits], 16).unwrap_or_default();
+        if digits == 1 {
+            value * 17
+        } else {
+            value
+        }
+    };
+    let alpha = if matches!(hex.len(), 4 | 8) {
+        channel(3) as f32 / 255.
+    } else {
+        1.
+    };
+
+    Ok(rgba(channel(0), channel(1), channel(2), alpha).into())
+}
+
+/// Formats a color as a `#rrggbb` string, or `#rrggbbaa` if it's not opaque.
+pub fn to_hex_string(color: Hsla) -> String {
+    let Rgba { r, g, b, a } = Rgba::from(color);
+    let channel = |value: f32| (value.clamp(0., 1.) * 255.).round() as u8;
+    if channel(a) == 255 {
+        format!("#{:02x}{:02x}{:02x}", channel(r), channel(g), channel(b))
+    } else {
+        format!(
+            "#{:02x}{:02x}{:02x}{:02x}",
+            channel(r),
+            channel(g),
+            channel(b),
+            channel(a)
+        )
+    }
+}
+
+/// Formats a color as a `hsl(h, s%, l%)` string, or `hsla(h, s%, l%, a)` if it's not opaque.
+///
+/// Values are rounded to one decimal.
+pub fn to_hsl_string(color: Hsla) -> String {
+    let round = |value: f32| (value * 10.).round() / 10.;
+    let (h, s, l) = (
+        round(color.h * 360.),
+        round(color.s * 100.),
+        round(color.l * 100.),
+    );
+    if color.a >= 1. {
+        format!("hsl({h}, {s}%, {l}%)")
+    } else {
+        format!(
+            "hsla({h}, {s}%, {l}%, {})",
+            (color.a * 1000.).round() / 1000.
+        )
+    }
+}
+
+fn parse_number(value: &str) -> Result<f32> {
+    value
+        .parse::<f32>()
+        .ok()
+        .filter(|v| v.is_finite())
+        .ok_or_else(|| anyhow!("invalid number `{value}`"))
+}
+
+fn parse_u8(value: &str) -> Result<u8> {
+    value
+        .parse::<u8>()
+        .map_err(|_| anyhow!("invalid color channel `{value}`, expected 0..255"))
+}
+
+fn parse_alpha(value: &str) -> Result<f32> {
+    let alpha = parse_number(value)?;
+    if !(0.0..=1.0).contains(&alpha) {
+        bail!("invalid alpha `{value}`, expected 0.0..1.0");
+    }
+    Ok(alpha)
+}
+
+fn parse_hue(value: &str) -> Result<f32> {
+    Ok(parse_number(value.strip_suffix("deg").unwrap_or(value))?.rem_euclid(360.))
+}
+
+fn parse_percent(value: &str) -> Result<f32> {
+    let Some(percent) = value.strip_suffix('%') else {
+        bail!("invalid percentage `{value}`, expected a value like `50%`");
+    };
+    let percent = parse_number(percent)?;
+    if !(0.0..=100.0).contains(&percent) {
+        bail!("invalid percentage `{value}`, expected 0%..100%");
+    }
+    Ok(percent)
+}
+
+/// The minimum WCAG contrast ratio of normal text to reach the AA level.
+pub const WCAG_AA_CONTRAST: f32 = 4.5;
+
+/// The minimum WCAG contrast ratio of normal text to reach the AAA level.
+pub const WCAG_AAA_CONTRAST: f32 = 7.;
+
+/// Returns the relative luminance of a color, from 0.0 (black) to 1.0 (white).
+///
+/// See <https://www.w3.org/TR/WCAG21/#dfn-relative-luminance>, the alpha is ignored.
+pub fn relative_luminance(color: Hsla) -> f32 {
+    let Rgba { r, g, b, .. } = Rgba::from(color);
+    let linear = |c: f32| {
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+
+    0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+}
+
+/// Returns the WCAG contrast ratio between two colors, from 1.0 to 21.0.
+///
+/// The order of the colors doesn't matter, see <https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio>.
+pub fn contrast_ratio(a: Hsla, b: Hsla) -> f32 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// Returns the APCA lightness contrast (Lc) of a text color on a background color.
+///
+/// The result is roughly from -108 to 106, positive for dark text on a light background
+/// and negative for light text on a dark background. An absolute value of 60 is the minimum
+/// for body text, see <https://github.com/Myndex/apca-w3>.
+pub fn apca_contrast(text: Hsla, bg: Hsla) -> f32 {
+    let luminance = |color: Hsla| {
+        let Rgba { r, g, b, .. } = Rgba::from(color);
+        let y = 0.2126729 * r.powf(2.4) + 0.7151522 * g.powf(2.4) + 0.072175 * b.powf(2.4);
+        if y < 0.022 {
+            y + (0.022 - y).powf(1.414)
+        } else {
+            y
+        }
+    };
+    let (text, bg) = (luminance(text), luminance(bg));
+    if (bg - text).abs() < 0.0005 {
+        return 0.;
+    }
+
+    let contrast = if bg > text {
+        let contrast = (bg.powf(0.56) - text.powf(0.57)) * 1.14;
+        if contrast < 0.1 {
+            0.
+        } else {
+            contrast - 0.027
+        }
+    } else {
+        let contrast = (bg.powf(0.65) - text.powf(0.62)) * 1.14;
+        if contrast > -0.1 {
+            0.
+        } else {
+            contrast + 0.027
+        }
+    };
+    contrast * 100.
+}
+
 /// Make a BoxShadow like CSS
 ///
 /// e.g:
@@ -97,4 +321,83 @@ mod tests {
             color.h, color.l, color.s, color.a
         );
     }
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color("#54a9ff").unwrap(), rgb(84, 169, 255).into());
+        assert_eq!(
+            parse_color("#54a9ff80").unwrap(),
+            rgba(84, 169, 255, 128. / 255.).into()
+        );
+        assert_eq!(
+            parse_color("rgb(84, 169, 255)").unwrap(),
+            rgb(84, 169, 255).into()
+        );
+        assert_eq!(
+            parse_color("rgba(84, 169, 255, 0.5)").unwrap(),
+            rgba(84, 169, 255, 0.5).into()
+        );
+        assert_eq!(
+            parse_color("hsl(212, 100%, 47%)").unwrap(),
+            hsl(212., 100., 47.)
+        );
+        assert_eq!(
+            parse_color("hsla(212, 100%, 47%, 0.5)").unwrap(),
+            hsla(212. / 360., 1., 0.47, 0.5)
+        );
+
+        assert_eq!(parse_color("#5af").unwrap(), rgb(85, 170, 255).into());
+        assert_eq!(
+            parse_color("#5af8").unwrap(),
+            rgba(85, 170, 255, 136. / 255.).into()
+        );
+        assert_eq!(parse_color("tomato").unwrap(), rgb(255, 99, 71).into());
+        assert_eq!(
+            parse_color("RebeccaPurple").unwrap(),
+            rgb(102, 51, 153).into()
+        );
+        assert_eq!(parse_color("transparent").unwrap().a, 0.);
+
+        assert!(parse_color("#54a9f").is_err());
+        assert!(parse_color("tomatoes").is_err());
+        assert!(parse_color("#54a9zz").is_err());
+        assert!(parse_color("rgb(84, 169)").is_err());
+        assert!(parse_color("rgb(84, 169, 256)").is_err());
+        assert!(parse_color("hsl(212, 100, 47%)").is_err());
+        assert!(parse_color("cmyk(0, 0, 0, 0)").is_err());
+    }
+
+    #[test]
+    fn test_format_color() {
+        assert_eq!(to_hex_string(rgb(84, 169, 255).into()), "#54a9ff");
+        assert_eq!(to_hex_string(rgba(84, 169, 255, 0.5).into()), "#54a9ff80");
+        assert_eq!(to_hsl_string(hsl(212., 100., 47.)), "hsl(212, 100%, 47%)");
+        assert_eq!(
+            to_hsl_string(hsla(212. / 360., 1., 0.47, 0.5)),
+            "hsla(212, 100%, 47%, 0.5)"
+        );
+
+        for value in ["#0070f3", "#54a9ff80", "#000000", "#ffffff"] {
+            assert_eq!(to_hex_string(parse_color(value).unwrap()), value);
+        }
+    }
+
+    #[test]
+    fn test_contrast() {
+        let white: Hsla = rgb(255, 255, 255).into();
+        let black: Hsla = rgb(0, 0, 0).into();
+
+        assert!((relative_luminance(white) - 1.).abs() < 1e-4);
+        assert!(relative_luminance(black).abs() < 1e-4);
+        assert!((contrast_ratio(white, black) - 21.).abs() < 1e-2);
+        assert!((contrast_ratio(black, white) - 21.).abs() < 1e-2);
+        assert!((contrast_ratio(white, white) - 1.).abs() < 1e-4);
+        // #767676 is the lightest gray reaching AA on white.
+        assert!(contrast_ratio(rgb(118, 118, 118).into(), white) >= WCAG_AA_CONTRAST);
+        assert!(contrast_ratio(rgb(119, 119, 119).into(), white) < WCAG_AA_CONTRAST);
+
+        assert!((apca_contrast(black, white) - 106.04).abs() < 0.1);
+        assert!((apca_contrast(white, black) + 107.88).abs() < 0.1);
+        assert_eq!(apca_contrast(white, white), 0.);
+    }
 }
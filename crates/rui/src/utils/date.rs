@@ -0,0 +1,233 @@
+use std::fmt;
+
+use chrono::Datelike;
+
+/// A day of the week.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Weekday {
+    #[default]
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    const ALL: [Weekday; 7] = [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+        Weekday::Sunday,
+    ];
+
+    /// The number of days since the previous Monday, 0 for Monday.
+    pub fn days_from_monday(self) -> u32 {
+        self as u32
+    }
+
+    /// Returns the day `days` after this one, wrapping around the week.
+    pub fn add_days(self, days: u32) -> Self {
+        Self::ALL[((self as u32 + days) % 7) as usize]
+    }
+
+    /// Two letters, `Mo` for Monday.
+    pub fn short_name(self) -> &'static str {
+        match self {
+            Weekday::Monday => "Mo",
+            Weekday::Tuesday => "Tu",
+            Weekday::Wednesday => "We",
+            Weekday::Thursday => "Th",
+            Weekday::Friday => "Fr",
+            Weekday::Saturday => "Sa",
+            Weekday::Sunday => "Su",
+        }
+    }
+}
+
+/// A calendar date in the proleptic Gregorian calendar, without a time zone.
+///
+/// Dates compare chronologically, and format and parse as ISO 8601, `2025-03-09`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+impl Date {
+    /// Returns the date, or `None` if the month or the day is out of range.
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+        Some(Self { year, month, day })
+    }
+
+    /// Returns the current date in the local time zone.
+    pub fn today() -> Self {
+        let today = chrono::Local::now().date_naive();
+        Self {
+            year: today.year(),
+            month: today.month(),
+            day: today.day(),
+        }
+    }
+
+    pub fn year(self) -> i32 {
+        self.year
+    }
+
+    /// The month, from 1 for January to 12.
+    pub fn month(self) -> u32 {
+        self.month
+    }
+
+    pub fn day(self) -> u32 {
+        self.day
+    }
+
+    pub fn weekday(self) -> Weekday {
+        // 1970-01-01 was a Thursday.
+        Weekday::Thursday.add_days(self.to_days().rem_euclid(7) as u32)
+    }
+
+    /// The number of days in the month of this date.
+    pub fn days_in_month(self) -> u32 {
+        days_in_month(self.year, self.month)
+    }
+
+    /// The first day of the month of this date.
+    pub fn first_of_month(self) -> Self {
+        Self { day: 1, ..self }
+    }
+
+    pub fn add_days(self, days: i64) -> Self {
+        Self::from_days(self.to_days() + days)
+    }
+
+    /// Adds calendar months, clamping the day to the end of the month, so one month after
+    /// January 31 is the end of February.
+    pub fn add_months(self, months: i32) -> Self {
+        let months = self.year * 12 + self.month as i32 - 1 + months;
+        let (year, month) = (months.div_euclid(12), months.rem_euclid(12) as u32 + 1);
+        let day = self.day.min(days_in_month(year, month));
+        Self { year, month, day }
+    }
+
+    /// Parses an ISO 8601 date, `2025-03-09`, the month and day may have one digit.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parts = text.trim().splitn(3, '-');
+        let year = parts.next()?.parse().ok()?;
+        let month = parts.next()?.parse().ok()?;
+        let day = parts.next()?.parse().ok()?;
+        Self::new(year, month, day)
+    }
+
+    /// The number of days since 1970-01-01.
+    fn to_days(self) -> i64 {
+        // See http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+        let year = self.year as i64 - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = (self.month as i64 + 9) % 12;
+        let day_of_year = (153 * month + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    fn from_days(days: i64) -> Self {
+        // See http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * month + 2) / 5 + 1) as u32;
+        let month = if month < 10 { month + 3 } else { month - 9 } as u32;
+        let year = (year_of_era + era * 400) as i32 + i32::from(month <= 2);
+        Self { year, month, day }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> Date {
+        Date::new(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_new_and_parse() {
+        assert_eq!(Date::new(2024, 2, 29), Some(date(2024, 2, 29)));
+        assert_eq!(Date::new(2023, 2, 29), None);
+        assert_eq!(Date::new(1900, 2, 29), None);
+        assert_eq!(Date::new(2000, 2, 29), Some(date(2000, 2, 29)));
+        assert_eq!(Date::new(2025, 13, 1), None);
+        assert_eq!(Date::new(2025, 4, 31), None);
+
+        assert_eq!(Date::parse("2025-03-09"), Some(date(2025, 3, 9)));
+        assert_eq!(Date::parse(" 2025-3-9 "), Some(date(2025, 3, 9)));
+        assert_eq!(Date::parse("2025-03"), None);
+        assert_eq!(Date::parse("2025-03-09x"), None);
+        assert_eq!(date(2025, 3, 9).to_string(), "2025-03-09");
+    }
+
+    #[test]
+    fn test_days() {
+        for (date, days) in [
+            (date(1970, 1, 1), 0),
+            (date(1969, 12, 31), -1),
+            (date(2000, 3, 1), 11_017),
+            (date(2024, 2, 29), 19_782),
+        ] {
+            assert_eq!(date.to_days(), days);
+            assert_eq!(Date::from_days(days), date);
+        }
+        assert_eq!(date(2024, 12, 31).add_days(1), date(2025, 1, 1));
+        assert_eq!(date(2024, 3, 1).add_days(-1), date(2024, 2, 29));
+    }
+
+    #[test]
+    fn test_weekday() {
+        assert_eq!(date(1970, 1, 1).weekday(), Weekday::Thursday);
+        assert_eq!(date(2025, 3, 9).weekday(), Weekday::Sunday);
+        assert_eq!(date(1969, 12, 29).weekday(), Weekday::Monday);
+        assert_eq!(Weekday::Saturday.add_days(2), Weekday::Monday);
+    }
+
+    #[test]
+    fn test_add_months() {
+        assert_eq!(date(2025, 1, 31).add_months(1), date(2025, 2, 28));
+        assert_eq!(date(2024, 1, 31).add_months(1), date(2024, 2, 29));
+        assert_eq!(date(2025, 1, 15).add_months(-1), date(2024, 12, 15));
+        assert_eq!(date(2025, 11, 1).add_months(14), date(2027, 1, 1));
+    }
+}
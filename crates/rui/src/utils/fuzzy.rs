@@ -0,0 +1,161 @@
+use std::ops::Range;
+
+const SCORE_MATCH: i32 = 16;
+/// Added when a char matches at the start of a word, `b` in `foo_bar` or `fooBar`.
+const BONUS_BOUNDARY: i32 = 8;
+/// Added when a char matches right after the previous one.
+const BONUS_CONSECUTIVE: i32 = 8;
+/// Added when a char matches with the same case.
+const BONUS_CASE: i32 = 1;
+/// Removed for every char skipped between two matched chars.
+const PENALTY_GAP: i32 = 1;
+
+/// A match of a query in a text, see [`fuzzy_match`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// Higher is better, only comparable between matches of the same query.
+    pub score: i32,
+    /// The byte offsets of the matched chars in the text.
+    pub positions: Vec<usize>,
+}
+
+impl FuzzyMatch {
+    /// Returns the matched byte ranges of `text`, consecutive chars merged together.
+    pub fn ranges(&self, text: &str) -> Vec<Range<usize>> {
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+        for &start in &self.positions {
+            let end = start + text[start..].chars().next().map_or(0, char::len_utf8);
+            match ranges.last_mut() {
+                Some(range) if range.end == start => range.end = end,
+                _ => ranges.push(start..end),
+            }
+        }
+        ranges
+    }
+}
+
+/// Matches the chars of `query` in order in `text`, ignoring case and whitespace in the query.
+///
+/// Of all the ways the query can match, returns the one preferring word starts and
+/// consecutive chars, or `None` if the query doesn't match. An empty query matches anything
+/// with a score of 0.
+pub fn fuzzy_match(query: &str, text: &str) -> Option<FuzzyMatch> {
+    let query: Vec<char> = query.chars().filter(|c| !c.is_whitespace()).collect();
+    if query.is_empty() {
+        return Some(FuzzyMatch {
+            score: 0,
+            positions: Vec::new(),
+        });
+    }
+    let text: Vec<(usize, char)> = text.char_indices().collect();
+    let (m, n) = (query.len(), text.len());
+    if m > n {
+        return None;
+    }
+
+    // scores[i * n + j] is the best score of `query[..=i]` with `query[i]` matched at
+    // `text[j]`, and previous[i * n + j] where `query[i - 1]` was matched in that case.
+    let mut scores: Vec<Option<i32>> = vec![None; m * n];
+    let mut previous = vec![0; m * n];
+    for (i, &q) in query.iter().enumerate() {
+        // The best match of the previous query char before `j - 1`, less the gap until `j`.
+        let mut best_gap: Option<(i32, usize)> = None;
+        for j in i..n {
+            if i > 0 && j >= 2 {
+                best_gap = best_gap.map(|(score, k)| (score - PENALTY_GAP, k));
+                if let Some(score) = scores[(i - 1) * n + j - 2] {
+                    let score = score - PENALTY_GAP;
+                    if best_gap.is_none_or(|(best, _)| score > best) {
+                        best_gap = Some((score, j - 2));
+                    }
+                }
+            }
+
+            let c = text[j].1;
+            if !chars_eq(q, c) {
+                continue;
+            }
+            let mut score = SCORE_MATCH;
+            if q == c {
+                score += BONUS_CASE;
+            }
+            if j == 0 || is_boundary(text[j - 1].1, c) {
+                score += BONUS_BOUNDARY;
+            }
+            if i > 0 {
+                let consecutive =
+                    scores[(i - 1) * n + j - 1].map(|score| (score + BONUS_CONSECUTIVE, j - 1));
+                let best = match (consecutive, best_gap) {
+                    (Some(a), Some(b)) => Some(if a.0 >= b.0 { a } else { b }),
+                    (a, b) => a.or(b),
+                };
+                let Some((best, k)) = best else {
+                    continue;
+                };
+                score += best;
+                previous[i * n + j] = k;
+            }
+            scores[i * n + j] = Some(score);
+        }
+    }
+
+    let last = (m - 1) * n;
+    let (mut j, score) = (0..n)
+        .filter_map(|j| scores[last + j].map(|score| (j, score)))
+        .max_by_key(|&(j, score)| (score, std::cmp::Reverse(j)))?;
+    let mut positions = vec![0; m];
+    for i in (0..m).rev() {
+        positions[i] = text[j].0;
+        j = previous[i * n + j];
+    }
+    Some(FuzzyMatch { score, positions })
+}
+
+fn chars_eq(a: char, b: char) -> bool {
+    a == b || a.to_lowercase().eq(b.to_lowercase())
+}
+
+fn is_boundary(previous: char, c: char) -> bool {
+    !previous.is_alphanumeric() || previous.is_lowercase() && c.is_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn positions(query: &str, text: &str) -> Option<Vec<usize>> {
+        fuzzy_match(query, text).map(|m| m.positions)
+    }
+
+    #[test]
+    fn test_fuzzy_match() {
+        assert_eq!(positions("", "abc"), Some(vec![]));
+        assert_eq!(positions("abc", "a_b_c"), Some(vec![0, 2, 4]));
+        assert_eq!(positions("ABC", "abc"), Some(vec![0, 1, 2]));
+        assert_eq!(positions("a c", "abc"), Some(vec![0, 2]));
+        assert_eq!(positions("ba", "abc"), None);
+        assert_eq!(positions("abcd", "abc"), None);
+        assert_eq!(positions("é", "Élan"), Some(vec![0]));
+
+        // Prefers consecutive chars and word starts over the first occurrence.
+        assert_eq!(positions("ab", "axxb ab"), Some(vec![5, 6]));
+        assert_eq!(positions("fb", "afbx foo_bar"), Some(vec![5, 9]));
+        assert_eq!(positions("fb", "xfxxbx fooBar"), Some(vec![7, 10]));
+    }
+
+    #[test]
+    fn test_fuzzy_score() {
+        let score = |query, text| fuzzy_match(query, text).unwrap().score;
+        assert!(score("foo", "foobar") > score("foo", "f_o_o"));
+        assert!(score("fb", "FooBar") > score("fb", "xfxxbx"));
+        assert!(score("bar", "bar") > score("bar", "Bar"));
+        assert!(score("ab", "a_b") > score("ab", "a__b"));
+    }
+
+    #[test]
+    fn test_ranges() {
+        let text = "foo_bår";
+        let m = fuzzy_match("fobå", text).unwrap();
+        assert_eq!(m.ranges(text), vec![0..2, 4..7]);
+    }
+}
@@ -0,0 +1,210 @@
+use gpui::{Hsla, Rgba};
+
+/// A color in the [OKLab](https://bottosson.github.io/posts/oklab/) perceptual color space.
+///
+/// - l: lightness, 0.0..1.0
+/// - a: green/red axis, about -0.4..0.4
+/// - b: blue/yellow axis, about -0.4..0.4
+/// - alpha: 0.0..1.0
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklab {
+    pub l: f32,
+    pub a: f32,
+    pub b: f32,
+    pub alpha: f32,
+}
+
+/// A color in the OKLCH color space, the polar form of [`Oklab`].
+///
+/// - l: lightness, 0.0..1.0
+/// - c: chroma, 0.0..about 0.37
+/// - h: hue in degrees, 0.0..360.0
+/// - alpha: 0.0..1.0
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklch {
+    pub l: f32,
+    pub c: f32,
+    pub h: f32,
+    pub alpha: f32,
+}
+
+fn to_linear(c: f64) -> f64 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn from_linear(c: f64) -> f64 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1. / 2.4) - 0.055
+    }
+}
+
+impl From<Hsla> for Oklab {
+    fn from(color: Hsla) -> Self {
+        let Rgba { r, g, b, a } = Rgba::from(color);
+        let (r, g, b) = (
+            to_linear(r as f64),
+            to_linear(g as f64),
+            to_linear(b as f64),
+        );
+
+        let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+        let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+        let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+
+        Self {
+            l: (0.2104542553 * l + 0.793617785 * m - 0.0040720468 * s) as f32,
+            a: (1.9779984951 * l - 2.428592205 * m + 0.4505937099 * s) as f32,
+            b: (0.0259040371 * l + 0.7827717662 * m - 0.808675766 * s) as f32,
+            alpha: a,
+        }
+    }
+}
+
+impl From<Oklab> for Hsla {
+    /// Converts back to sRGB, colors out of the sRGB gamut are clipped.
+    fn from(color: Oklab) -> Self {
+        let (l, a, b) = (color.l as f64, color.a as f64, color.b as f64);
+        let l_ = (l + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+        let m_ = (l - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+        let s_ = (l - 0.0894841775 * a - 1.291485548 * b).powi(3);
+
+        let channel = |c: f64| from_linear(c).clamp(0., 1.) as f32;
+        Rgba {
+            r: channel(4.0767416621 * l_ - 3.3077115913 * m_ + 0.2309699292 * s_),
+            g: channel(-1.2684380046 * l_ + 2.6097574011 * m_ - 0.3413193965 * s_),
+            b: channel(-0.0041960863 * l_ - 0.7034186147 * m_ + 1.707614701 * s_),
+            a: color.alpha,
+        }
+        .into()
+    }
+}
+
+impl From<Oklab> for Oklch {
+    fn from(color: Oklab) -> Self {
+        Self {
+            l: color.l,
+            c: color.a.hypot(color.b),
+            h: color.b.atan2(color.a).to_degrees().rem_euclid(360.),
+            alpha: color.alpha,
+        }
+    }
+}
+
+impl From<Oklch> for Oklab {
+    fn from(color: Oklch) -> Self {
+        let h = color.h.to_radians();
+        Self {
+            l: color.l,
+            a: color.c * h.cos(),
+            b: color.c * h.sin(),
+            alpha: color.alpha,
+        }
+    }
+}
+
+impl From<Hsla> for Oklch {
+    fn from(color: Hsla) -> Self {
+        Oklab::from(color).into()
+    }
+}
+
+impl From<Oklch> for Hsla {
+    fn from(color: Oklch) -> Self {
+        Oklab::from(color).into()
+    }
+}
+
+/// Mixes two colors in the OKLab color space.
+///
+/// `amount` is the proportion of `b`, from 0.0 (only `a`) to 1.0 (only `b`).
+pub fn mix(a: Hsla, b: Hsla, amount: f32) -> Hsla {
+    let amount = amount.clamp(0., 1.);
+    let (a, b) = (Oklab::from(a), Oklab::from(b));
+    let lerp = |a: f32, b: f32| a + (b - a) * amount;
+
+    Oklab {
+        l: lerp(a.l, b.l),
+        a: lerp(a.a, b.a),
+        b: lerp(a.b, b.b),
+        alpha: lerp(a.alpha, b.alpha),
+    }
+    .into()
+}
+
+/// Increases the OKLCH lightness of a color by `amount`, from 0.0 to 1.0.
+pub fn lighten(color: Hsla, amount: f32) -> Hsla {
+    let mut color = Oklch::from(color);
+    color.l = (color.l + amount).clamp(0., 1.);
+    color.into()
+}
+
+/// Decreases the OKLCH lightness of a color by `amount`, from 0.0 to 1.0.
+pub fn darken(color: Hsla, amount: f32) -> Hsla {
+    lighten(color, -amount)
+}
+
+/// Scales the OKLCH chroma of a color up by `amount`, e.g. `0.2` for 20% more colorful.
+pub fn saturate(color: Hsla, amount: f32) -> Hsla {
+    let mut color = Oklch::from(color);
+    color.c = (color.c * (1. + amount)).max(0.);
+    color.into()
+}
+
+/// Scales the OKLCH chroma of a color down by `amount`, `1.0` makes it gray.
+pub fn desaturate(color: Hsla, amount: f32) -> Hsla {
+    saturate(color, -amount.clamp(0., 1.))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{rgb, to_hex_string};
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-3, "{a} != {b}");
+    }
+
+    #[test]
+    fn test_oklab() {
+        let red = Oklab::from(Hsla::from(rgb(255, 0, 0)));
+        assert_close(red.l, 0.62796);
+        assert_close(red.a, 0.22486);
+        assert_close(red.b, 0.12585);
+
+        let red = Oklch::from(red);
+        assert_close(red.c, 0.25768);
+        assert_close(red.h, 29.2339);
+
+        let white = Oklch::from(Hsla::from(rgb(255, 255, 255)));
+        assert_close(white.l, 1.);
+        assert_close(white.c, 0.);
+
+        for hex in ["#0070f3", "#54a9ff", "#dc322f", "#000000", "#ffffff"] {
+            let color = crate::parse_color(hex).unwrap();
+            assert_eq!(to_hex_string(Oklch::from(color).into()), hex);
+        }
+    }
+
+    #[test]
+    fn test_color_helpers() {
+        let black = Hsla::from(rgb(0, 0, 0));
+        let white = Hsla::from(rgb(255, 255, 255));
+        let blue = Hsla::from(rgb(0, 112, 243));
+
+        assert_eq!(to_hex_string(mix(black, white, 0.)), "#000000");
+        assert_eq!(to_hex_string(mix(black, white, 1.)), "#ffffff");
+        assert_close(Oklab::from(mix(black, white, 0.5)).l, 0.5);
+
+        assert!(Oklch::from(lighten(blue, 0.1)).l > Oklch::from(blue).l);
+        assert!(Oklch::from(darken(blue, 0.1)).l < Oklch::from(blue).l);
+        assert!(Oklch::from(desaturate(blue, 0.5)).c < Oklch::from(blue).c);
+        assert_close(Oklch::from(desaturate(blue, 1.)).c, 0.);
+        assert_close(Oklch::from(darken(blue, 1.)).l, 0.);
+    }
+}
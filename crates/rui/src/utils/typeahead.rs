@@ -0,0 +1,94 @@
+use std::time::{Duration, Instant};
+
+/// Typed chars within this delay are matched together.
+const TYPEAHEAD_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Type-to-select for lists, jumps to the first item whose label starts with the typed text.
+///
+/// A repeated char, `aaa`, cycles through the items starting with it.
+#[derive(Debug, Default)]
+pub(crate) struct Typeahead {
+    text: String,
+    typed_at: Option<Instant>,
+}
+
+impl Typeahead {
+    /// Adds a typed char and returns the index of the item to jump to, if any.
+    ///
+    /// `labels` has the label of each item, `None` for the items that can't be selected, and
+    /// `current` is the index of the item selected now. Leading whitespace is ignored.
+    pub fn type_char<'a>(
+        &mut self,
+        key_char: &str,
+        labels: impl IntoIterator<Item = Option<&'a str>>,
+        current: Option<usize>,
+    ) -> Option<usize> {
+        if self
+            .typed_at
+            .is_none_or(|typed_at| typed_at.elapsed() > TYPEAHEAD_TIMEOUT)
+        {
+            self.text.clear();
+        }
+        if self.text.is_empty() && key_char.trim().is_empty() {
+            return None;
+        }
+        self.text.push_str(&key_char.to_lowercase());
+        self.typed_at = Some(Instant::now());
+        find_match(&self.text, labels, current)
+    }
+}
+
+/// Finds the item to jump to for the lowercase `typed` text, see [`Typeahead::type_char`].
+fn find_match<'a>(
+    typed: &str,
+    labels: impl IntoIterator<Item = Option<&'a str>>,
+    current: Option<usize>,
+) -> Option<usize> {
+    let cycling = typed.chars().count() > 1 && typed.chars().all(|c| typed.starts_with(c));
+    let query = if cycling {
+        &typed[..typed.chars().next().map_or(0, char::len_utf8)]
+    } else {
+        typed
+    };
+    let matches: Vec<usize> = labels
+        .into_iter()
+        .enumerate()
+        .filter(|(_, label)| label.is_some_and(|label| label.to_lowercase().starts_with(query)))
+        .map(|(ix, _)| ix)
+        .collect();
+    match current {
+        Some(current) if cycling || typed.chars().count() == 1 => matches
+            .iter()
+            .copied()
+            .find(|ix| *ix > current)
+            .or(matches.first().copied()),
+        Some(current) if matches.contains(&current) => Some(current),
+        _ => matches.first().copied(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_match() {
+        let labels = [
+            Some("Apple"),
+            Some("Apricot"),
+            None,
+            Some("Banana"),
+            Some("avocado"),
+        ];
+        let labels = || labels.iter().copied();
+
+        assert_eq!(find_match("a", labels(), None), Some(0));
+        assert_eq!(find_match("a", labels(), Some(0)), Some(1));
+        assert_eq!(find_match("a", labels(), Some(4)), Some(0));
+        assert_eq!(find_match("aa", labels(), Some(1)), Some(4));
+        assert_eq!(find_match("apr", labels(), Some(1)), Some(1));
+        assert_eq!(find_match("apr", labels(), Some(0)), Some(1));
+        assert_eq!(find_match("b", labels(), Some(0)), Some(3));
+        assert_eq!(find_match("c", labels(), Some(0)), None);
+    }
+}
@@ -1,4 +1,15 @@
-use rui::{prelude::*, AlertModal, Button, Color, IconName, Modal, Root, Text, Theme};
+use rui::{
+    prelude::*, AlertModal, Button, Color, IconName, Modal, ModalWidth, Root, Text, Theme,
+};
+
+/// Opens a modal with a short text, set up by `f`.
+fn open_modal(window: &mut Window, cx: &mut App, title: &'static str, f: fn(Modal) -> Modal) {
+    window.toggle_modal(cx, move |_window, cx| {
+        f(Modal::new(cx, title).show_close()).content(|_window, _cx| {
+            Text::new("Press Escape to close.").into_any_element()
+        })
+    })
+}
 
 struct AlertModalStory {}
 
@@ -58,7 +69,14 @@ impl Render for AlertModalStory {
                                         h_flex()
                                             .items_center()
                                             .gap_1()
-                                            .child(Button::new("Ok").text("Ok"))
+                                            .child(Button::new("Ok").text("Ok").on_click(|_, window, cx| {
+                                                // Stacks a confirmation above this modal, Escape closes it first.
+                                                window.push_modal(cx, |_window, cx| {
+                                                    Modal::new(cx, "Are you sure?").show_close().content(|_window, _cx| {
+                                                        Text::new("Press Escape to go back to the modal below.").into_any_element()
+                                                    })
+                                                })
+                                            }))
                                             .child(Button::new("Cancel").text("Cancel").soft()),
                                         )
                                 )
@@ -67,6 +85,46 @@ impl Render for AlertModalStory {
                     })
                 })
 
+            Row! {
+                Button::new("small-modal")
+                    .text("Small")
+                    .on_click(|_, window, cx| open_modal(window, cx, "Small", |m| m.with_width(ModalWidth::Small)))
+                Button::new("large-modal")
+                    .text("Large")
+                    .on_click(|_, window, cx| open_modal(window, cx, "Large", |m| m.with_width(ModalWidth::Large)))
+                Button::new("full-modal")
+                    .text("Full width")
+                    .on_click(|_, window, cx| open_modal(window, cx, "Full width", |m| m.with_width(ModalWidth::Full)))
+                Button::new("fullscreen-modal")
+                    .text("Fullscreen")
+                    .on_click(|_, window, cx| open_modal(window, cx, "Fullscreen", Modal::fullscreen))
+                Button::new("sticky-modal")
+                    .text("Non-dismissable backdrop")
+                    .on_click(|_, window, cx| {
+                        open_modal(window, cx, "Backdrop clicks are ignored", |m| m.dismiss_on_backdrop_click(false))
+                    })
+            }
+            .gap_1()
+
+            Row! {
+                Button::new("left-drawer")
+                    .text("Left drawer")
+                    .on_click(|_, window, cx| {
+                        open_modal(window, cx, "Left drawer", |m| m.drawer(AnimationDirection::FromLeft))
+                    })
+                Button::new("right-drawer")
+                    .text("Right drawer")
+                    .on_click(|_, window, cx| {
+                        open_modal(window, cx, "Right drawer", |m| m.drawer(AnimationDirection::FromRight).with_width(ModalWidth::Small))
+                    })
+                Button::new("bottom-sheet")
+                    .text("Bottom sheet")
+                    .on_click(|_, window, cx| {
+                        open_modal(window, cx, "Bottom sheet", |m| m.drawer(AnimationDirection::FromBottom))
+                    })
+            }
+            .gap_1()
+
             Button::new("appearance")
                 .text(cx.theme().appearance.to_string())
                 .on_click(cx.listener(|_, _, window, cx| {
@@ -90,9 +148,9 @@ fn main() {
                 window_bounds: Some(WindowBounds::Windowed(bounds)),
                 ..Default::default()
             },
-            |_window, cx| {
+            |window, cx| {
                 let view = cx.new(|_cx| AlertModalStory {});
-                cx.new(|cx| Root::new(cx, view.into()))
+                cx.new(|cx| Root::new(view.into(), window, cx))
             },
         )
         .unwrap();
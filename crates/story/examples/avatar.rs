@@ -56,7 +56,7 @@ fn main() {
                     window_bounds: Some(WindowBounds::Windowed(bounds)),
                     ..Default::default()
                 },
-                |_, cx| {
+                |window, cx| {
                     let view = cx.new(|_| AvatarStory {
                         local_resource: manifest_dir
                             .join("../../assets/images/app-icon.png")
@@ -64,7 +64,7 @@ fn main() {
                         remote_resource: "https://picsum.photos/512/512".into(),
                         asset_resource: "images/logo.jpg".into(),
                     });
-                    cx.new(|cx| Root::new(cx, view.into()))
+                    cx.new(|cx| Root::new(view.into(), window, cx))
                 },
             )
             .unwrap();
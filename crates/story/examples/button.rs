@@ -114,9 +114,9 @@ fn main() {
                 window_bounds: Some(WindowBounds::Windowed(bounds)),
                 ..Default::default()
             },
-            |_, cx| {
+            |window, cx| {
                 let view = cx.new(|_| ButtonStory {});
-                cx.new(|cx| Root::new(cx, view.into()))
+                cx.new(|cx| Root::new(view.into(), window, cx))
             },
         )
         .unwrap();
@@ -0,0 +1,141 @@
+use gpui::{Entity, Subscription};
+use rui::{
+    prelude::*, Calendar, CalendarEvent, Date, DatePicker, DateSelection, Text, TextInput, Weekday,
+};
+
+struct CalendarStory {
+    calendar: Entity<Calendar>,
+    range_calendar: Entity<Calendar>,
+    limited: Entity<Calendar>,
+    date_picker: Entity<DatePicker>,
+    range_picker: Entity<DatePicker>,
+    weekday_picker: Entity<DatePicker>,
+    picked: SharedString,
+    _subscriptions: Vec<Subscription>,
+}
+
+fn is_weekend(date: Date) -> bool {
+    matches!(date.weekday(), Weekday::Saturday | Weekday::Sunday)
+}
+
+impl CalendarStory {
+    fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let today = Date::today();
+        let calendar = cx.new(|_| Calendar::new().selected(today));
+        let range_calendar = cx.new(|_| {
+            Calendar::new()
+                .week_start(Weekday::Sunday)
+                .selected_range(today, today.add_days(4))
+                .on_change(|selection, _window, _cx| println!("range: {selection:?}"))
+        });
+        // Only the weekdays of the next 30 days can be picked.
+        let limited = cx.new(|_| {
+            Calendar::new()
+                .min(today)
+                .max(today.add_days(30))
+                .disabled_days(is_weekend)
+        });
+
+        let date_picker = cx.new(|cx| {
+            DatePicker::new(window, cx)
+                .on_change(|selection, _window, _cx| println!("date: {selection:?}"))
+        });
+        let range_picker = cx.new(|cx| {
+            DatePicker::new(window, cx)
+                .selected_range(today.add_days(-7), today)
+                .max(today)
+        });
+        let weekday_picker = cx.new(|cx| {
+            DatePicker::new(window, cx)
+                .placeholder("Pick a weekday")
+                .size(Size::Small)
+                .disabled_days(is_weekend)
+        });
+
+        let subscriptions = vec![cx.subscribe(&calendar, |this, _, event, cx| {
+            let CalendarEvent::Change(DateSelection::Single(date) | DateSelection::Range(date, _)) =
+                event;
+            this.picked = format!("Picked {date}").into();
+            cx.notify();
+        })];
+
+        Self {
+            calendar,
+            range_calendar,
+            limited,
+            date_picker,
+            range_picker,
+            weekday_picker,
+            picked: format!("Today is {today}").into(),
+            _subscriptions: subscriptions,
+        }
+    }
+}
+
+impl Render for CalendarStory {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        Col! {
+            Row! {
+                Section! {
+                    "Calendar";
+                    self.calendar.clone()
+                    Text::new(self.picked.clone())
+                }
+                .gap_2()
+
+                Section! {
+                    "Range, weeks start on Sunday";
+                    self.range_calendar.clone()
+                }
+
+                Section! {
+                    "Weekdays of the next 30 days";
+                    self.limited.clone()
+                }
+            }
+            .gap_8()
+
+            Section! {
+                "Date picker";
+                self.date_picker.clone()
+            }
+            .w_80()
+
+            Section! {
+                "Range picker";
+                self.range_picker.clone()
+            }
+            .w_80()
+
+            Section! {
+                "Weekdays only";
+                self.weekday_picker.clone()
+            }
+            .w_80()
+        }
+        .p_4()
+        .gap_4()
+    }
+}
+
+fn main() {
+    Application::new().with_assets(Assets).run(|cx: &mut App| {
+        cx.activate(true);
+        Theme::init(cx, None, None);
+        TextInput::bind_keys(cx);
+        DatePicker::bind_keys(cx);
+
+        let bounds = Bounds::centered(None, size(px(900.), px(800.0)), cx);
+        cx.open_window(
+            WindowOptions {
+                window_bounds: Some(WindowBounds::Windowed(bounds)),
+                ..Default::default()
+            },
+            |window, cx| {
+                let view = cx.new(|cx| CalendarStory::new(window, cx));
+                cx.new(|cx| Root::new(view.into(), window, cx))
+            },
+        )
+        .unwrap();
+    });
+}
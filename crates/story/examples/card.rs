@@ -1,4 +1,4 @@
-use rui::{prelude::*, Root, Theme};
+use rui::{prelude::*, ElevationIndex, Root, Theme};
 
 struct CardStory;
 
@@ -11,6 +11,11 @@ impl Render for CardStory {
             Card::new()
                 .direction_horizontal()
                 .child("horizontal")
+
+            Card::new()
+                .elevation(ElevationIndex::ElevatedSurface)
+                .hover_elevation(ElevationIndex::ModalSurface)
+                .child("Elevated")
         }
         .p_4()
     }
@@ -26,9 +31,9 @@ fn main() {
                 window_bounds: Some(WindowBounds::Windowed(bounds)),
                 ..Default::default()
             },
-            |_, cx| {
+            |window, cx| {
                 let view = cx.new(|_| CardStory {});
-                cx.new(|cx| Root::new(cx, view.into()))
+                cx.new(|cx| Root::new(view.into(), window, cx))
             },
         )
         .unwrap();
@@ -1,23 +1,11 @@
-use rui::{prelude::*, Checkbox, CheckboxGroup, Divider, Switch, Text, ToggleState};
+use rui::{prelude::*, Checkbox, CheckboxGroup, CheckboxOption, Switch, Text, ToggleState};
 
 struct CheckboxStory {
     state: bool,
     second_state: bool,
     disabled: bool,
-    checked_indexes: Vec<usize>,
-    all_state: ToggleState,
-}
-
-impl CheckboxStory {
-    fn change_handler(&mut self, value: &Vec<usize>) {
-        println!("{:?}", *value);
-        self.checked_indexes = value.clone();
-        match self.checked_indexes.len() {
-            3 => self.all_state = ToggleState::Selected,
-            0 => self.all_state = ToggleState::Unselected,
-            _ => self.all_state = ToggleState::Indeterminate,
-        }
-    }
+    fruits: Vec<&'static str>,
+    toppings: Vec<usize>,
 }
 
 impl Render for CheckboxStory {
@@ -63,41 +51,36 @@ impl Render for CheckboxStory {
 
             Section! {
                 "Checkbox Group";
-
-                Checkbox::new("id")
-                    .checked(self.all_state)
-                    .text("Checkall")
-                    .on_click(cx.listener(|this, _v, _window, _cx| {
-                    if this.all_state.selected() {
-                        this.checked_indexes.clear();
-                        this.all_state = false.into();
-                    } else {
-                        this.checked_indexes = vec![0, 1, 2];
-                        this.all_state = true.into();
-                    }
-                }))
-
-                Divider::new().py_2()
-
-                CheckboxGroup::new()
-                    .checked_indexes(self.checked_indexes.clone())
-                    .children(["One", "Two", "Three"])
-                    .on_change(cx.listener(|this, checked_indexes: &Vec<usize>, _, _| {
-                        this.change_handler(checked_indexes)
+                CheckboxGroup::new("fruits")
+                    .select_all("All fruits")
+                    .checked(self.fruits.clone())
+                    .options(
+                        ["Apple", "Banana", "Cherry"]
+                            .map(|fruit| CheckboxOption::new(fruit, fruit)),
+                    )
+                    .option(CheckboxOption::new("Durian", "Durian").disabled(true))
+                    .on_change(cx.listener(|this, fruits: &[&'static str], _, _| {
+                        println!("{fruits:?}");
+                        this.fruits = fruits.to_vec();
                     }))
             }
 
             Section! {
-                "Checkbox Group Vertical";
-                CheckboxGroup::new()
-                    .vertical()
+                "Checkbox Group Vertical, 1 to 2 toppings";
+                CheckboxGroup::new("toppings")
+                    .direction_vertical()
                     .disabled(self.disabled)
-                    .checked_indexes(self.checked_indexes.clone())
-                    .child(Checkbox::new("one1").text("one1"))
-                    .child(Checkbox::new("one2").text("one2"))
-                    .child(Checkbox::new("one3").text(Text::new("one3")))
-                    .on_change(cx.listener(|this, checked_indexes: &Vec<usize>, _, _| {
-                        this.change_handler(checked_indexes)
+                    .min(1)
+                    .max(2)
+                    .checked(self.toppings.clone())
+                    .options(
+                        ["Cheese", "Mushrooms", "Olives", "Peppers"]
+                            .into_iter()
+                            .enumerate()
+                            .map(|(ix, topping)| CheckboxOption::new(ix, topping)),
+                    )
+                    .on_change(cx.listener(|this, toppings: &[usize], _, _| {
+                        this.toppings = toppings.to_vec();
                     }))
             }
         }
@@ -115,15 +98,15 @@ fn main() {
                 window_bounds: Some(WindowBounds::Windowed(bounds)),
                 ..Default::default()
             },
-            |_, cx| {
+            |window, cx| {
                 let view = cx.new(|_| CheckboxStory {
                     state: false,
                     second_state: false,
                     disabled: true,
-                    checked_indexes: Vec::new(),
-                    all_state: ToggleState::Unselected,
+                    fruits: vec!["Banana"],
+                    toppings: vec![0],
                 });
-                cx.new(|cx| Root::new(cx, view.into()))
+                cx.new(|cx| Root::new(view.into(), window, cx))
             },
         )
         .unwrap();
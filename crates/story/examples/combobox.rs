@@ -0,0 +1,143 @@
+use std::time::Duration;
+
+use gpui::{Entity, Subscription};
+use rui::{
+    bind_combobox_keys, prelude::*, Combobox, ComboboxEvent, ComboboxOption, Text, TextInput,
+};
+
+const ADJECTIVES: [&str; 40] = [
+    "Amber", "Brave", "Calm", "Dusty", "Eager", "Fuzzy", "Gentle", "Hollow", "Icy", "Jolly",
+    "Kind", "Lucky", "Misty", "Noble", "Odd", "Proud", "Quiet", "Rapid", "Silent", "Tidy", "Urban",
+    "Vivid", "Wild", "Young", "Zesty", "Bold", "Crisp", "Dark", "Early", "Fancy", "Grand", "Happy",
+    "Lively", "Mellow", "Neat", "Plain", "Rustic", "Sunny", "Warm", "Witty",
+];
+
+const NOUNS: [&str; 40] = [
+    "Badger", "Canyon", "Delta", "Falcon", "Garden", "Harbor", "Island", "Jungle", "Kettle",
+    "Lagoon", "Meadow", "Nebula", "Orchard", "Pebble", "Quarry", "River", "Summit", "Thicket",
+    "Valley", "Willow", "Anchor", "Beacon", "Comet", "Dune", "Ember", "Fjord", "Glacier", "Hollow",
+    "Inlet", "Lantern", "Marsh", "Oasis", "Prairie", "Reef", "Spruce", "Tundra", "Vortex", "Wharf",
+    "Yonder", "Zephyr",
+];
+
+/// Every adjective and noun pair, 1600 options.
+fn places() -> impl Iterator<Item = String> {
+    ADJECTIVES
+        .iter()
+        .flat_map(|adjective| NOUNS.iter().map(move |noun| format!("{adjective} {noun}")))
+}
+
+struct ComboboxStory {
+    place: Entity<Combobox<usize>>,
+    tags: Entity<Combobox<usize>>,
+    remote: Entity<Combobox<String>>,
+    tags_selected: usize,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ComboboxStory {
+    fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let place = cx.new(|cx| {
+            Combobox::new(window, cx)
+                .placeholder("Search 1600 places")
+                .options(
+                    places()
+                        .enumerate()
+                        .map(|(ix, place)| ComboboxOption::new(ix, place)),
+                )
+                .on_change(|values, _window, _cx| println!("place: {values:?}"))
+        });
+
+        let tags = cx.new(|cx| {
+            Combobox::new(window, cx)
+                .placeholder("Add tags")
+                .multiple(true)
+                .options(
+                    ["bug", "feature", "docs", "performance", "design", "wontfix"]
+                        .into_iter()
+                        .enumerate()
+                        .map(|(ix, tag)| ComboboxOption::new(ix, tag).disabled(tag == "wontfix")),
+                )
+        });
+
+        // Simulates a slow query, the provider runs on the background executor.
+        let remote = cx.new(|cx| {
+            Combobox::new(window, cx)
+                .placeholder("Search a remote index")
+                .size(Size::Small)
+                .provider(|query| async move {
+                    std::thread::sleep(Duration::from_millis(600));
+                    let query = query.to_lowercase();
+                    places()
+                        .filter(|place| place.to_lowercase().contains(&query))
+                        .take(50)
+                        .map(|place| ComboboxOption::new(place.clone(), place))
+                        .collect()
+                })
+        });
+
+        let subscriptions = vec![cx.subscribe(&tags, |this, _, event, cx| {
+            let ComboboxEvent::Change(values) = event;
+            this.tags_selected = values.len();
+            cx.notify();
+        })];
+
+        Self {
+            place,
+            tags,
+            remote,
+            tags_selected: 0,
+            _subscriptions: subscriptions,
+        }
+    }
+}
+
+impl Render for ComboboxStory {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        Col! {
+            Section! {
+                "Combobox";
+                self.place.clone()
+            }
+            .w_96()
+
+            Section! {
+                "Multiple";
+                self.tags.clone()
+                Text::new(format!("{} tags selected", self.tags_selected))
+            }
+            .w_96()
+            .gap_2()
+
+            Section! {
+                "Async provider";
+                self.remote.clone()
+            }
+            .w_96()
+        }
+        .p_4()
+        .gap_4()
+    }
+}
+
+fn main() {
+    Application::new().with_assets(Assets).run(|cx: &mut App| {
+        cx.activate(true);
+        Theme::init(cx, None, None);
+        TextInput::bind_keys(cx);
+        bind_combobox_keys(cx);
+
+        let bounds = Bounds::centered(None, size(px(800.), px(600.0)), cx);
+        cx.open_window(
+            WindowOptions {
+                window_bounds: Some(WindowBounds::Windowed(bounds)),
+                ..Default::default()
+            },
+            |window, cx| {
+                let view = cx.new(|cx| ComboboxStory::new(window, cx));
+                cx.new(|cx| Root::new(view.into(), window, cx))
+            },
+        )
+        .unwrap();
+    });
+}
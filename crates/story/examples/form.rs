@@ -0,0 +1,222 @@
+use std::time::Duration;
+
+use gpui::{Entity, Subscription};
+use rui::{
+    bind_select_keys, prelude::*, Button, Direction, Form, FormControl, FormEvent, FormField,
+    NumberInput, Select, SelectOption, Slider, Text, TextArea, TextInput,
+};
+
+const TAKEN_USERNAMES: [&str; 3] = ["admin", "root", "sandhope"];
+
+struct FormStory {
+    form: Entity<Form>,
+    direction: Direction,
+    dirty: bool,
+    status: SharedString,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl FormStory {
+    fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let username = cx.new(|cx| TextInput::new(cx).placeholder("Pick a username"));
+        let username = cx.new(|cx| {
+            FormField::new(username, window, cx)
+                .label("Username")
+                .help("Letters and digits, at least 3 of them.")
+                .required(true)
+                .validator(|name| match name.len() {
+                    0..3 => Err("Must be at least 3 characters".into()),
+                    _ if !name.chars().all(char::is_alphanumeric) => {
+                        Err("Only letters and digits".into())
+                    }
+                    _ => Ok(()),
+                })
+                // Simulates a server query, the validator runs on the background executor.
+                .async_validator(|name| async move {
+                    std::thread::sleep(Duration::from_millis(600));
+                    match TAKEN_USERNAMES.contains(&name.to_lowercase().as_str()) {
+                        true => Err(format!("{name} is already taken").into()),
+                        false => Ok(()),
+                    }
+                })
+        });
+
+        let email = cx.new(|cx| TextInput::new(cx).placeholder("name@example.com"));
+        let email = cx.new(|cx| {
+            FormField::new(email, window, cx)
+                .label("Email")
+                .required(true)
+                .validator(|email| match email.split_once('@') {
+                    Some((name, domain)) if !name.is_empty() && domain.contains('.') => Ok(()),
+                    _ => Err("Enter a valid email address".into()),
+                })
+        });
+
+        let age = cx.new(|cx| NumberInput::new(window, cx).integer().min(0.).max(150.));
+        let age = cx.new(|cx| {
+            FormField::new(age, window, cx)
+                .label("Age")
+                .help("Optional.")
+                .validator(|age| match age {
+                    Some(age) if *age < 13. => Err("You must be at least 13".into()),
+                    _ => Ok(()),
+                })
+        });
+
+        let country = cx.new(|cx| {
+            Select::new("country", cx)
+                .placeholder("Select a country")
+                .options(
+                    ["Canada", "France", "Germany", "Japan", "Norway"]
+                        .map(|country| SelectOption::new(SharedString::from(country), country)),
+                )
+        });
+        let country = cx.new(|cx| {
+            FormField::new(country, window, cx)
+                .label("Country")
+                .required(true)
+        });
+
+        let bio = cx.new(|cx| {
+            TextArea::new(cx)
+                .placeholder("Tell us about yourself")
+                .max_length(200)
+                .show_count(true)
+        });
+        let bio = cx.new(|cx| {
+            FormField::new(bio, window, cx)
+                .label("Bio")
+                .help("Shown on your profile.")
+        });
+
+        let volume = cx.new(|cx| Slider::new(cx).value(50.));
+        let volume = cx.new(|cx| FormField::new(volume, window, cx).label("Volume"));
+
+        let form = cx.new(|cx| {
+            Form::new(cx)
+                .field(username.clone())
+                .field(email.clone())
+                .field(age.clone())
+                .field(country.clone())
+                .field(bio.clone())
+                .field(volume.clone())
+                .on_submit(|_window, _cx| println!("submitted"))
+        });
+
+        let mut subscriptions = vec![
+            cx.observe(&form, |_, _, cx| cx.notify()),
+            cx.subscribe(&form, |this, _, event, cx| {
+                this.status = match event {
+                    FormEvent::Submit => "Saved".into(),
+                    FormEvent::Reset => SharedString::default(),
+                };
+                cx.notify();
+            }),
+        ];
+        subscriptions.extend([
+            Self::observe_dirty(&form, &username, cx),
+            Self::observe_dirty(&form, &email, cx),
+            Self::observe_dirty(&form, &age, cx),
+            Self::observe_dirty(&form, &country, cx),
+            Self::observe_dirty(&form, &bio, cx),
+            Self::observe_dirty(&form, &volume, cx),
+        ]);
+
+        Self {
+            form,
+            direction: Direction::Vertical,
+            dirty: false,
+            status: SharedString::default(),
+            _subscriptions: subscriptions,
+        }
+    }
+
+    /// Keeps the dirty state, shown by the reset button, up to date.
+    fn observe_dirty<C: FormControl>(
+        form: &Entity<Form>,
+        field: &Entity<FormField<C>>,
+        cx: &mut Context<Self>,
+    ) -> Subscription {
+        let form = form.clone();
+        cx.observe(field, move |this, _, cx| {
+            this.dirty = form.read(cx).is_dirty(cx);
+            cx.notify();
+        })
+    }
+}
+
+impl Render for FormStory {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let form = self.form.clone();
+        let submitting = form.read(cx).is_submitting();
+        let next_direction = match self.direction {
+            Direction::Vertical => Direction::Horizontal,
+            Direction::Horizontal => Direction::Vertical,
+        };
+
+        Col! {
+            Section! {
+                "Form";
+                Button::new("direction")
+                    .text(format!("Switch to {next_direction:?}"))
+                    .outline()
+                    .size(Size::Small)
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.direction = next_direction;
+                        this.form.update(cx, |form, cx| form.set_direction(next_direction, cx));
+                    }))
+
+                self.form.clone()
+
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Button::new("submit")
+                            .text(if submitting { "Saving..." } else { "Save" })
+                            .primary()
+                            .disabled(submitting)
+                            .on_click({
+                                let form = form.clone();
+                                move |_, window, cx| form.update(cx, |form, cx| form.submit(window, cx))
+                            }),
+                    )
+                    .child(
+                        Button::new("reset")
+                            .text("Reset")
+                            .outline()
+                            .disabled(!self.dirty)
+                            .on_click(move |_, _, cx| form.update(cx, |form, cx| form.reset(cx))),
+                    )
+                    .child(Text::new(self.status.clone()))
+            }
+            .w(px(560.))
+            .gap_4()
+        }
+        .p_4()
+    }
+}
+
+fn main() {
+    Application::new().with_assets(Assets).run(|cx: &mut App| {
+        cx.activate(true);
+        Theme::init(cx, None, None);
+        TextInput::bind_keys(cx);
+        TextArea::bind_keys(cx);
+        NumberInput::bind_keys(cx);
+        bind_select_keys(cx);
+        Slider::bind_keys(cx);
+
+        let bounds = Bounds::centered(None, size(px(800.), px(800.0)), cx);
+        cx.open_window(
+            WindowOptions {
+                window_bounds: Some(WindowBounds::Windowed(bounds)),
+                ..Default::default()
+            },
+            |window, cx| {
+                let view = cx.new(|cx| FormStory::new(window, cx));
+                cx.new(|cx| Root::new(view.into(), window, cx))
+            },
+        )
+        .unwrap();
+    });
+}
@@ -0,0 +1,177 @@
+use gpui::{Entity, Subscription};
+use rui::{
+    prelude::*, IconName, InputEvent, NumberFormat, NumberInput, Switch, Text, TextArea, TextInput,
+};
+
+struct InputStory {
+    name: Entity<TextInput>,
+    password: Entity<TextInput>,
+    search: Entity<TextInput>,
+    read_only: Entity<TextInput>,
+    disabled: Entity<TextInput>,
+    sizes: Vec<Entity<TextInput>>,
+    comment: Entity<TextArea>,
+    description: Entity<TextArea>,
+    quantity: Entity<NumberInput>,
+    price: Entity<NumberInput>,
+    submitted: SharedString,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl InputStory {
+    fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let name = cx.new(|cx| {
+            TextInput::new(cx)
+                .placeholder("Your name")
+                .on_change(|text, _window, _cx| println!("name: {text}"))
+        });
+        let password = cx.new(|cx| {
+            TextInput::new(cx)
+                .placeholder("Password")
+                .masked(true)
+                .suffix(IconName::EyeOff)
+        });
+        let search = cx.new(|cx| {
+            TextInput::new(cx)
+                .placeholder("Search and press enter")
+                .prefix(IconName::MagnifyingGlass)
+        });
+        let read_only = cx.new(|cx| {
+            TextInput::new(cx)
+                .default_text("Read-only text can be selected and copied")
+                .read_only(true)
+        });
+        let disabled = cx.new(|cx| TextInput::new(cx).default_text("Disabled").disabled(true));
+        let sizes = [Size::XSmall, Size::Small, Size::Medium, Size::Large]
+            .into_iter()
+            .map(|size| cx.new(|cx| TextInput::new(cx).placeholder("Size").size(size)))
+            .collect();
+
+        let comment = cx.new(|cx| {
+            TextArea::new(cx)
+                .placeholder("Leave a comment, it grows up to 6 rows")
+                .max_rows(6)
+                .max_length(280)
+                .show_count(true)
+        });
+        let description = cx.new(|cx| {
+            TextArea::new(cx)
+                .default_text("A read-only text area wraps long lines to its width.")
+                .min_rows(3)
+                .read_only(true)
+                .size(Size::Small)
+        });
+
+        let quantity = cx.new(|cx| {
+            NumberInput::new(window, cx)
+                .integer()
+                .min(0.)
+                .max(99.)
+                .value(1.)
+                .on_change(|value, _window, _cx| println!("quantity: {value}"))
+        });
+        let price = cx.new(|cx| {
+            NumberInput::new(window, cx)
+                .min(0.)
+                .step(0.25)
+                .precision(2)
+                .value(1234.5)
+                .format(NumberFormat::from_locale("de-DE"))
+                .size(Size::Small)
+        });
+
+        let subscriptions = vec![cx.subscribe(&search, |this, _, event, cx| {
+            if let InputEvent::Submit(text) = event {
+                this.submitted = text.clone();
+                cx.notify();
+            }
+        })];
+
+        Self {
+            name,
+            password,
+            search,
+            read_only,
+            disabled,
+            sizes,
+            comment,
+            description,
+            quantity,
+            price,
+            submitted: SharedString::default(),
+            _subscriptions: subscriptions,
+        }
+    }
+}
+
+impl Render for InputStory {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        Col! {
+            Switch::new("appearance")
+                .checked(cx.theme().appearance.is_light())
+                .text(cx.theme().appearance.to_string())
+                .on_click(cx.listener(|_, _v, window, cx| {
+                    cx.theme_mut().toggle_builtin_appearance(window);
+                }))
+
+            Section! {
+                "TextInput";
+                self.name.clone()
+                self.password.clone()
+                self.search.clone()
+                Text::new(format!("Submitted: {}", self.submitted))
+                self.read_only.clone()
+                self.disabled.clone()
+            }
+            .w_96()
+            .gap_2()
+
+            Section! {
+                "TextArea";
+                self.comment.clone()
+                self.description.clone()
+            }
+            .w_96()
+            .gap_2()
+
+            Section! {
+                "NumberInput";
+                self.quantity.clone()
+                self.price.clone()
+            }
+            .w_96()
+            .gap_2()
+
+            Section! {
+                "Sizes";
+                v_flex().gap_2().children(self.sizes.clone())
+            }
+            .w_96()
+        }
+        .p_4()
+        .gap_4()
+    }
+}
+
+fn main() {
+    Application::new().with_assets(Assets).run(|cx: &mut App| {
+        cx.activate(true);
+        Theme::init(cx, None, None);
+        TextInput::bind_keys(cx);
+        TextArea::bind_keys(cx);
+        NumberInput::bind_keys(cx);
+
+        let bounds = Bounds::centered(None, size(px(1024.), px(700.0)), cx);
+        cx.open_window(
+            WindowOptions {
+                window_bounds: Some(WindowBounds::Windowed(bounds)),
+                ..Default::default()
+            },
+            |window, cx| {
+                let view = cx.new(|cx| InputStory::new(window, cx));
+                cx.new(|cx| Root::new(view.into(), window, cx))
+            },
+        )
+        .unwrap();
+    });
+}
@@ -0,0 +1,199 @@
+use gpui::{actions, FocusHandle, KeyBinding, WeakEntity};
+use rui::{
+    bind_popover_keys, prelude::*, Button, ContextMenu, ContextMenuEntry, DropdownMenu, EdgeCorner,
+    IconName, RightClickMenu, Text, ToggleState,
+};
+
+actions!(menu_story, [NewFile, OpenFile, Save, CloseWindow]);
+
+struct MenuStory {
+    focus_handle: FocusHandle,
+    last_picked: SharedString,
+    word_wrap: bool,
+    minimap: bool,
+}
+
+impl MenuStory {
+    fn pick(&mut self, picked: &str, cx: &mut Context<Self>) {
+        self.last_picked = format!("Picked {picked}").into();
+        cx.notify();
+    }
+}
+
+/// Runs `f` on the story from a menu entry.
+fn update(
+    story: &WeakEntity<MenuStory>,
+    f: impl Fn(&mut MenuStory, &mut Context<MenuStory>) + 'static,
+) -> impl Fn(&mut Window, &mut App) + 'static {
+    let story = story.clone();
+    move |_, cx| {
+        story.update(cx, |story, cx| f(story, cx)).ok();
+    }
+}
+
+impl Render for MenuStory {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let story = cx.entity().downgrade();
+        let (word_wrap, minimap) = (self.word_wrap, self.minimap);
+
+        let file_menu = DropdownMenu::new(
+            "file-menu",
+            Button::new("file").text("File").outline(),
+            |menu, _, _| {
+                menu.entry(
+                    ContextMenuEntry::new("New File")
+                        .icon(IconName::File)
+                        .action(NewFile),
+                )
+                .entry(
+                    ContextMenuEntry::new("Open...")
+                        .icon(IconName::Folder)
+                        .action(OpenFile),
+                )
+                .entry(
+                    ContextMenuEntry::new("Open Recent")
+                        .icon(IconName::HistoryRerun)
+                        .submenu(|menu, _, _| {
+                            menu.header("Projects")
+                                .entry(ContextMenuEntry::new("rui"))
+                                .entry(ContextMenuEntry::new("gpui"))
+                                .entry(ContextMenuEntry::new("More").submenu(|menu, _, _| {
+                                    menu.entry(ContextMenuEntry::new("zed"))
+                                        .entry(ContextMenuEntry::new("story"))
+                                }))
+                        }),
+                )
+                .separator()
+                .entry(ContextMenuEntry::new("Save").action(Save))
+                .entry(ContextMenuEntry::new("Save As...").disabled(true))
+                .separator()
+                .action("Close Window", CloseWindow)
+            },
+        );
+
+        let view_menu = DropdownMenu::new(
+            "view-menu",
+            Button::new("view").text("View").outline(),
+            move |menu, _, _| {
+                menu.header("Editor")
+                    .entry(
+                        ContextMenuEntry::new("Word Wrap")
+                            .toggle(word_wrap)
+                            .key_hint("alt-z")
+                            .handler(update(&story, |story, cx| {
+                                story.word_wrap = !story.word_wrap;
+                                cx.notify();
+                            })),
+                    )
+                    .entry(
+                        ContextMenuEntry::new("Minimap")
+                            .toggle(minimap)
+                            .handler(update(&story, |story, cx| {
+                                story.minimap = !story.minimap;
+                                cx.notify();
+                            })),
+                    )
+                    .entry(
+                        ContextMenuEntry::new("Indent Guides")
+                            .toggle(ToggleState::Indeterminate)
+                            .disabled(true),
+                    )
+            },
+        )
+        .anchor(EdgeCorner::BottomRight);
+
+        let story = cx.entity().downgrade();
+        let area = RightClickMenu::new(
+            "area-menu",
+            div()
+                .size_full()
+                .h_40()
+                .flex()
+                .items_center()
+                .justify_center()
+                .border_1()
+                .border_dashed()
+                .border_color(cx.theme().colors.border)
+                .rounded_lg()
+                .child(Text::new("Right click here")),
+            move |menu, _, _| {
+                let entry = |label: &'static str, icon| {
+                    ContextMenuEntry::new(label)
+                        .icon(icon)
+                        .handler(update(&story, move |story, cx| story.pick(label, cx)))
+                };
+                menu.entry(entry("Copy", IconName::Copy))
+                    .entry(entry("Copy Link", IconName::Link))
+                    .entry(entry("Delete", IconName::Trash))
+                    .separator()
+                    .entry(ContextMenuEntry::new("Share").submenu({
+                        let story = story.clone();
+                        move |menu, _, _| {
+                            let entry = |label: &'static str| {
+                                ContextMenuEntry::new(label)
+                                    .handler(update(&story, move |story, cx| story.pick(label, cx)))
+                            };
+                            menu.entry(entry("Email")).entry(entry("Link"))
+                        }
+                    }))
+            },
+        );
+
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(|this, _: &NewFile, _, cx| this.pick("New File", cx)))
+            .on_action(cx.listener(|this, _: &OpenFile, _, cx| this.pick("Open", cx)))
+            .on_action(cx.listener(|this, _: &Save, _, cx| this.pick("Save", cx)))
+            .on_action(cx.listener(|this, _: &CloseWindow, _, cx| this.pick("Close Window", cx)))
+            .size_full()
+            .p_4()
+            .gap_4()
+            .child(Section! {
+                "Dropdown menus";
+                Row! {
+                    file_menu
+                    view_menu
+                }
+                .gap_2()
+            })
+            .child(Section! {
+                "Right click menu";
+                area
+            })
+            .child(Text::new(self.last_picked.clone()))
+    }
+}
+
+fn main() {
+    Application::new().with_assets(Assets).run(|cx: &mut App| {
+        cx.activate(true);
+        Theme::init(cx, None, None);
+        bind_popover_keys(cx);
+        ContextMenu::bind_keys(cx);
+        cx.bind_keys([
+            KeyBinding::new("ctrl-n", NewFile, None),
+            KeyBinding::new("ctrl-o", OpenFile, None),
+            KeyBinding::new("ctrl-s", Save, None),
+            KeyBinding::new("ctrl-w", CloseWindow, None),
+        ]);
+
+        let bounds = Bounds::centered(None, size(px(800.), px(600.0)), cx);
+        cx.open_window(
+            WindowOptions {
+                window_bounds: Some(WindowBounds::Windowed(bounds)),
+                ..Default::default()
+            },
+            |window, cx| {
+                let view = cx.new(|cx| MenuStory {
+                    focus_handle: cx.focus_handle(),
+                    last_picked: "Nothing picked yet".into(),
+                    word_wrap: true,
+                    minimap: false,
+                });
+                window.focus(&view.read(cx).focus_handle);
+                cx.new(|cx| Root::new(view.into(), window, cx))
+            },
+        )
+        .unwrap();
+    });
+}
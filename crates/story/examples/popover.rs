@@ -0,0 +1,105 @@
+use gpui::{DismissEvent, EventEmitter, FocusHandle, Focusable};
+use rui::{bind_popover_keys, prelude::*, Button, EdgeCorner, Popover, Position, Text};
+
+/// The content of the popovers, it closes itself from its button.
+struct Info {
+    focus_handle: FocusHandle,
+    title: SharedString,
+}
+
+impl Info {
+    fn new(title: impl Into<SharedString>, cx: &mut Context<Self>) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            title: title.into(),
+        }
+    }
+}
+
+impl EventEmitter<DismissEvent> for Info {}
+
+impl Focusable for Info {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for Info {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .w_64()
+            .p_3()
+            .gap_2()
+            .child(Text::new(self.title.clone()))
+            .child(Text::new(
+                "Press escape or click outside to close the popover.",
+            ))
+            .child(
+                Button::new("close")
+                    .text("Close")
+                    .size(Size::Small)
+                    .on_click(cx.listener(|_, _, _, cx| cx.emit(DismissEvent))),
+            )
+    }
+}
+
+fn popover(id: &'static str, text: &'static str) -> Popover<Info> {
+    Popover::new(id)
+        .trigger(Button::new(id).text(text).outline())
+        .content(move |_, cx| cx.new(|cx| Info::new(text, cx)))
+}
+
+struct PopoverStory {}
+
+impl Render for PopoverStory {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        Col! {
+            Section! {
+                "Anchors";
+                Row! {
+                    popover("bottom-left", "Bottom left")
+                    popover("bottom", "Bottom").anchor(EdgeCorner::Bottom)
+                    popover("bottom-right", "Bottom right").anchor(EdgeCorner::BottomRight)
+                    // There is no room above, it flips below.
+                    popover("top-left", "Top left").anchor(EdgeCorner::TopLeft)
+                }
+                .gap_2()
+            }
+
+            Section! {
+                "Positions with an arrow";
+                Row! {
+                    popover("top", "Top").position(Position::Top).arrow(true)
+                    popover("right", "Right").position(Position::Right).arrow(true)
+                    popover("below", "Bottom").position(Position::Bottom).arrow(true)
+                    popover("left", "Left").position(Position::Left).arrow(true)
+                }
+                .gap_2()
+            }
+        }
+        .p_4()
+        .gap_4()
+    }
+}
+
+fn main() {
+    Application::new().with_assets(Assets).run(|cx: &mut App| {
+        cx.activate(true);
+        Theme::init(cx, None, None);
+        bind_popover_keys(cx);
+
+        let bounds = Bounds::centered(None, size(px(800.), px(600.0)), cx);
+        cx.open_window(
+            WindowOptions {
+                window_bounds: Some(WindowBounds::Windowed(bounds)),
+                ..Default::default()
+            },
+            |window, cx| {
+                let view = cx.new(|_| PopoverStory {});
+                cx.new(|cx| Root::new(view.into(), window, cx))
+            },
+        )
+        .unwrap();
+    });
+}
@@ -1,8 +1,16 @@
-use rui::{prelude::*, Radio, RadioGroup, Root, Section, Text};
+use rui::{bind_radio_keys, prelude::*, Radio, RadioGroup, RadioOption, Root, Section};
+
+#[derive(Clone, Copy, PartialEq)]
+enum Plan {
+    Free,
+    Pro,
+    Team,
+}
 
 struct RadioStory {
     enabled: bool,
     selected_index: Option<usize>,
+    plan: Option<Plan>,
 }
 
 impl Render for RadioStory {
@@ -30,9 +38,14 @@ impl Render for RadioStory {
 
             Section! {
                 "Radio Group";
-                RadioGroup::new()
-                    .children(["One", "Two", "Three"])
-                    .selected_index(self.selected_index)
+                RadioGroup::new("numbers")
+                    .options(
+                        ["One", "Two", "Three"]
+                            .into_iter()
+                            .enumerate()
+                            .map(|(ix, label)| RadioOption::new(ix, label)),
+                    )
+                    .selected(self.selected_index)
                     .on_change(cx.listener(|this, selected_index: &usize, _, _| {
                         this.selected_index = Some(*selected_index);
                     }))
@@ -40,17 +53,35 @@ impl Render for RadioStory {
 
             Section! {
                 "Radio Group Vertical";
-                RadioGroup::new()
+                RadioGroup::new("numbers-vertical")
                     .direction_vertical()
                     .disabled(!self.enabled)
-                    .child(Radio::new("one1").text("one1"))
-                    .child(Radio::new("one2").text("one2"))
-                    .child(Radio::new("one3").text(Text::new("one3")))
-                    .selected_index(self.selected_index)
+                    .option(RadioOption::new(0, "one1"))
+                    .option(RadioOption::new(1, "one2").disabled(true))
+                    .option(RadioOption::new(2, "one3"))
+                    .selected(self.selected_index)
                     .on_change(cx.listener(|this, selected_index: &usize, _, _| {
                         this.selected_index = Some(*selected_index);
                     }))
             }
+
+            Section! {
+                "Radio Group Cards, required";
+                RadioGroup::new("plan")
+                    .cards()
+                    .required(true)
+                    .option(RadioOption::new(Plan::Free, "Free").description("For trying things out"))
+                    .option(RadioOption::new(Plan::Pro, "Pro").description("For professionals"))
+                    .option(
+                        RadioOption::new(Plan::Team, "Team")
+                            .description("Coming soon")
+                            .disabled(true),
+                    )
+                    .selected(self.plan)
+                    .on_change(cx.listener(|this, plan: &Plan, _, _| {
+                        this.plan = Some(*plan);
+                    }))
+            }
         }
         .justify_start()
         .items_start()
@@ -63,18 +94,20 @@ fn main() {
     Application::new().run(|cx: &mut App| {
         cx.activate(true);
         Theme::init(cx, None, None);
+        bind_radio_keys(cx);
         let bounds = Bounds::centered(None, size(px(1024.), px(700.0)), cx);
         cx.open_window(
             WindowOptions {
                 window_bounds: Some(WindowBounds::Windowed(bounds)),
                 ..Default::default()
             },
-            |_, cx| {
+            |window, cx| {
                 let view = cx.new(|_| RadioStory {
                     enabled: false,
                     selected_index: None,
+                    plan: None,
                 });
-                cx.new(|cx| Root::new(cx, view.into()))
+                cx.new(|cx| Root::new(view.into(), window, cx))
             },
         )
         .unwrap();
@@ -0,0 +1,166 @@
+use gpui::{Entity, Subscription};
+use rui::{
+    bind_select_keys, prelude::*, ButtonVariant, IconName, Select, SelectEvent, SelectGroup,
+    SelectOption, Text,
+};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Fruit {
+    Apple,
+    Banana,
+    Cherry,
+    Durian,
+}
+
+struct SelectStory {
+    fruit: Entity<Select<Fruit>>,
+    language: Entity<Select<SharedString>>,
+    assignee: Entity<Select<usize>>,
+    sizes: Vec<Entity<Select<usize>>>,
+    disabled: Entity<Select<usize>>,
+    selected_language: SharedString,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl SelectStory {
+    fn new(cx: &mut Context<Self>) -> Self {
+        let fruit = cx.new(|cx| {
+            Select::new("fruit", cx)
+                .placeholder("Pick a fruit")
+                .options([
+                    SelectOption::new(Fruit::Apple, "Apple"),
+                    SelectOption::new(Fruit::Banana, "Banana"),
+                    SelectOption::new(Fruit::Cherry, "Cherry"),
+                    SelectOption::new(Fruit::Durian, "Durian").disabled(true),
+                ])
+                .on_change(|fruit, _window, _cx| println!("fruit: {fruit:?}"))
+        });
+
+        let language = cx.new(|cx| {
+            let option = |name: &'static str| SelectOption::new(SharedString::from(name), name);
+            Select::new("language", cx)
+                .placeholder("Language")
+                .variant(ButtonVariant::Soft)
+                .group(SelectGroup::new("Systems").options([
+                    option("C"),
+                    option("Rust"),
+                    option("Zig"),
+                ]))
+                .group(SelectGroup::new("Scripting").options([
+                    option("JavaScript"),
+                    option("Lua"),
+                    option("Python"),
+                    option("Ruby"),
+                ]))
+                .selected("Rust".into())
+        });
+
+        let assignee = cx.new(|cx| {
+            Select::new("assignee", cx)
+                .placeholder("Assignee")
+                .option(SelectOption::new(0, "Nobody").icon(IconName::EyeOff))
+                .option(
+                    SelectOption::new(1, "Sandhope").avatar(SharedString::from("images/logo.jpg")),
+                )
+                .option(SelectOption::new(2, "Rust").render(|_window, cx| {
+                    v_flex()
+                        .child(Text::new("Rust"))
+                        .child(
+                            Text::new("rust-lang/rust")
+                                .text_xs()
+                                .color(cx.theme().colors.text_muted),
+                        )
+                        .into_any_element()
+                }))
+        });
+
+        let sizes = [Size::XSmall, Size::Small, Size::Medium, Size::Large]
+            .into_iter()
+            .enumerate()
+            .map(|(ix, size)| {
+                cx.new(|cx| {
+                    Select::new(("size", ix), cx)
+                        .placeholder(format!("{size:?}"))
+                        .size(size)
+                        .options((1..=3).map(|n| SelectOption::new(n, format!("Option {n}"))))
+                })
+            })
+            .collect();
+
+        let disabled = cx.new(|cx| {
+            Select::new("disabled", cx)
+                .placeholder("Disabled")
+                .disabled(true)
+                .option(SelectOption::new(0, "Unreachable"))
+        });
+
+        let subscriptions = vec![cx.subscribe(&language, |this, _, event, cx| {
+            let SelectEvent::Change(language) = event;
+            this.selected_language = language.clone();
+            cx.notify();
+        })];
+
+        Self {
+            fruit,
+            language,
+            assignee,
+            sizes,
+            disabled,
+            selected_language: "Rust".into(),
+            _subscriptions: subscriptions,
+        }
+    }
+}
+
+impl Render for SelectStory {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        Col! {
+            Section! {
+                "Select";
+                self.fruit.clone()
+                self.disabled.clone()
+            }
+            .gap_2()
+
+            Section! {
+                "Groups";
+                self.language.clone()
+                Text::new(format!("Selected: {}", self.selected_language))
+            }
+            .gap_2()
+
+            Section! {
+                "Custom options";
+                self.assignee.clone()
+            }
+
+            Section! {
+                "Sizes";
+                h_flex().gap_2().children(self.sizes.clone())
+            }
+        }
+        .p_4()
+        .gap_4()
+    }
+}
+
+fn main() {
+    Application::new().with_assets(Assets).run(|cx: &mut App| {
+        cx.activate(true);
+        Theme::init(cx, None, None);
+        bind_select_keys(cx);
+
+        let bounds = Bounds::centered(None, size(px(800.), px(600.0)), cx);
+        cx.open_window(
+            WindowOptions {
+                window_bounds: Some(WindowBounds::Windowed(bounds)),
+                ..Default::default()
+            },
+            |window, cx| {
+                let view = cx.new(SelectStory::new);
+                cx.new(|cx| Root::new(view.into(), window, cx))
+            },
+        )
+        .unwrap();
+    });
+}
@@ -0,0 +1,140 @@
+use gpui::{Entity, Subscription};
+use rui::{prelude::*, Slider, SliderEvent, SliderValue, Text};
+
+struct SliderStory {
+    volume: Entity<Slider>,
+    rating: Entity<Slider>,
+    price: Entity<Slider>,
+    vertical: Entity<Slider>,
+    vertical_range: Entity<Slider>,
+    disabled: Entity<Slider>,
+    sizes: Vec<Entity<Slider>>,
+    price_range: SliderValue,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl SliderStory {
+    fn new(cx: &mut Context<Self>) -> Self {
+        let volume = cx.new(|cx| {
+            Slider::new(cx)
+                .value(40.)
+                .on_change(|value, _window, _cx| println!("volume: {value:?}"))
+        });
+        let rating = cx.new(|cx| {
+            (1..=5).fold(
+                Slider::new(cx).min(1.).max(5.).step(1.).value(3.),
+                |slider, n| slider.tick_label(n as f32, format!("{n}")),
+            )
+        });
+        let price = cx.new(|cx| {
+            Slider::new(cx)
+                .max(1000.)
+                .step(10.)
+                .range(200., 600.)
+                .ticks([250., 500., 750.])
+                .tick_label(0., "$0")
+                .tick_label(1000., "$1000")
+                .format(|value| format!("${value}").into())
+        });
+        let vertical = cx.new(|cx| Slider::new(cx).vertical().value(70.));
+        let vertical_range = cx.new(|cx| {
+            Slider::new(cx)
+                .vertical()
+                .step(25.)
+                .range(25., 75.)
+                .tick_label(0., "Low")
+                .tick_label(50., "Mid")
+                .tick_label(100., "High")
+        });
+        let disabled = cx.new(|cx| Slider::new(cx).value(60.).disabled(true));
+        let sizes = [Size::XSmall, Size::Small, Size::Medium, Size::Large]
+            .into_iter()
+            .map(|size| cx.new(|cx| Slider::new(cx).value(50.).size(size)))
+            .collect();
+
+        let subscriptions = vec![cx.subscribe(&price, |this, _, event, cx| {
+            let SliderEvent::Change(value) = event;
+            this.price_range = *value;
+            cx.notify();
+        })];
+
+        Self {
+            volume,
+            rating,
+            price,
+            vertical,
+            vertical_range,
+            disabled,
+            sizes,
+            price_range: SliderValue::Range(200., 600.),
+            _subscriptions: subscriptions,
+        }
+    }
+}
+
+impl Render for SliderStory {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        let price_range = match self.price_range {
+            SliderValue::Range(start, end) => format!("${start} - ${end}"),
+            SliderValue::Single(value) => format!("${value}"),
+        };
+
+        Col! {
+            Section! {
+                "Slider";
+                self.volume.clone()
+                self.disabled.clone()
+            }
+            .w_96()
+            .gap_4()
+
+            Section! {
+                "Steps and ticks";
+                self.rating.clone()
+            }
+            .w_96()
+
+            Section! {
+                "Range";
+                self.price.clone()
+                Text::new(price_range)
+            }
+            .w_96()
+            .gap_2()
+
+            Section! {
+                "Vertical";
+                h_flex().h_48().gap_8().child(self.vertical.clone()).child(self.vertical_range.clone())
+            }
+
+            Section! {
+                "Sizes";
+                v_flex().gap_4().children(self.sizes.clone())
+            }
+            .w_96()
+        }
+        .p_4()
+        .gap_6()
+    }
+}
+
+fn main() {
+    Application::new().with_assets(Assets).run(|cx: &mut App| {
+        cx.activate(true);
+        Theme::init(cx, None, None);
+        Slider::bind_keys(cx);
+
+        let bounds = Bounds::centered(None, size(px(800.), px(700.0)), cx);
+        cx.open_window(
+            WindowOptions {
+                window_bounds: Some(WindowBounds::Windowed(bounds)),
+                ..Default::default()
+            },
+            |window, cx| {
+                let view = cx.new(SliderStory::new);
+                cx.new(|cx| Root::new(view.into(), window, cx))
+            },
+        )
+        .unwrap();
+    });
+}
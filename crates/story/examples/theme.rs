@@ -1,6 +1,6 @@
 use rui::{
-    prelude::*, Assets, IconName, Label, Radio, RadioGroup, Root, Row, Section, Switch, Text,
-    ThemeMode,
+    bind_radio_keys, prelude::*, Assets, Button, IconName, Label, RadioGroup, RadioOption, Root,
+    Row, Section, Switch, Text, ThemeMode, ThemeRegistry, ThemeSetting,
 };
 
 struct RadioStory {
@@ -23,6 +23,21 @@ impl Render for RadioStory {
             }
             .w_full()
 
+            Row! {
+                Text::new("Theme: ").w_1_4()
+            }
+            .children(ThemeRegistry::global(cx).families().iter().map(|family| {
+                let name = family.name.clone();
+                Button::new(name.clone())
+                    .text(name.clone())
+                    .outline()
+                    .on_click(move |_, _, cx| {
+                        ThemeSetting::set_theme_family(cx, &name).unwrap();
+                    })
+            }))
+            .gap_2()
+            .w_full()
+
             Row! {
                 Text::new("label with theme").w(relative(0.25))
                 Label::new(IconName::AiZed, "label")
@@ -31,9 +46,14 @@ impl Render for RadioStory {
 
             Section! {
                 "Radio Group";
-                RadioGroup::new()
-                    .children(["One", "Two", "Three"])
-                    .selected_index(self.selected_index)
+                RadioGroup::new("numbers")
+                    .options(
+                        ["One", "Two", "Three"]
+                            .into_iter()
+                            .enumerate()
+                            .map(|(ix, label)| RadioOption::new(ix, label)),
+                    )
+                    .selected(self.selected_index)
                     .on_change(cx.listener(|this, selected_index: &usize, _, _| {
                         this.selected_index = Some(*selected_index);
                     }))
@@ -58,13 +78,13 @@ impl Render for RadioStory {
 
             Section! {
                 "Radio Group Vertical";
-                RadioGroup::new()
+                RadioGroup::new("numbers-vertical")
                     .direction_vertical()
                     .disabled(!self.enabled)
-                    .child(Radio::new("one1").text("one1"))
-                    .child(Radio::new("one2").text("one2"))
-                    .child(Radio::new("one3").text(Text::new("one3")))
-                    .selected_index(self.selected_index)
+                    .option(RadioOption::new(0, "one1"))
+                    .option(RadioOption::new(1, "one2"))
+                    .option(RadioOption::new(2, "one3"))
+                    .selected(self.selected_index)
                     .on_change(cx.listener(|this, selected_index: &usize, _, _| {
                         this.selected_index = Some(*selected_index);
                     }))
@@ -81,18 +101,26 @@ fn main() {
     Application::new().with_assets(Assets).run(|cx: &mut App| {
         cx.activate(true);
         Theme::init(cx, None, Some(ThemeMode::Dark));
+        bind_radio_keys(cx);
         let bounds = Bounds::centered(None, size(px(1024.), px(700.0)), cx);
         cx.open_window(
             WindowOptions {
                 window_bounds: Some(WindowBounds::Windowed(bounds)),
                 ..Default::default()
             },
-            |_, cx| {
+            |window, cx| {
                 let view = cx.new(|_| RadioStory {
                     enabled: false,
                     selected_index: None,
                 });
-                cx.new(|cx| Root::new(cx, view.into()))
+                cx.new(|cx| {
+                    let mut root = Root::new(view.into(), window, cx);
+                    // e.g. `RUI_THEME_FILE=assets/themes/solarized_dark.json`
+                    if let Some(path) = std::env::var_os("RUI_THEME_FILE") {
+                        root.watch_theme_file(path, cx);
+                    }
+                    root
+                })
             },
         )
         .unwrap();
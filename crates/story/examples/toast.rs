@@ -1,5 +1,6 @@
 use gpui::{size, Application, Bounds, WindowBounds, WindowOptions};
-use rui::{prelude::*, Button, IconName, Root, StatusToast, Theme, ToastIcon};
+use rui::{prelude::*, Button, IconName, Root, StatusToast, Theme, ToastIcon, ToastPosition};
+use std::time::Duration;
 
 struct ToastStory {}
 
@@ -31,6 +32,45 @@ impl Render for ToastStory {
                     window.toggle_status_toast(cx, status_toast)
                 })
 
+            Row! {
+                Button::new("short-toast")
+                    .text("2 seconds")
+                    .on_click(|_, window, cx| {
+                        let toast = StatusToast::new("Saved", cx, |this, _cx| {
+                            this.icon(ToastIcon::new(IconName::Check))
+                                .with_duration(Some(Duration::from_secs(2)))
+                        });
+                        window.show_toast(cx, toast)
+                    })
+                Button::new("sticky-toast")
+                    .text("Sticky")
+                    .on_click(|_, window, cx| {
+                        let toast = StatusToast::new("Stays until dismissed", cx, |this, _cx| {
+                            this.with_duration(None).action("Dismiss", |_, _| {})
+                        });
+                        window.show_toast(cx, toast)
+                    })
+                Button::new("many-toasts")
+                    .text("Five toasts")
+                    .on_click(|_, window, cx| {
+                        for i in 1..=5 {
+                            let toast = StatusToast::new(format!("Toast {i}"), cx, |this, _cx| this);
+                            window.show_toast(cx, toast)
+                        }
+                    })
+            }
+            .gap_1()
+
+            Row! {
+                Button::new("top-left").text("Top left").on_click(|_, window, cx| window.set_toast_position(cx, ToastPosition::TopLeft))
+                Button::new("top-center").text("Top center").on_click(|_, window, cx| window.set_toast_position(cx, ToastPosition::TopCenter))
+                Button::new("top-right").text("Top right").on_click(|_, window, cx| window.set_toast_position(cx, ToastPosition::TopRight))
+                Button::new("bottom-left").text("Bottom left").on_click(|_, window, cx| window.set_toast_position(cx, ToastPosition::BottomLeft))
+                Button::new("bottom-center").text("Bottom center").on_click(|_, window, cx| window.set_toast_position(cx, ToastPosition::BottomCenter))
+                Button::new("bottom-right").text("Bottom right").on_click(|_, window, cx| window.set_toast_position(cx, ToastPosition::BottomRight))
+            }
+            .gap_1()
+
             Button::new("appearance")
                 .text(cx.theme().appearance.to_string())
                 .on_click(cx.listener(|_, _, window, cx| {
@@ -54,9 +94,9 @@ fn main() {
                 window_bounds: Some(WindowBounds::Windowed(bounds)),
                 ..Default::default()
             },
-            |_window, cx| {
+            |window, cx| {
                 let view = cx.new(|_cx| ToastStory {});
-                cx.new(|cx| Root::new(cx, view.into()))
+                cx.new(|cx| Root::new(view.into(), window, cx))
             },
         )
         .unwrap();